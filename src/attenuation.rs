@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use uom::si::f32::{ElectricalConductivity, Frequency};
+use waves_core::attenuation::{calculate_attenuation_coefficient, Attenuation};
+
+use crate::attenuation_ui::{attenuation_text_listener, update_attenuation_label};
+
+/// conductor the em wave travels through, α follows from it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LossyMedium {
+    pub conductivity: ElectricalConductivity,
+    /// real part εᵣ, the imaginary part is σ/(ωε₀)
+    pub relative_permittivity: f32,
+}
+
+/// attenuation entered in the form
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AttenuationInput {
+    /// α is only used if there's no lossy medium
+    pub attenuation: Attenuation,
+    /// em wave only
+    pub lossy_medium: Option<LossyMedium>,
+}
+
+impl AttenuationInput {
+    /// α and γ, entered directly
+    pub fn mechanical() -> Self {
+        AttenuationInput {
+            attenuation: Attenuation::default(),
+            lossy_medium: None,
+        }
+    }
+
+    /// α derived from conductivity and permittivity, starting with a lossless medium
+    pub fn electromagnetic() -> Self {
+        AttenuationInput {
+            attenuation: Attenuation::default(),
+            lossy_medium: Some(LossyMedium {
+                conductivity: ElectricalConductivity::default(),
+                relative_permittivity: 1.0,
+            }),
+        }
+    }
+
+    /// attenuation of a wave with the given frequency
+    pub fn at(&self, frequency: Frequency) -> Attenuation {
+        match self.lossy_medium {
+            Some(medium) => Attenuation {
+                alpha: calculate_attenuation_coefficient(
+                    frequency,
+                    medium.conductivity,
+                    medium.relative_permittivity,
+                ),
+                ..self.attenuation
+            },
+            None => self.attenuation,
+        }
+    }
+}
+
+/// the scenes add the inputs to their form, see add_attenuation_inputs
+pub fn add_attenuation_system(app: &mut App, input: AttenuationInput) {
+    app.insert_resource(input).add_systems(
+        Update,
+        (attenuation_text_listener, update_attenuation_label),
+    );
+}
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{
+    electrical_conductivity::siemens_per_meter,
+    f32::{ElectricalConductivity, Frequency, ReciprocalLength},
+    frequency::hertz,
+    length::meter,
+    reciprocal_length::reciprocal_meter,
+};
+
+use crate::{
+    attenuation::AttenuationInput,
+    medium::MediumInput,
+    wave_ui::{generate_input_box, generate_label, parse_float, Freq, WaveLength},
+};
+
+/// conductivity and permittivity are only added for the em wave, α only for mechanical waves
+#[derive(Resource)]
+pub struct AttenuationUiInputEntities {
+    pub alpha: Option<Entity>,
+    pub gamma: Entity,
+    pub conductivity: Option<Entity>,
+    pub relative_permittivity: Option<Entity>,
+}
+
+#[derive(Component, Default)]
+pub struct AlphaInputMarker;
+#[derive(Component, Default)]
+pub struct GammaInputMarker;
+#[derive(Component, Default)]
+pub struct ConductivityInputMarker;
+#[derive(Component, Default)]
+pub struct RelativePermittivityInputMarker;
+#[derive(Component, Default)]
+pub struct AttenuationInfoMarker;
+
+/// adds the attenuation inputs to a form, for an em wave α is derived from σ and εᵣ
+pub fn add_attenuation_inputs(
+    commands: &mut Commands,
+    root_id: Entity,
+    font: &Handle<Font>,
+    input: &AttenuationInput,
+) {
+    let (alpha, conductivity, relative_permittivity) = match input.lossy_medium {
+        Some(medium) => {
+            let conductivity = generate_input_box(
+                font,
+                root_id,
+                commands,
+                "Conductivity (S/m)",
+                ConductivityInputMarker,
+                medium.conductivity.get::<siemens_per_meter>().to_string(),
+            );
+            let relative_permittivity = generate_input_box(
+                font,
+                root_id,
+                commands,
+                "Permittivity εᵣ",
+                RelativePermittivityInputMarker,
+                medium.relative_permittivity.to_string(),
+            );
+            (None, Some(conductivity), Some(relative_permittivity))
+        }
+        None => {
+            let alpha = generate_input_box(
+                font,
+                root_id,
+                commands,
+                "Absorption α (1/m)",
+                AlphaInputMarker,
+                input
+                    .attenuation
+                    .alpha
+                    .get::<reciprocal_meter>()
+                    .to_string(),
+            );
+            (Some(alpha), None, None)
+        }
+    };
+    let gamma = generate_input_box(
+        font,
+        root_id,
+        commands,
+        "Damping γ (1/s)",
+        GammaInputMarker,
+        input.attenuation.gamma.get::<hertz>().to_string(),
+    );
+
+    let info_label = generate_label(font, "");
+    let spawned_info_label = commands.spawn((AttenuationInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(AttenuationUiInputEntities {
+        alpha,
+        gamma,
+        conductivity,
+        relative_permittivity,
+    });
+}
+
+pub fn attenuation_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Option<Res<AttenuationUiInputEntities>>,
+    mut input: ResMut<AttenuationInput>,
+) {
+    let Some(input_entities) = input_entities else {
+        return;
+    };
+    for event in events.read() {
+        let entity = Some(event.entity);
+        let value = match parse_float(&event.value) {
+            Ok(value) if value >= 0.0 => value,
+            Ok(value) => {
+                println!("error: attenuation can't be negative: {}", value);
+                continue;
+            }
+            Err(err) => {
+                println!("error: {}", err);
+                continue;
+            }
+        };
+        if entity == input_entities.alpha {
+            input.attenuation.alpha = ReciprocalLength::new::<reciprocal_meter>(value);
+        } else if event.entity == input_entities.gamma {
+            input.attenuation.gamma = Frequency::new::<hertz>(value);
+        } else if entity == input_entities.conductivity {
+            if let Some(medium) = &mut input.lossy_medium {
+                medium.conductivity = ElectricalConductivity::new::<siemens_per_meter>(value);
+            }
+        } else if entity == input_entities.relative_permittivity {
+            if let Some(medium) = &mut input.lossy_medium {
+                medium.relative_permittivity = value;
+            }
+        }
+    }
+}
+
+/// α at the frequency that's drawn and the skin depth 1/α
+pub fn update_attenuation_label(
+    input: Res<AttenuationInput>,
+    medium: Res<MediumInput>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+    mut label_query: Query<&mut Text, With<AttenuationInfoMarker>>,
+) {
+    let (Ok(wave_length), Ok(frequency)) = (wave_length.get_single(), frequency.get_single())
+    else {
+        return;
+    };
+    let attenuation = input.at(medium.frequency(wave_length.0, frequency.0));
+    let text = format!(
+        "α: {:e} 1/m\nskin depth: {}",
+        attenuation.alpha.get::<reciprocal_meter>(),
+        attenuation
+            .skin_depth()
+            .map(|depth| format!("{:e} m", depth.get::<meter>()))
+            .unwrap_or_else(|| "∞".to_owned())
+    );
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{electromagnetic_wave_ui::PolarityInput, sample::SampleArgs};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Simulations using the bevy game engine")]
+pub struct Cli {
+    /// same as run with default arguments if not set
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// opens the simulation window
+    Run(RunArgs),
+    /// writes wave samples to csv or json, without opening a window
+    Sample(SampleArgs),
+}
+
+#[derive(Args, Debug, Default)]
+pub struct RunArgs {
+    /// em-wave if not set, or the scene of the preset
+    #[arg(long, value_enum)]
+    pub scene: Option<Scene>,
+    /// ron or toml file with the starting parameters, applied before the other options
+    #[arg(long)]
+    pub preset: Option<PathBuf>,
+    /// draws a grid behind 2d scenes
+    #[arg(long)]
+    pub grid: bool,
+    /// initial amplitude, m for mechanical waves, V/m for the electromagnetic wave
+    #[arg(long)]
+    pub amplitude: Option<f32>,
+    /// initial wave length (m)
+    #[arg(long)]
+    pub wave_length: Option<f32>,
+    /// initial frequency (hz)
+    #[arg(long)]
+    pub frequency: Option<f32>,
+    /// initial phase (rad)
+    #[arg(long, allow_negative_numbers = true)]
+    pub phase: Option<f32>,
+    /// initial polarity of the electromagnetic wave
+    #[arg(long, value_enum)]
+    pub polarity: Option<PolarityArg>,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Scene {
+    /// electromagnetic wave in 3d
+    #[default]
+    EmWave,
+    /// mechanical wave and superposition in 2d
+    #[value(name = "wave-2d")]
+    #[serde(rename = "wave_2d")]
+    Wave2d,
+    /// standing wave on a string in 2d
+    StandingWave,
+    /// string solved with finite differences from an initial shape, in 2d
+    VibratingString,
+    /// coherent sources or slits with their interference pattern, in 2d
+    Interference,
+    /// fourier series of a periodic waveform, built up harmonic by harmonic, in 2d
+    Fourier,
+    /// membrane or ripple tank solved with finite differences, as a 3d surface
+    Membrane,
+    /// some basic functions in 2d
+    #[value(name = "curves-2d")]
+    #[serde(rename = "curves_2d")]
+    Curves2d,
+    /// some basic functions in 3d
+    #[value(name = "curves-3d")]
+    #[serde(rename = "curves_3d")]
+    Curves3d,
+}
+
+impl Scene {
+    pub fn is_2d(&self) -> bool {
+        match self {
+            Scene::Wave2d
+            | Scene::StandingWave
+            | Scene::VibratingString
+            | Scene::Interference
+            | Scene::Fourier
+            | Scene::Curves2d => true,
+            Scene::EmWave | Scene::Membrane | Scene::Curves3d => false,
+        }
+    }
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PolarityArg {
+    Planar,
+    Circular,
+    Elliptical,
+}
+
+impl From<PolarityArg> for PolarityInput {
+    fn from(p: PolarityArg) -> Self {
+        match p {
+            PolarityArg::Planar => PolarityInput::Planar,
+            PolarityArg::Circular => PolarityInput::Circular,
+            PolarityArg::Elliptical => PolarityInput::Elliptical,
+        }
+    }
+}
+
+impl From<PolarityInput> for PolarityArg {
+    fn from(p: PolarityInput) -> Self {
+        match p {
+            PolarityInput::Planar => PolarityArg::Planar,
+            PolarityInput::Circular => PolarityArg::Circular,
+            PolarityInput::Elliptical => PolarityArg::Elliptical,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::{
+    clock_ui::{
+        clock_text_listener, faster_button_handler, listen_clock_ui_inputs, pause_button_handler,
+        reverse_button_handler, setup_clock_ui, slower_button_handler, step_button_handler,
+        time_slider_handler, update_clock_label, update_time_slider, ClockUiEvent,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// wall clock duration of a single step, one frame at 60 fps
+pub const STEP_SECONDS: f32 = 1.0 / 60.0;
+
+/// time the draw systems use instead of bevy's Time, so the animation can be paused, stepped,
+/// slowed down, reversed and scrubbed
+/// elapsed is the animation time, DisplayScale maps it to physical time
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SimulationClock {
+    /// s
+    pub elapsed: f32,
+    /// simulated seconds per wall clock second, negative runs backwards
+    pub rate: f32,
+    pub paused: bool,
+    /// the time slider spans [0, scrub_range] s
+    pub scrub_range: f32,
+    /// advance by one step in the next frame, even if paused
+    step_requested: bool,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        SimulationClock {
+            elapsed: 0.0,
+            rate: 1.0,
+            paused: false,
+            scrub_range: 20.0,
+            step_requested: false,
+        }
+    }
+}
+
+impl SimulationClock {
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// advances by one frame at the current rate, pauses the clock
+    pub fn step(&mut self) {
+        self.paused = true;
+        self.step_requested = true;
+    }
+
+    pub fn reverse(&mut self) {
+        self.rate = -self.rate;
+    }
+
+    /// jumps to t and pauses, to look at a specific moment
+    pub fn set_elapsed(&mut self, elapsed: f32) {
+        self.elapsed = elapsed;
+        self.paused = true;
+    }
+}
+
+pub fn add_clock_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.add_event::<ClockUiEvent>()
+        .init_resource::<SimulationClock>()
+        .add_systems(PreUpdate, advance_clock)
+        .add_systems(Startup, setup_clock_ui)
+        .add_systems(
+            Update,
+            (
+                pause_button_handler,
+                step_button_handler,
+                reverse_button_handler,
+                slower_button_handler,
+                faster_button_handler,
+                listen_clock_ui_inputs,
+                clock_text_listener,
+                time_slider_handler,
+                update_time_slider,
+                update_clock_label,
+            ),
+        );
+}
+
+fn advance_clock(time: Res<Time>, mut clock: ResMut<SimulationClock>) {
+    if clock.step_requested {
+        clock.step_requested = false;
+        clock.elapsed += STEP_SECONDS * clock.rate;
+    } else if !clock.paused {
+        clock.elapsed += time.delta_seconds() * clock.rate;
+    }
+}
@@ -0,0 +1,341 @@
+use bevy::{
+    color::palettes::css::{BLACK, GRAY, GREEN, WHITE},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::time::second;
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::DisplayScale,
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float},
+};
+
+/// slowest and fastest playback rate reachable with the buttons
+const MIN_RATE: f32 = 1.0 / 64.0;
+const MAX_RATE: f32 = 64.0;
+
+#[derive(Resource)]
+pub struct ClockUiInputEntities {
+    pub time: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct PauseMarker;
+#[derive(Component, Default)]
+pub struct StepMarker;
+#[derive(Component, Default)]
+pub struct ReverseMarker;
+#[derive(Component, Default)]
+pub struct SlowerMarker;
+#[derive(Component, Default)]
+pub struct FasterMarker;
+#[derive(Component, Default)]
+pub struct ClockTimeInputMarker;
+#[derive(Component, Default)]
+pub struct ClockLabelMarker;
+#[derive(Component, Default)]
+pub struct TimeSliderMarker;
+#[derive(Component, Default)]
+pub struct TimeSliderHandleMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum ClockUiEvent {
+    TogglePause,
+    Step,
+    Reverse,
+    /// halves the rate
+    Slower,
+    /// doubles the rate
+    Faster,
+}
+
+/// adds the playback controls, bottom, next to the preset panel
+pub fn setup_clock_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            bottom: Val::Px(0.0),
+            left: Val::Px(220.0),
+            width: Val::Px(300.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let clock_label = generate_label(&font, "");
+    let spawned_clock_label = commands.spawn((ClockLabelMarker, clock_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_clock_label]);
+
+    let buttons = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                width: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    commands.entity(root_id).push_children(&[buttons]);
+
+    add_button(&mut commands, buttons, &font, "Pause", PauseMarker);
+    add_button(&mut commands, buttons, &font, "Step", StepMarker);
+    add_button(&mut commands, buttons, &font, "Rev", ReverseMarker);
+    add_button(&mut commands, buttons, &font, "×½", SlowerMarker);
+    add_button(&mut commands, buttons, &font, "×2", FasterMarker);
+
+    let slider = commands
+        .spawn((
+            TimeSliderMarker,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(16.0),
+                    margin: UiRect::vertical(Val::Px(5.0)),
+                    ..default()
+                },
+                background_color: GRAY.into(),
+                ..default()
+            },
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+        ))
+        .id();
+    let handle = commands
+        .spawn((
+            TimeSliderHandleMarker,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(0.0),
+                    width: Val::Px(6.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: WHITE.into(),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(slider).push_children(&[handle]);
+    commands.entity(root_id).push_children(&[slider]);
+
+    let time_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Jump to t (s)",
+        ClockTimeInputMarker,
+        "0".to_owned(),
+    );
+
+    commands.insert_resource(ClockUiInputEntities { time: time_input });
+}
+
+#[allow(clippy::type_complexity)]
+pub fn pause_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<PauseMarker>),
+    >,
+    mut my_events: EventWriter<ClockUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        clock_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ClockUiEvent::TogglePause,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn step_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<StepMarker>),
+    >,
+    mut my_events: EventWriter<ClockUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        clock_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ClockUiEvent::Step,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn reverse_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ReverseMarker>),
+    >,
+    mut my_events: EventWriter<ClockUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        clock_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ClockUiEvent::Reverse,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn slower_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<SlowerMarker>),
+    >,
+    mut my_events: EventWriter<ClockUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        clock_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ClockUiEvent::Slower,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn faster_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<FasterMarker>),
+    >,
+    mut my_events: EventWriter<ClockUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        clock_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ClockUiEvent::Faster,
+        );
+    }
+}
+
+fn clock_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<ClockUiEvent>,
+    event: ClockUiEvent,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(event);
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+pub fn listen_clock_ui_inputs(
+    mut events: EventReader<ClockUiEvent>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    for event in events.read() {
+        match event {
+            ClockUiEvent::TogglePause => clock.toggle_pause(),
+            ClockUiEvent::Step => clock.step(),
+            ClockUiEvent::Reverse => clock.reverse(),
+            // a rate of 0 becomes MIN_RATE, signum keeps the direction of a reversed −0
+            ClockUiEvent::Slower => {
+                clock.rate =
+                    (clock.rate.abs() / 2.0).clamp(MIN_RATE, MAX_RATE) * clock.rate.signum()
+            }
+            ClockUiEvent::Faster => {
+                clock.rate =
+                    (clock.rate.abs() * 2.0).clamp(MIN_RATE, MAX_RATE) * clock.rate.signum()
+            }
+        }
+    }
+}
+
+/// jumps to the submitted time
+pub fn clock_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<ClockUiInputEntities>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.time {
+            match parse_float(&event.value) {
+                Ok(t) => clock.set_elapsed(t),
+                Err(err) => println!("error: {}", err),
+            }
+        }
+    }
+}
+
+/// sets the time while the slider is pressed, i.e. also while dragging
+pub fn time_slider_handler(
+    slider_query: Query<(&Interaction, &RelativeCursorPosition), With<TimeSliderMarker>>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    for (interaction, cursor) in &slider_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            let range = clock.scrub_range;
+            clock.set_elapsed(position.x.clamp(0.0, 1.0) * range);
+        }
+    }
+}
+
+/// moves the handle to the current time, wrapping around at the end of the slider
+pub fn update_time_slider(
+    clock: Res<SimulationClock>,
+    mut handle_query: Query<&mut Style, With<TimeSliderHandleMarker>>,
+) {
+    let fraction = clock.elapsed.rem_euclid(clock.scrub_range) / clock.scrub_range;
+    for mut style in &mut handle_query {
+        style.left = Val::Percent(fraction * 100.0);
+    }
+}
+
+/// shows the animation time and, for scenes with a display scale, the physical time
+pub fn update_clock_label(
+    clock: Res<SimulationClock>,
+    display_scale: Option<Res<DisplayScale>>,
+    mut label_query: Query<&mut Text, With<ClockLabelMarker>>,
+) {
+    let display_scale_changed = display_scale.as_ref().is_some_and(|s| s.is_changed());
+    if !clock.is_changed() && !display_scale_changed {
+        return;
+    }
+    let physical = match &display_scale {
+        Some(display_scale) => format!(
+            "  ({:e} s)",
+            display_scale.physical_time(clock.elapsed).get::<second>()
+        ),
+        None => "".to_owned(),
+    };
+    for mut label in &mut label_query {
+        label.sections[0].value = format!(
+            "t: {:.3} s{}  rate: ×{}{}",
+            clock.elapsed,
+            physical,
+            clock.rate,
+            if clock.paused { "  (paused)" } else { "" }
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use waves_core::expression::{parse_parameters, Expression};
+
+use crate::{
+    curve_ui::{curve_text_listener, listen_curve_ui_inputs, setup_curve_ui, CurveUiInputs},
+    wave_ui::add_text_input_plugin,
+};
+
+/// the variables every curve can use, the parameters come after them
+pub const CURVE_VARIABLES: [&str; 2] = ["x", "t"];
+
+/// the function the curves scenes plot, as entered in the form
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Curve {
+    /// f(x, t) with the parameters
+    pub expression: Expression,
+    /// in the order they were entered
+    pub parameters: Vec<(String, f32)>,
+    pub x_start: f32,
+    pub x_end: f32,
+}
+
+impl Curve {
+    /// parses the function with the parameters, e.g. "a*sin(k*x - t)" with "a = 2; k = pi"
+    pub fn parse(
+        function: &str,
+        parameters: &str,
+        x_start: f32,
+        x_end: f32,
+    ) -> Result<Curve, String> {
+        if x_end <= x_start {
+            return Err("x to must be greater than x from".to_owned());
+        }
+        let parameters = parse_parameters(parameters, &CURVE_VARIABLES)?;
+        let mut names = CURVE_VARIABLES.to_vec();
+        names.extend(parameters.iter().map(|(name, _)| name.as_str()));
+        Ok(Curve {
+            expression: Expression::parse(function, &names)?,
+            parameters,
+            x_start,
+            x_end,
+        })
+    }
+
+    /// f(x) at time t (s)
+    pub fn at(&self, t: f32) -> impl FnMut(f32) -> f32 + '_ {
+        let mut values = vec![0.0, t];
+        values.extend(self.parameters.iter().map(|(_, value)| *value));
+        move |x| {
+            values[0] = x;
+            self.expression.evaluate(&values)
+        }
+    }
+}
+
+/// a form to type the function a curves scene plots, starting with `curve`
+pub fn add_curve_system(app: &mut App, curve: Curve) {
+    add_text_input_plugin(app);
+    app.insert_resource(CurveUiInputs::from(&curve))
+        .insert_resource(curve)
+        .add_systems(Startup, setup_curve_ui)
+        .add_systems(Update, (curve_text_listener, listen_curve_ui_inputs));
+}
@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInputSubmitEvent;
+
+use crate::{
+    curve::Curve,
+    wave_ui::{add_label, add_warning_label, generate_input_box, parse_float, WarningMarker},
+};
+
+#[derive(Resource)]
+pub struct CurveUiInputs {
+    pub function: String,
+    pub parameters: String,
+    pub x_start: String,
+    pub x_end: String,
+}
+
+impl From<&Curve> for CurveUiInputs {
+    fn from(curve: &Curve) -> Self {
+        CurveUiInputs {
+            function: curve.expression.to_string(),
+            parameters: curve
+                .parameters
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("; "),
+            x_start: curve.x_start.to_string(),
+            x_end: curve.x_end.to_string(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CurveUiInputEntities {
+    pub function: Entity,
+    pub parameters: Entity,
+    pub x_start: Entity,
+    pub x_end: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct CurveFunctionInputMarker;
+#[derive(Component, Default)]
+pub struct CurveParametersInputMarker;
+#[derive(Component, Default)]
+pub struct CurveXStartInputMarker;
+#[derive(Component, Default)]
+pub struct CurveXEndInputMarker;
+
+/// adds ui to type the function and its parameters
+pub fn setup_curve_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<CurveUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let function_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "f(x, t)",
+        CurveFunctionInputMarker,
+        form_state.function.clone(),
+    );
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "t in s, pi, e,\n+ - * / ^, sin,\ncos, exp, ln,\nsqrt, abs, ...",
+    );
+    let parameters_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Parameters",
+        CurveParametersInputMarker,
+        form_state.parameters.clone(),
+    );
+    add_label(&mut commands, root_id, &font, "e.g. a = 2; k = pi");
+    let x_start_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "x from",
+        CurveXStartInputMarker,
+        form_state.x_start.clone(),
+    );
+    let x_end_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "x to",
+        CurveXEndInputMarker,
+        form_state.x_end.clone(),
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    commands.insert_resource(CurveUiInputEntities {
+        function: function_input,
+        parameters: parameters_input,
+        x_start: x_start_input,
+        x_end: x_end_input,
+    });
+}
+
+pub fn curve_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<CurveUiInputs>,
+    input_entities: Res<CurveUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.function {
+            inputs.function = event.value.clone();
+        } else if event.entity == input_entities.parameters {
+            inputs.parameters = event.value.clone();
+        } else if event.entity == input_entities.x_start {
+            inputs.x_start = event.value.clone();
+        } else if event.entity == input_entities.x_end {
+            inputs.x_end = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the curve, showing errors in the warning label
+/// the last valid curve stays on screen until the error is fixed
+pub fn listen_curve_ui_inputs(
+    inputs: Res<CurveUiInputs>,
+    mut curve: ResMut<Curve>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let warning = match parse_curve_inputs(&inputs) {
+        Ok(parsed) => {
+            curve.set_if_neq(parsed);
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_curve_inputs(inputs: &CurveUiInputs) -> Result<Curve, String> {
+    Curve::parse(
+        &inputs.function,
+        &inputs.parameters,
+        parse_float(&inputs.x_start)?,
+        parse_float(&inputs.x_end)?,
+    )
+}
@@ -1,38 +1,46 @@
 use bevy::prelude::*;
 
-/// some basic 2d functions
-#[allow(dead_code)]
-pub fn add_curves_2d_system(app: &mut App) {
-    // app.add_systems(Update, draw_square_fn);
-    app.add_systems(Update, draw_sin_fn);
-}
+use crate::{
+    clock::SimulationClock,
+    curve::{add_curve_system, Curve},
+    display_scale::samples,
+};
 
-/// x^2
-#[allow(dead_code)]
-fn draw_square_fn(gizmos: Gizmos) {
-    draw_fn(gizmos, -10, 10, |x| x * x);
+/// functions of x and t typed by the user, in 2d
+pub fn add_curves_2d_system(app: &mut App) {
+    let curve = Curve::parse("sin(x - t)", "", -10.0, 10.0).expect("default curve is valid");
+    add_curve_system(app, curve);
+    app.add_systems(Update, draw_curve);
 }
 
-/// sin(x)
-fn draw_sin_fn(gizmos: Gizmos, time: Res<Time>) {
-    // println!("drawsign t: {}", t);
-    let t = time.elapsed_seconds();
-    // draw_fn(gizmos, -10, 10, |x| x.sin());
-    // animate movement
-    draw_fn(gizmos, -10 + t as i32, 10 + t as i32, |x| x.sin());
+/// the typed function, animated with the clock
+fn draw_curve(mut gizmos: Gizmos, clock: Res<SimulationClock>, curve: Res<Curve>) {
+    draw_fn(
+        &mut gizmos,
+        curve.x_start,
+        curve.x_end,
+        curve.at(clock.elapsed),
+    );
 }
 
 /// draws function as a line, i.e. draws a line between each 2 consecutive points
-fn draw_fn(mut gizmos: Gizmos, range_start: i32, range_end: i32, function: fn(f32) -> f32) {
+/// points where the function isn't finite leave a gap
+fn draw_fn<F>(gizmos: &mut Gizmos, range_start: f32, range_end: f32, mut function: F)
+where
+    F: FnMut(f32) -> f32,
+{
     let scaling = 20.0;
     let x_scaling = scaling;
     let y_scaling = scaling;
 
     let mut last_point = None;
 
-    for i in range_start..range_end {
-        let x = i as f32;
+    for x in samples(range_start, range_end) {
         let y = function(x);
+        if !y.is_finite() {
+            last_point = None;
+            continue;
+        }
 
         if let Some((last_x, last_y)) = last_point {
             gizmos.line_2d(
@@ -1,64 +1,65 @@
-use crate::functions::draw_line_fn;
+use crate::{
+    clock::SimulationClock,
+    curve::{add_curve_system, Curve},
+    display_scale::{samples, DisplayScale},
+    functions::draw_line_fn,
+};
 use bevy::{color::palettes::css::WHITE, prelude::*};
 
-/// some basic 2d functions in a 3d space
-#[allow(dead_code)]
+/// functions of x and t typed by the user, in a 3d space
 pub fn add_curves_3d_system(app: &mut App) {
-    app.add_systems(Update, draw_square_fn);
+    let curve = Curve::parse("x^2", "", -10.0, 10.0).expect("default curve is valid");
+    add_curve_system(app, curve);
+    app.add_systems(Update, draw_curve);
     // app.add_systems(Update, draw_sin_as_vert_vecs);
 }
 
-/// x^2
-#[allow(dead_code)]
-fn draw_square_fn(mut gizmos: Gizmos) {
-    draw_line_fn(&mut gizmos, -10, 10, 1, 0.2, |x| x * x);
-}
-
-/// sin(x)
-#[allow(dead_code)]
-fn draw_sin_fn(mut gizmos: Gizmos, _time: Res<Time>) {
-    draw_line_fn(&mut gizmos, -10, 10, 1, 0.2, |x| x.sin());
-    // animate
-    // let t = time.elapsed_seconds();
-    // draw_fn(gizmos, -10 + t as i32, 10 + t as i32, |x| x.sin());
+/// the typed function, animated with the clock
+fn draw_curve(mut gizmos: Gizmos, clock: Res<SimulationClock>, curve: Res<Curve>) {
+    draw_line_fn(
+        &mut gizmos,
+        curve.x_start,
+        curve.x_end,
+        0.2,
+        curve.at(clock.elapsed),
+    );
 }
 
-/// like draw_sin_fn, but each point is drawn as a vector, with origin at the perpendicular axis
+/// sin(x) like draw_curve, but each point is drawn as a vector, with origin at the perpendicular
+/// axis
 #[allow(dead_code)]
-fn draw_sin_as_vert_vecs(mut gizmos: Gizmos, _time: Res<Time>) {
-    let range = 20;
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, WHITE, |x| Vec3 {
-        x: 0.0,
-        y: 0.0,
-        z: x.sin(),
+fn draw_sin_as_vert_vecs(mut gizmos: Gizmos, _clock: Res<SimulationClock>) {
+    let range = 20.0;
+    let display_scale = DisplayScale::default_3d();
+    draw_planar_fn_as_vert_vecs(&mut gizmos, &display_scale, -range, range, WHITE, |x| {
+        Vec3 {
+            x: 0.0,
+            y: 0.0,
+            z: x.sin(),
+        }
     });
     // animate
-    // let t = time.elapsed_seconds();
+    // let t = clock.elapsed;
     // draw_fn(gizmos, -10 + t as i32, 10 + t as i32, |x| x.sin());
 }
 
 /// draws planar function as a sequence of vectors
 pub fn draw_planar_fn_as_vert_vecs<F>(
     gizmos: &mut Gizmos,
-    range_start: i32,
-    range_end: i32,
+    display_scale: &DisplayScale,
+    x_start: f32,
+    x_end: f32,
     color: Srgba,
     function: F,
 ) where
     F: Fn(f32) -> Vec3,
 {
-    let x_scaling = 0.2;
-    let z_scaling = 0.2;
-    let y_scaling = 0.2;
-
-    let mut value = range_start as f32;
-    while value < range_end as f32 {
-        let x = value;
+    for x in samples(x_start, x_end) {
         let vec = function(x);
 
-        let scaled_x = x * x_scaling;
-        let scaled_z = vec.z * z_scaling;
-        let scaled_y = vec.y * y_scaling;
+        let scaled_x = x * display_scale.length;
+        let scaled_z = display_scale.amplitude_to_screen(vec.z);
+        let scaled_y = display_scale.amplitude_to_screen(vec.y);
 
         // println!("x: {}, y: {}, z: {}", scaled_x, scaled_y, scaled_z);
 
@@ -67,7 +68,69 @@ pub fn draw_planar_fn_as_vert_vecs<F>(
             Vec3::new(scaled_x, scaled_z, scaled_y),
             color,
         );
+    }
+}
+
+/// draws a vector function along a ray through the origin, s is the distance along the ray in m
+/// unlike draw_planar_fn_as_vert_vecs the vectors may have a component along x, e.g. for waves
+/// that don't travel along x
+pub fn draw_ray_fn_as_vert_vecs<F>(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    direction: Vec3,
+    s_start: f32,
+    s_end: f32,
+    color: Srgba,
+    function: F,
+) where
+    F: Fn(f32) -> Vec3,
+{
+    for s in samples(s_start, s_end) {
+        let vec = function(s);
+
+        let start = to_screen_axes(direction * (s * display_scale.length));
+        let end = start
+            + to_screen_axes(Vec3::new(
+                display_scale.amplitude_to_screen(vec.x),
+                display_scale.amplitude_to_screen(vec.y),
+                display_scale.amplitude_to_screen(vec.z),
+            ));
 
-        value += 0.1;
+        gizmos.line(start, end, color);
     }
 }
+
+/// draws a function along a ray through the origin as a continuous line, e.g. an envelope, with
+/// the same axes as draw_ray_fn_as_vert_vecs
+pub fn draw_ray_fn_as_line<F>(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    direction: Vec3,
+    s_start: f32,
+    s_end: f32,
+    color: Srgba,
+    function: F,
+) where
+    F: Fn(f32) -> Vec3,
+{
+    gizmos.linestrip(
+        samples(s_start, s_end).map(|s| {
+            let vec = function(s);
+            to_screen_axes(
+                direction * (s * display_scale.length)
+                    + Vec3::new(
+                        display_scale.amplitude_to_screen(vec.x),
+                        display_scale.amplitude_to_screen(vec.y),
+                        display_scale.amplitude_to_screen(vec.z),
+                    ),
+            )
+        }),
+        color,
+    );
+}
+
+/// physical axes to the ones of the scene: z is drawn upwards and y towards the viewer, like in
+/// draw_planar_fn_as_vert_vecs
+pub fn to_screen_axes(vec: Vec3) -> Vec3 {
+    Vec3::new(vec.x, vec.z, vec.y)
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use uom::si::{
+    f32::{Length, Time},
+    length::meter,
+    time::second,
+};
+
+use crate::{
+    display_scale_ui::{
+        display_scale_text_listener, listen_toggle_auto_scale_events, setup_display_scale_ui,
+        toggle_auto_scale_button_handler, update_display_scale_inputs, update_display_scale_label,
+        ToggleAutoScaleEvent,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// wave lengths that fit on screen when the scale is picked automatically
+pub const WAVES_ON_SCREEN: f32 = 5.0;
+/// wall clock duration of one period when the scale is picked automatically
+pub const PERIOD_ON_SCREEN: f32 = 2.0;
+/// number of vectors drawn across a function's range
+const SAMPLES: u32 = 400;
+
+/// maps physical units to screen units and wall clock time
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct DisplayScale {
+    /// screen units per meter, along the propagation axis
+    pub length: f32,
+    /// screen units per unit of the wave quantity (m for mechanical waves, V/m for em waves)
+    pub amplitude: f32,
+    /// physical seconds per second of animation time, e.g. 1e-9 shows a 1 GHz wave at 1 Hz
+    pub time: f32,
+    /// pick length, amplitude and time for the entered wave length, frequency and amplitude
+    pub auto: bool,
+    /// screen units of the drawn range along the propagation axis, centered at 0
+    pub screen_width: f32,
+    /// screen units an automatically scaled amplitude is drawn with, at most
+    pub screen_amplitude: f32,
+}
+
+impl DisplayScale {
+    /// pixels
+    pub fn default_2d() -> DisplayScale {
+        DisplayScale {
+            length: 50.0,
+            amplitude: 50.0,
+            time: 1.0,
+            auto: true,
+            screen_width: 1000.0,
+            screen_amplitude: 80.0,
+        }
+    }
+
+    /// world units
+    pub fn default_3d() -> DisplayScale {
+        DisplayScale {
+            length: 0.2,
+            amplitude: 0.2,
+            time: 1e-8,
+            auto: true,
+            screen_width: 8.0,
+            screen_amplitude: 0.4,
+        }
+    }
+
+    /// physical time at the given animation time
+    pub fn physical_time(&self, animation_seconds: f32) -> Time {
+        Time::new::<second>(animation_seconds * self.time)
+    }
+
+    pub fn x_to_screen(&self, x: Length) -> f32 {
+        x.get::<meter>() * self.length
+    }
+
+    pub fn amplitude_to_screen(&self, amplitude: f32) -> f32 {
+        amplitude * self.amplitude
+    }
+
+    /// physical range on screen, in m
+    pub fn x_range(&self) -> (f32, f32) {
+        let half_width = self.screen_width / 2.0 / self.length;
+        (-half_width, half_width)
+    }
+
+    /// scale showing `span` meters across the screen, one period of `period` seconds in
+    /// PERIOD_ON_SCREEN and `amplitude` about as high as screen_amplitude
+    /// values are rounded to 1, 2 or 5 times a power of ten, so they're easy to read
+    /// non positive values leave the respective scale unchanged
+    pub fn fitted(&self, span: Length, period: Time, amplitude: f32) -> DisplayScale {
+        let mut fitted = *self;
+        let span = span.get::<meter>();
+        if span > 0.0 && span.is_finite() {
+            fitted.length = round_down_to_nice(self.screen_width / span);
+        }
+        let period = period.get::<second>();
+        if period > 0.0 && period.is_finite() {
+            fitted.time = round_down_to_nice(period / PERIOD_ON_SCREEN);
+        }
+        let amplitude = amplitude.abs();
+        if amplitude > 0.0 && amplitude.is_finite() {
+            fitted.amplitude = round_down_to_nice(self.screen_amplitude / amplitude);
+        }
+        fitted
+    }
+}
+
+/// largest 1, 2 or 5 times a power of ten, that is not greater than value
+fn round_down_to_nice(value: f32) -> f32 {
+    let power = 10f32.powf(value.log10().floor());
+    let mantissa = value / power;
+    let nice = if mantissa >= 5.0 {
+        5.0
+    } else if mantissa >= 2.0 {
+        2.0
+    } else {
+        1.0
+    };
+    nice * power
+}
+
+/// x positions in m between start and end, where the function is evaluated
+pub fn samples(x_start: f32, x_end: f32) -> impl Iterator<Item = f32> {
+    let step = (x_end - x_start) / SAMPLES as f32;
+    (0..SAMPLES).map(move |i| x_start + i as f32 * step)
+}
+
+/// inserts the scale, shows its factors on screen and lets them be edited
+pub fn add_display_scale(app: &mut App, display_scale: DisplayScale) {
+    add_text_input_plugin(app);
+    app.insert_resource(display_scale)
+        .add_event::<ToggleAutoScaleEvent>()
+        .add_systems(Startup, setup_display_scale_ui)
+        .add_systems(
+            Update,
+            (
+                display_scale_text_listener,
+                toggle_auto_scale_button_handler,
+                listen_toggle_auto_scale_events,
+                update_display_scale_label,
+                update_display_scale_inputs,
+            ),
+        );
+}
+
+/// scientific notation only for very small or large factors
+pub fn format_factor(factor: f32) -> String {
+    if factor != 0.0 && !(1e-3..1e4).contains(&factor.abs()) {
+        format!("{:e}", factor)
+    } else {
+        format!("{}", factor)
+    }
+}
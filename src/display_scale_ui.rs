@@ -0,0 +1,210 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::{TextInputInactive, TextInputSubmitEvent, TextInputValue};
+
+use crate::{
+    display_scale::{format_factor, DisplayScale},
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float},
+};
+
+#[derive(Resource)]
+pub struct DisplayScaleUiInputEntities {
+    pub length: Entity,
+    pub amplitude: Entity,
+    pub time: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct DisplayScaleLabelMarker;
+#[derive(Component, Default)]
+pub struct ToggleAutoScaleMarker;
+#[derive(Component, Default)]
+pub struct LengthScaleInputMarker;
+#[derive(Component, Default)]
+pub struct AmplitudeScaleInputMarker;
+#[derive(Component, Default)]
+pub struct TimeScaleInputMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleAutoScaleEvent;
+
+/// top, left of the side panel
+pub fn setup_display_scale_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    display_scale: Res<DisplayScale>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(140.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let label = generate_label(&font, "");
+    let spawned_label = commands.spawn((DisplayScaleLabelMarker, label)).id();
+    commands.entity(root_id).push_children(&[spawned_label]);
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Auto scale on/off",
+        ToggleAutoScaleMarker,
+    );
+
+    let length_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "x scale (units per m)",
+        LengthScaleInputMarker,
+        format_factor(display_scale.length),
+    );
+
+    let amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "u scale (units per 1)",
+        AmplitudeScaleInputMarker,
+        format_factor(display_scale.amplitude),
+    );
+
+    let time_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "t scale (s per s shown)",
+        TimeScaleInputMarker,
+        format_factor(display_scale.time),
+    );
+
+    commands.insert_resource(DisplayScaleUiInputEntities {
+        length: length_input,
+        amplitude: amplitude_input,
+        time: time_input,
+    });
+}
+
+/// a submitted scale turns the automatic scaling off, so it isn't fitted away right after
+pub fn display_scale_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<DisplayScaleUiInputEntities>,
+    mut display_scale: ResMut<DisplayScale>,
+) {
+    for event in events.read() {
+        let scale = if event.entity == input_entities.length {
+            &mut display_scale.length
+        } else if event.entity == input_entities.amplitude {
+            &mut display_scale.amplitude
+        } else if event.entity == input_entities.time {
+            &mut display_scale.time
+        } else {
+            continue;
+        };
+        match parse_scale(&event.value) {
+            Ok(value) => {
+                *scale = value;
+                display_scale.auto = false;
+            }
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
+
+fn parse_scale(str: &str) -> Result<f32, String> {
+    let value = parse_float(str)?;
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("Scale must be finite and > 0, got {}", value))
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_auto_scale_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleAutoScaleMarker>),
+    >,
+    mut my_events: EventWriter<ToggleAutoScaleEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleAutoScaleEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_auto_scale_events(
+    mut events: EventReader<ToggleAutoScaleEvent>,
+    mut display_scale: ResMut<DisplayScale>,
+) {
+    for _ in events.read() {
+        display_scale.auto = !display_scale.auto;
+    }
+}
+
+pub fn update_display_scale_label(
+    display_scale: Res<DisplayScale>,
+    mut label_query: Query<&mut Text, With<DisplayScaleLabelMarker>>,
+) {
+    if !display_scale.is_changed() {
+        return;
+    }
+    for mut label in &mut label_query {
+        label.sections[0].value = format!(
+            "scale{}\nx: 1 m = {} units\nu: 1 = {} units\nt: 1 s shown = {} s",
+            if display_scale.auto { " (auto)" } else { "" },
+            format_factor(display_scale.length),
+            format_factor(display_scale.amplitude),
+            format_factor(display_scale.time),
+        );
+    }
+}
+
+/// shows fitted or preset scales in the input boxes, except in the one being edited
+pub fn update_display_scale_inputs(
+    display_scale: Res<DisplayScale>,
+    input_entities: Res<DisplayScaleUiInputEntities>,
+    mut input_query: Query<(&mut TextInputValue, &TextInputInactive)>,
+) {
+    if !display_scale.is_changed() {
+        return;
+    }
+    let values = [
+        (input_entities.length, display_scale.length),
+        (input_entities.amplitude, display_scale.amplitude),
+        (input_entities.time, display_scale.time),
+    ];
+    for (entity, value) in values {
+        if let Ok((mut input_value, inactive)) = input_query.get_mut(entity) {
+            let value = format_factor(value);
+            if inactive.0 && input_value.0 != value {
+                input_value.0 = value;
+            }
+        }
+    }
+}
@@ -1,38 +1,67 @@
 use bevy::{
-    color::palettes::css::{GREEN, WHITE},
+    color::palettes::css::{DARK_GRAY, GRAY, GREEN, ORANGE, WHITE, YELLOW},
     ecs::query::QuerySingleError,
     prelude::*,
 };
 use bevy_simple_text_input::{TextInputPlugin, TextInputSystem};
-use once_cell::sync::Lazy;
 use uom::si::{
+    angle::radian,
     electric_field::volt_per_meter,
-    f32::{ElectricField, Frequency, Length, Velocity},
+    f32::{Angle, Length, Time},
     frequency::hertz,
+    heat_flux_density::watt_per_square_meter,
     length::meter,
-    time::second,
     velocity::meter_per_second,
 };
+use waves_core::{
+    attenuation::Attenuation,
+    electromagnetic_wave::{
+        calculate_frequency, calculate_magnetic_field, calculate_u_polarized,
+        calculate_u_polarized_3d, ElectromagneticWaveParameters, JonesVector, SPEED_OF_LIGHT,
+    },
+    fresnel::{p_unit_vector, S_UNIT_VECTOR},
+    poynting::{calculate_intensity, calculate_poynting_vector},
+    wave::{LengthVec3, SphericalDirection},
+    wave_packet::{calculate_envelope_raw, calculate_u_packet_polarized},
+};
 
 use crate::{
-    curves_3d::draw_planar_fn_as_vert_vecs,
+    attenuation::{add_attenuation_system, AttenuationInput},
+    clock::SimulationClock,
+    curves_3d::{draw_ray_fn_as_line, draw_ray_fn_as_vert_vecs, to_screen_axes},
+    display_scale::{add_display_scale, DisplayScale, WAVES_ON_SCREEN},
     electromagnetic_wave_ui::{
-        listen_electromagnetic_wave_ui_inputs, listen_polarity_ui_inputs,
-        polarity_circular_button_handler, polarity_planar_button_handler,
-        setup_electromagnetic_wave_infos, setup_electromagnetic_wave_ui, ElectromagneticAmplitude,
-        PolarityInput, PolarityInputEvent,
+        elliptical_polarity_text_listener, handedness_left_button_handler,
+        handedness_right_button_handler, listen_electromagnetic_wave_ui_inputs,
+        listen_elliptical_polarity_ui_inputs, listen_handedness_ui_inputs,
+        listen_magnetic_field_ui_inputs, listen_polarity_ui_inputs, magnetic_field_text_listener,
+        polarity_circular_button_handler, polarity_elliptical_button_handler,
+        polarity_planar_button_handler, setup_electromagnetic_wave_infos,
+        setup_electromagnetic_wave_ui, to_jones_vector, update_energy_label,
+        update_magnetic_field_label, update_polarization_ellipse_label, ElectromagneticAmplitude,
+        EllipticalPolarityInput, EllipticalPolarityUiInputs, HandednessInput, HandednessInputEvent,
+        MagneticFieldDisplayScale, MagneticFieldUiInputs, PolarityInput, PolarityInputEvent,
+    },
+    fdtd::add_fdtd_system,
+    interface::{add_interface_system, InterfaceInput, REFLECTED_COLOR, TRANSMITTED_COLOR},
+    medium::{add_medium_system, MediumInput},
+    oscilloscope::add_oscilloscope_system,
+    propagation::{add_propagation_system, PropagationInput},
+    sampled_wave::SampledWave,
+    spectrum::add_spectrum_system,
+    wave_packet::{
+        add_wave_packet_system, PacketOnScreen, WavePacketInput, GROUP_MARKER_COLOR,
+        PHASE_MARKER_COLOR,
     },
-    wave::{calculate_u_raw, calculate_u_scalar_raw, RawUserParameters},
     wave_ui::{
         focus, form_state_notifier_system, text_listener, Freq, Phase, UiInputs, UiInputsEvent,
         WarningMarker, WaveLength,
     },
 };
 
-static SPEED_OF_LIGHT: Lazy<Velocity> =
-    Lazy::new(|| Velocity::new::<meter_per_second>(299_792_458.0));
+/// number of poynting vectors drawn across the range
+const POYNTING_ARROWS: u32 = 20;
 
-#[allow(dead_code)]
 pub fn add_electromagnetic_wave(app: &mut App) {
     let wave_length = Length::new::<meter>(1.0);
 
@@ -42,6 +71,7 @@ pub fn add_electromagnetic_wave(app: &mut App) {
 
     app.add_event::<UiInputsEvent>()
         .add_event::<PolarityInputEvent>()
+        .add_event::<HandednessInputEvent>()
         .add_plugins(TextInputPlugin)
         .insert_resource(UiInputs {
             amplitude: "1".to_owned(),
@@ -50,50 +80,88 @@ pub fn add_electromagnetic_wave(app: &mut App) {
             phase: "0".to_owned(),
         })
         .insert_resource(PolarityInput::Planar)
+        .init_resource::<HandednessInput>()
+        .insert_resource(EllipticalPolarityUiInputs {
+            amplitude_y: "1".to_owned(),
+            amplitude_z: "0.5".to_owned(),
+            phase_difference: "0.785".to_owned(),
+        })
+        .insert_resource(EllipticalPolarityInput {
+            amplitude_y: 1.0,
+            amplitude_z: 0.5,
+            phase_difference: Angle::new::<radian>(0.785),
+        })
+        .init_resource::<MagneticFieldDisplayScale>()
+        .insert_resource(MagneticFieldUiInputs {
+            scale: "1".to_owned(),
+        })
         .add_systems(Update, focus.before(TextInputSystem))
         .add_systems(
             Update,
             (
-                draw_planar_polarity_electromagnetic_wave
-                    .pipe(handle_draw_electromagnetic_wave_result)
-                    .run_if(is_planar_polarity_selected),
-                draw_circular_polarity_electromagnetic_wave
-                    .pipe(handle_draw_electromagnetic_wave_result)
-                    .run_if(is_circular_polarity_selected),
+                magnetic_field_text_listener,
+                listen_magnetic_field_ui_inputs,
+                update_energy_label,
+                update_magnetic_field_label,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                draw_electromagnetic_wave.pipe(handle_draw_electromagnetic_wave_result),
                 listen_electromagnetic_wave_ui_inputs,
                 text_listener,
                 form_state_notifier_system,
                 validate_inputs.pipe(handle_validate_inputs_result),
                 polarity_planar_button_handler,
                 polarity_circular_button_handler,
+                polarity_elliptical_button_handler,
                 listen_polarity_ui_inputs,
+                handedness_right_button_handler,
+                handedness_left_button_handler,
+                listen_handedness_ui_inputs,
+                elliptical_polarity_text_listener,
+                listen_elliptical_polarity_ui_inputs,
+                update_polarization_ellipse_label,
             ),
         )
+        .add_systems(
+            Update,
+            fit_display_scale.pipe(handle_validate_inputs_result),
+        )
         .add_systems(Startup, setup_electromagnetic_wave_infos)
         .add_systems(Startup, setup_electromagnetic_wave_ui);
+    add_display_scale(app, DisplayScale::default_3d());
+    // vacuum isn't dispersive, v_g = v_p = c
+    add_wave_packet_system(app, WavePacketInput::new(1.0));
+    add_medium_system(app);
+    add_attenuation_system(app, AttenuationInput::electromagnetic());
+    add_interface_system(app);
+    add_propagation_system(app);
+    add_fdtd_system(app);
+    add_spectrum_system(app);
+    add_oscilloscope_system(app);
 }
 
-fn calculate_frequency(wave_length: Length) -> Frequency {
-    *SPEED_OF_LIGHT / wave_length
-}
-
-#[allow(dead_code)]
-fn calculate_wave_length(frequency: Frequency) -> Length {
-    *SPEED_OF_LIGHT / frequency
-}
-
-fn is_planar_polarity_selected(polarity: Res<PolarityInput>) -> bool {
-    match *polarity {
-        PolarityInput::Planar => true,
-        _ => false,
-    }
-}
-
-fn is_circular_polarity_selected(polarity: Res<PolarityInput>) -> bool {
-    match *polarity {
-        PolarityInput::Circular => true,
-        _ => false,
+/// picks the scale for the entered wave length, frequency and amplitude, e.g. a 500 nm wave
+/// needs a much larger length factor than a 1 km wave
+fn fit_display_scale(
+    mut display_scale: ResMut<DisplayScale>,
+    medium: Res<MediumInput>,
+    amplitude: Query<&ElectromagneticAmplitude>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+) -> Result<(), QuerySingleError> {
+    if !display_scale.auto {
+        return Ok(());
     }
+    let fitted = display_scale.fitted(
+        wave_length.get_single()?.0 * WAVES_ON_SCREEN,
+        1.0 / medium.frequency(wave_length.get_single()?.0, frequency.get_single()?.0),
+        amplitude.get_single()?.0.get::<volt_per_meter>(),
+    );
+    display_scale.set_if_neq(fitted);
+    Ok(())
 }
 
 fn handle_validate_inputs_result(In(result): In<Result<(), QuerySingleError>>) {
@@ -110,7 +178,11 @@ fn handle_validate_inputs_result(In(result): In<Result<(), QuerySingleError>>) {
     }
 }
 
+/// warns if fλ isn't c, without a medium
+/// with a medium the wave is drawn with the frequency the medium gives the wave length, which the
+/// medium panel shows with n, so only a wave length the medium doesn't support is a warning
 fn validate_inputs(
+    medium: Res<MediumInput>,
     frequency: Query<&Freq>,
     wave_length: Query<&WaveLength>,
     mut warning_query: Query<&mut Text, With<WarningMarker>>,
@@ -118,20 +190,24 @@ fn validate_inputs(
     let wave_length = wave_length.get_single()?;
     let frequency = frequency.get_single()?;
 
-    let speed = frequency.0 * wave_length.0;
-
-    let factor = speed / *SPEED_OF_LIGHT;
-
-    let factor_number = factor.value;
-    let speed_number = speed.get::<meter_per_second>();
-
-    let warning = if speed != *SPEED_OF_LIGHT {
-        Some(format!(
-            "{}x speed of light, speed: {} m/s",
-            factor_number, speed_number
-        ))
-    } else {
-        None
+    let warning = match medium.0 {
+        None => {
+            let speed = frequency.0 * wave_length.0;
+            let factor = speed / *SPEED_OF_LIGHT;
+            if speed != *SPEED_OF_LIGHT {
+                Some(format!(
+                    "{}x speed of light, speed: {} m/s",
+                    factor.value,
+                    speed.get::<meter_per_second>()
+                ))
+            } else {
+                None
+            }
+        }
+        Some(medium) => medium.frequency(wave_length.0).is_none().then(|| {
+            "The medium doesn't support this wave length, drawn with the entered frequency"
+                .to_owned()
+        }),
     };
 
     let mut warning_label: Mut<Text> = warning_query.get_single_mut()?;
@@ -166,173 +242,331 @@ fn handle_draw_electromagnetic_wave_result(In(result): In<Result<(), QuerySingle
     }
 }
 
+/// draws electric field and magnetic field (k̂ × E / c) for the selected polarity
 #[allow(clippy::too_many_arguments)]
-fn draw_planar_polarity_electromagnetic_wave(
+fn draw_electromagnetic_wave(
     mut gizmos: Gizmos,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
+    // together they give the jones vector
+    (polarity, handedness, elliptical): (
+        Res<PolarityInput>,
+        Res<HandednessInput>,
+        Res<EllipticalPolarityInput>,
+    ),
+    magnetic_scale: Res<MagneticFieldDisplayScale>,
+    display_scale: Res<DisplayScale>,
+    packet: Res<WavePacketInput>,
+    medium: Res<MediumInput>,
+    attenuation: Res<AttenuationInput>,
+    interface: Res<InterfaceInput>,
+    propagation: Res<PropagationInput>,
+    mut sampled: ResMut<SampledWave>,
     amplitude: Query<&ElectromagneticAmplitude>,
     wave_length: Query<&WaveLength>,
     frequency: Query<&Freq>,
     phase: Query<&Phase>,
 ) -> Result<(), QuerySingleError> {
-    let user_pars = ElectromagneticWaveUserParameters {
+    let mut pars: ElectromagneticWaveParameters = ElectromagneticWaveUserParameters {
         amplitude: *amplitude.get_single()?,
         wave_length: *wave_length.get_single()?,
         frequency: *frequency.get_single()?,
         phase: *phase.get_single()?,
-    };
-
-    let range = 20;
-
-    let t = uom::si::f32::Time::new::<second>(time.elapsed_seconds());
-    // let t = uom::si::f32::Time::new::<second>(0);  // not animated
-
-    // electric
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, WHITE, |x: f32| {
-        calculate_u_planar(Length::new::<meter>(x), t, &user_pars, Vec3::Z).to_vec3()
-    });
+    }
+    .into();
 
-    // magnetic
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, GREEN, |x: f32| {
-        calculate_u_planar(Length::new::<meter>(x), t, &user_pars, Vec3::Y).to_vec3()
-    });
+    pars.frequency = medium.frequency(pars.wave_length, pars.frequency);
+    let attenuation = attenuation.at(pars.frequency);
 
-    Ok(())
-}
+    let jones = to_jones_vector(*polarity, *handedness, &elliptical);
 
-#[allow(clippy::too_many_arguments)]
-fn draw_circular_polarity_electromagnetic_wave(
-    mut gizmos: Gizmos,
-    time: Res<Time>,
-    amplitude: Query<&ElectromagneticAmplitude>,
-    wave_length: Query<&WaveLength>,
-    frequency: Query<&Freq>,
-    phase: Query<&Phase>,
-) -> Result<(), QuerySingleError> {
-    let user_pars = ElectromagneticWaveUserParameters {
-        amplitude: *amplitude.get_single()?,
-        wave_length: *wave_length.get_single()?,
-        frequency: *frequency.get_single()?,
-        phase: *phase.get_single()?,
-    };
+    let (x_start, x_end) = display_scale.x_range();
 
-    let range = 20;
+    let t = display_scale.physical_time(clock.elapsed);
 
-    let t = uom::si::f32::Time::new::<second>(time.elapsed_seconds());
-    // let t = uom::si::f32::Time::new::<second>(0);  // not animated
+    // the plane of incidence fixes the directions, the entered propagation direction isn't used
+    if interface.enabled {
+        draw_interface_waves(&mut gizmos, &display_scale, &interface, &pars, &jones, t);
+        sampled.clear();
+        return Ok(());
+    }
 
-    // electric
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, WHITE, |x: f32| {
-        calculate_u_circular(
-            Length::new::<meter>(x),
-            t,
-            &user_pars,
-            Vec3::Y,
-            Vec3::Z,
-            true,
+    let packet = packet.enabled.then(|| {
+        PacketOnScreen::new(
+            packet.to_parameters(
+                pars.amplitude.get::<volt_per_meter>(),
+                pars.wave_length,
+                pars.frequency,
+                pars.phase,
+                &medium,
+            ),
+            &display_scale,
+            clock.elapsed,
         )
-        .to_vec3()
     });
 
-    // magnetic
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, GREEN, |x: f32| {
-        calculate_u_circular(
-            Length::new::<meter>(x),
+    // s is the distance along k̂, the wave is drawn along the line through the origin
+    let direction = propagation.0;
+    let k = direction.unit_vector();
+    let (unit_vector1, unit_vector2) = direction.transverse_unit_vectors();
+
+    let electric = |s: f32| match &packet {
+        Some(packet) => calculate_u_packet_polarized(
+            packet.local_x(s),
+            packet.t,
+            &packet.components,
+            &jones,
+            unit_vector1,
+            unit_vector2,
+        )
+        .scaled(attenuation.factor(Length::new::<meter>(s), packet.t)),
+        None => calculate_u_polarized_3d(
+            &LengthVec3::from_meters(k * s),
             t,
-            &user_pars,
-            Vec3::Z,
-            Vec3::Y,
-            false,
+            &pars,
+            &jones,
+            &direction,
         )
-        .to_vec3()
-    });
+        .scaled(attenuation.factor(Length::new::<meter>(s), t)),
+    };
+
+    // electric, in V/m
+    draw_ray_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        k,
+        x_start,
+        x_end,
+        WHITE,
+        |s: f32| electric(s).to_vec3(),
+    );
+    sampled.x_axis = to_screen_axes(k);
+    sampled.up_axis = to_screen_axes(unit_vector2);
+    sampled.record(
+        x_start,
+        x_end,
+        packet.as_ref().map_or(t, |packet| packet.t),
+        |s: f32| electric(s).to_vec3(),
+    );
+
+    // magnetic, in tesla, scaled to be comparable with E
+    let magnetic_scaling = SPEED_OF_LIGHT.get::<meter_per_second>() * magnetic_scale.0;
+    draw_ray_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        k,
+        x_start,
+        x_end,
+        GREEN,
+        |s: f32| calculate_magnetic_field(&electric(s), k).to_vec3() * magnetic_scaling,
+    );
+
+    // poynting vector, relative to its maximum cε0E0²(J₁² + J₂²) = 2I
+    let max_poynting =
+        2.0 * calculate_intensity(pars.amplitude, &jones).get::<watt_per_square_meter>();
+    if max_poynting > 0.0 {
+        // as long as E at its maximum
+        let arrow_length =
+            display_scale.amplitude_to_screen(pars.amplitude.get::<volt_per_meter>());
+        let step = (x_end - x_start) / POYNTING_ARROWS as f32;
+        for i in 0..POYNTING_ARROWS {
+            let s = x_start + i as f32 * step;
+            let e = electric(s);
+            let poynting = calculate_poynting_vector(&e, &calculate_magnetic_field(&e, k));
+            let relative = poynting.to_vec3() / max_poynting;
+            draw_poynting_arrow(
+                &mut gizmos,
+                k * (s * display_scale.length),
+                relative * arrow_length,
+            );
+        }
+    }
+
+    if let Some(packet) = &packet {
+        draw_packet_envelope(
+            &mut gizmos,
+            &display_scale,
+            packet,
+            &jones,
+            &direction,
+            &attenuation,
+        );
+    }
 
     Ok(())
 }
 
-/// parameters the user sets via the ui
-#[derive(Debug, Clone)]
-pub struct ElectromagneticWaveUserParameters {
-    pub amplitude: ElectromagneticAmplitude,
-    pub wave_length: WaveLength,
-    pub frequency: Freq,
-    pub phase: Phase,
-}
+/// envelope of E along each transverse axis and the markers moving with phase and group velocity
+fn draw_packet_envelope(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    packet: &PacketOnScreen,
+    jones: &JonesVector,
+    direction: &SphericalDirection,
+    attenuation: &Attenuation,
+) {
+    let (x_start, x_end) = display_scale.x_range();
+    let k = direction.unit_vector();
+    let (unit_vector1, unit_vector2) = direction.transverse_unit_vectors();
+    let envelope = |s: f32| {
+        calculate_envelope_raw(packet.local_x(s), packet.t, &packet.components)
+            * attenuation.factor(Length::new::<meter>(s), packet.t)
+    };
 
-impl From<ElectromagneticWaveUserParameters> for RawUserParameters {
-    fn from(p: ElectromagneticWaveUserParameters) -> Self {
-        RawUserParameters {
-            amplitude: p.amplitude.0.get::<volt_per_meter>(),
-            wave_length: p.wave_length,
-            frequency: p.frequency,
-            phase: p.phase,
+    for (axis, weight) in [(unit_vector1, jones.y), (unit_vector2, jones.z)] {
+        if weight == 0.0 {
+            continue;
+        }
+        for sign in [1.0, -1.0] {
+            draw_ray_fn_as_line(gizmos, display_scale, k, x_start, x_end, ORANGE, |s| {
+                axis * (sign * weight * envelope(s))
+            });
         }
     }
-}
 
-/// calculates wave function for a planar polarity wave
-#[allow(clippy::too_many_arguments)]
-fn calculate_u_planar(
-    x: Length,
-    t: uom::si::f32::Time,
-    up: &ElectromagneticWaveUserParameters,
-    direction: Vec3,
-) -> ElectricFieldVec3 {
-    let raw = calculate_u_raw(x, t, &up.clone().into(), direction);
-    // assumption: raw amplitude passed to calculate_u_raw (RawUserParameters) was in volt_per_meter
-    ElectricFieldVec3 {
-        x: ElectricField::new::<volt_per_meter>(raw.x),
-        y: ElectricField::new::<volt_per_meter>(raw.y),
-        z: ElectricField::new::<volt_per_meter>(raw.z),
+    // along the second transverse axis, i.e. upwards for a wave along x
+    let marker_height = display_scale.amplitude_to_screen(packet.packet.amplitude.abs()) * 1.2;
+    let marker_axis = to_screen_axes(unit_vector2) * marker_height;
+    for (s, color) in [
+        (packet.phase_x(), PHASE_MARKER_COLOR),
+        (packet.group_x(), GROUP_MARKER_COLOR),
+    ] {
+        let center = to_screen_axes(k * (s * display_scale.length));
+        gizmos.line(center - marker_axis, center + marker_axis, color);
+        gizmos.circle(center + marker_axis, Dir3::Z, marker_height * 0.05, color);
     }
 }
 
-/// calculates wave function for a circular polarity wave
-/// u(x, y) = A(cos(kx - wt)y + sin (kx - wt)z)
-/// see e.g. https://web.mit.edu/sahughes/www/8.022/lec21.pdf section 21.5
-#[allow(clippy::too_many_arguments)]
-fn calculate_u_circular(
-    x: Length,
-    t: uom::si::f32::Time,
-    up: &ElectromagneticWaveUserParameters,
-    unit_vector1: Vec3,
-    unit_vector2: Vec3,
-    sign_sin_cos: bool, // true for + between cos and sin terms, false for -
-) -> ElectricFieldVec3 {
-    let scalar = calculate_u_scalar_raw(x, t, &up.clone().into());
-    let cos = scalar.cos();
-    let sin = scalar.sin();
-
-    let term1 = unit_vector1 * cos;
-    let mut term2 = unit_vector2 * sin;
-
-    if !sign_sin_cos {
-        term2 = -term2;
-    }
-    let sub = term1 + term2;
+/// incident, reflected and transmitted electric field at the interface x=0
+/// the jones vector of the wave is relative to (s, p), so planar polarity is p polarized
+fn draw_interface_waves(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    input: &InterfaceInput,
+    pars: &ElectromagneticWaveParameters,
+    jones: &JonesVector,
+    t: Time,
+) {
+    let interface = input.interface;
+    let directions = interface.directions(input.incidence);
+    let coefficients = interface.coefficients(input.incidence);
+    let (x_start, x_end) = display_scale.x_range();
+
+    // k̂·r is the distance s along each ray, the phases match at the origin
+    let draw_wave = |gizmos: &mut Gizmos,
+                     direction: Vec3,
+                     (s_start, s_end): (f32, f32),
+                     pars: &ElectromagneticWaveParameters,
+                     jones: &JonesVector,
+                     color: Srgba| {
+        let p_unit = p_unit_vector(direction);
+        draw_ray_fn_as_vert_vecs(
+            gizmos,
+            display_scale,
+            direction,
+            s_start,
+            s_end,
+            color,
+            |s| {
+                calculate_u_polarized(
+                    Length::new::<meter>(s),
+                    t,
+                    pars,
+                    jones,
+                    S_UNIT_VECTOR,
+                    p_unit,
+                )
+                .to_vec3()
+            },
+        );
+    };
 
-    ElectricFieldVec3 {
-        x: up.amplitude.0 * sub.x,
-        y: up.amplitude.0 * sub.y,
-        z: up.amplitude.0 * sub.z,
+    draw_wave(
+        gizmos,
+        directions.incident,
+        (x_start, 0.0),
+        pars,
+        jones,
+        WHITE,
+    );
+
+    let (reflected_jones, reflected_phase) = coefficients.reflected(jones);
+    let reflected = ElectromagneticWaveParameters {
+        phase: pars.phase + reflected_phase,
+        ..*pars
+    };
+    draw_wave(
+        gizmos,
+        directions.reflected,
+        (0.0, x_end),
+        &reflected,
+        &reflected_jones,
+        REFLECTED_COLOR,
+    );
+
+    // evanescent for total internal reflection, not drawn
+    if let Some(direction) = directions.transmitted {
+        let (transmitted_jones, transmitted_phase) = coefficients.transmitted(jones);
+        let transmitted = ElectromagneticWaveParameters {
+            // same frequency, λ₂ = λ₁n₁/n₂
+            wave_length: pars.wave_length * (interface.n1 / interface.n2),
+            phase: pars.phase + transmitted_phase,
+            ..*pars
+        };
+        draw_wave(
+            gizmos,
+            direction,
+            (0.0, x_end),
+            &transmitted,
+            &transmitted_jones,
+            TRANSMITTED_COLOR,
+        );
     }
+
+    // interface plane, its normal and the direction of incidence at Brewster's angle
+    let half_size = x_end * display_scale.length;
+    gizmos.linestrip(
+        [
+            (1.0, 1.0),
+            (1.0, -1.0),
+            (-1.0, -1.0),
+            (-1.0, 1.0),
+            (1.0, 1.0),
+        ]
+        .map(|(y, z)| to_screen_axes(Vec3::new(0.0, y, z) * half_size)),
+        GRAY,
+    );
+    gizmos.line(
+        Vec3::new(-half_size, 0.0, 0.0),
+        Vec3::new(half_size, 0.0, 0.0),
+        DARK_GRAY,
+    );
+    let brewster = interface.directions(interface.brewster_angle()).incident;
+    gizmos.line(to_screen_axes(-brewster * half_size), Vec3::ZERO, DARK_GRAY);
 }
 
-/// 3d vector with domain specific units
-#[derive(Debug)]
-pub struct ElectricFieldVec3 {
-    pub x: ElectricField,
-    pub y: ElectricField,
-    pub z: ElectricField,
+/// draws an arrow starting at a point on the propagation axis, both in physical axes scaled to the
+/// screen
+fn draw_poynting_arrow(gizmos: &mut Gizmos, position: Vec3, s: Vec3) {
+    let start = to_screen_axes(position);
+    gizmos.arrow(start, start + to_screen_axes(s), YELLOW);
 }
 
-/// f32 vec
-impl ElectricFieldVec3 {
-    fn to_vec3(&self) -> Vec3 {
-        Vec3::new(
-            self.x.get::<volt_per_meter>(),
-            self.y.get::<volt_per_meter>(),
-            self.z.get::<volt_per_meter>(),
-        )
+/// parameters the user sets via the ui
+#[derive(Debug, Clone)]
+pub struct ElectromagneticWaveUserParameters {
+    pub amplitude: ElectromagneticAmplitude,
+    pub wave_length: WaveLength,
+    pub frequency: Freq,
+    pub phase: Phase,
+}
+
+impl From<ElectromagneticWaveUserParameters> for ElectromagneticWaveParameters {
+    fn from(p: ElectromagneticWaveUserParameters) -> Self {
+        ElectromagneticWaveParameters {
+            amplitude: p.amplitude.0,
+            wave_length: p.wave_length.0,
+            frequency: p.frequency.0,
+            phase: p.phase.0,
+        }
     }
 }
@@ -2,25 +2,47 @@ use bevy::{
     color::palettes::css::{BLACK, GREEN},
     prelude::*,
 };
+use bevy_simple_text_input::TextInputSubmitEvent;
 use uom::si::{
-    angle::{radian, Angle},
+    angle::{degree, radian},
     electric_field::volt_per_meter,
-    f32::{ElectricField, Frequency, Length},
+    f32::{Angle, ElectricField, Frequency, Length, MagneticFluxDensity},
     frequency::hertz,
+    heat_flux_density::watt_per_square_meter,
     length::meter,
+    magnetic_flux_density::tesla,
+    pressure::pascal,
+};
+use waves_core::{
+    electromagnetic_wave::{Handedness, JonesVector, SPEED_OF_LIGHT},
+    poynting::{
+        calculate_average_energy_density, calculate_intensity, calculate_radiation_pressure,
+    },
 };
 
+use crate::attenuation::AttenuationInput;
+use crate::attenuation_ui::add_attenuation_inputs;
+use crate::slider::{generate_slider_input_box, SliderRange};
 use crate::wave_ui::{
     add_button, add_label, add_warning_label, despawn_all_entities, generate_input_box,
-    parse_float, AmplitudeInputMarker, Freq, FrequencyInputMarker, Phase, PhaseMarker,
-    UiInputEntities, UiInputs, UiInputsEvent, WaveLength, WaveLengthInputMarker,
+    generate_label, parse_float, AmplitudeInputMarker, Freq, FrequencyInputMarker, Phase,
+    PhaseMarker, UiInputEntities, UiInputs, UiInputsEvent, WaveLength, WaveLengthInputMarker,
+    PHASE_RANGE,
 };
 
+/// what the sliders of the electromagnetic wave span, from microwaves to radio waves
+const AMPLITUDE_RANGE: SliderRange = SliderRange::linear(0.0, 10.0, 0.01);
+const WAVE_LENGTH_RANGE: SliderRange = SliderRange::log(0.001, 1000.0, 0.01);
+const FREQUENCY_RANGE: SliderRange = SliderRange::log(3e5, 3e11, 0.01);
+
 /// adds ui to configure wave
 pub fn setup_electromagnetic_wave_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     form_state: Res<UiInputs>,
+    elliptical_form_state: Res<EllipticalPolarityUiInputs>,
+    magnetic_form_state: Res<MagneticFieldUiInputs>,
+    attenuation: Res<AttenuationInput>,
 ) {
     let font = asset_server.load("fonts/FiraMono-Medium.ttf");
 
@@ -40,39 +62,67 @@ pub fn setup_electromagnetic_wave_ui(
 
     let root_id = root.id();
 
-    let amplitude_input = generate_input_box(
+    let amplitude_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
-        "Amplitude (volt/s)",
+        "Amplitude (V/m)",
         AmplitudeInputMarker,
         form_state.amplitude.clone(),
+        AMPLITUDE_RANGE,
     );
-    let wave_length_input = generate_input_box(
+    add_attenuation_inputs(&mut commands, root_id, &font, &attenuation);
+    let wave_length_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Wave length (m)",
         WaveLengthInputMarker,
         form_state.wave_length.clone(),
+        WAVE_LENGTH_RANGE,
     );
-    let frequency_input = generate_input_box(
+    let frequency_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Frequency (hz)",
         FrequencyInputMarker,
         form_state.frequency.clone(),
+        FREQUENCY_RANGE,
     );
-    let phase_input = generate_input_box(
+    let phase_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Phase (rad)",
         PhaseMarker,
         form_state.phase.clone(),
+        PHASE_RANGE,
+    );
+
+    let magnetic_scale_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "B scale (×c)",
+        MagneticScaleInputMarker,
+        magnetic_form_state.scale.clone(),
     );
 
+    let magnetic_label = generate_label(&font, "");
+    let spawned_magnetic_label = commands
+        .spawn((MagneticFieldInfoMarker, magnetic_label))
+        .id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_magnetic_label]);
+
+    let energy_label = generate_label(&font, "");
+    let spawned_energy_label = commands.spawn((EnergyInfoMarker, energy_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_energy_label]);
+
     add_warning_label(&mut commands, root_id, &font);
 
     add_label(&mut commands, root_id, &font, "Polarity");
@@ -91,6 +141,57 @@ pub fn setup_electromagnetic_wave_ui(
         "Circular",
         CircularPolarityMarker,
     );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Elliptical",
+        EllipticalPolarityMarker,
+    );
+
+    let amplitude_y_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Jones y",
+        JonesYInputMarker,
+        elliptical_form_state.amplitude_y.clone(),
+    );
+    let amplitude_z_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Jones z",
+        JonesZInputMarker,
+        elliptical_form_state.amplitude_z.clone(),
+    );
+    let phase_difference_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Phase diff (rad)",
+        PhaseDifferenceInputMarker,
+        elliptical_form_state.phase_difference.clone(),
+    );
+
+    add_label(&mut commands, root_id, &font, "Handedness");
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Right",
+        RightHandednessMarker,
+    );
+    add_button(&mut commands, root_id, &font, "Left", LeftHandednessMarker);
+
+    let ellipse_label = generate_label(&font, "");
+    let spawned_ellipse_label = commands
+        .spawn((PolarizationEllipseMarker, ellipse_label))
+        .id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_ellipse_label]);
 
     commands.insert_resource(UiInputEntities {
         amplitude: amplitude_input,
@@ -99,7 +200,15 @@ pub fn setup_electromagnetic_wave_ui(
         phase: phase_input,
     });
 
-    commands.insert_resource(PolarityInput::Planar);
+    commands.insert_resource(MagneticFieldUiInputEntities {
+        scale: magnetic_scale_input,
+    });
+
+    commands.insert_resource(EllipticalPolarityUiInputEntities {
+        amplitude_y: amplitude_y_input,
+        amplitude_z: amplitude_z_input,
+        phase_difference: phase_difference_input,
+    });
 }
 
 pub fn setup_electromagnetic_wave_infos(commands: Commands, asset_server: Res<AssetServer>) {
@@ -206,6 +315,55 @@ pub enum PolarityInput {
     #[default]
     Planar,
     Circular,
+    Elliptical,
+}
+
+#[derive(Debug, Default, Clone, Copy, Resource)]
+pub struct HandednessInput(pub Handedness);
+
+/// jones vector entered for elliptical polarity
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct EllipticalPolarityInput {
+    pub amplitude_y: f32,
+    pub amplitude_z: f32,
+    pub phase_difference: Angle,
+}
+
+#[derive(Resource)]
+pub struct EllipticalPolarityUiInputs {
+    pub amplitude_y: String,
+    pub amplitude_z: String,
+    pub phase_difference: String,
+}
+
+#[derive(Resource)]
+pub struct EllipticalPolarityUiInputEntities {
+    pub amplitude_y: Entity,
+    pub amplitude_z: Entity,
+    pub phase_difference: Entity,
+}
+
+/// the polarization state for the selected inputs
+pub fn to_jones_vector(
+    polarity: PolarityInput,
+    handedness: HandednessInput,
+    elliptical: &EllipticalPolarityInput,
+) -> JonesVector {
+    match polarity {
+        PolarityInput::Planar => JonesVector::planar(),
+        PolarityInput::Circular => JonesVector::circular(handedness.0),
+        PolarityInput::Elliptical => JonesVector::elliptical(
+            elliptical.amplitude_y,
+            elliptical.amplitude_z,
+            elliptical.phase_difference,
+            handedness.0,
+        ),
+    }
+}
+
+#[derive(Event, Default, Debug)]
+pub struct HandednessInputEvent {
+    pub handedness: Handedness,
 }
 
 #[derive(Event, Default, Debug)]
@@ -219,6 +377,57 @@ pub struct PlanarPolarityMarker;
 #[derive(Component, Default)]
 pub struct CircularPolarityMarker;
 
+#[derive(Component, Default)]
+pub struct EllipticalPolarityMarker;
+
+#[derive(Component, Default)]
+pub struct RightHandednessMarker;
+
+#[derive(Component, Default)]
+pub struct LeftHandednessMarker;
+
+#[derive(Component, Default)]
+pub struct JonesYInputMarker;
+
+#[derive(Component, Default)]
+pub struct JonesZInputMarker;
+
+#[derive(Component, Default)]
+pub struct PhaseDifferenceInputMarker;
+
+#[derive(Component, Default)]
+pub struct PolarizationEllipseMarker;
+
+#[derive(Component, Default)]
+pub struct MagneticScaleInputMarker;
+
+#[derive(Component, Default)]
+pub struct MagneticFieldInfoMarker;
+
+#[derive(Component, Default)]
+pub struct EnergyInfoMarker;
+
+/// B is drawn as c·B·scale, i.e. with scale 1 it has the same length as E
+/// (B itself is c≈3·10⁸ times smaller than E in SI units, so it wouldn't be visible)
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct MagneticFieldDisplayScale(pub f32);
+
+impl Default for MagneticFieldDisplayScale {
+    fn default() -> Self {
+        MagneticFieldDisplayScale(1.0)
+    }
+}
+
+#[derive(Resource)]
+pub struct MagneticFieldUiInputs {
+    pub scale: String,
+}
+
+#[derive(Resource)]
+pub struct MagneticFieldUiInputEntities {
+    pub scale: Entity,
+}
+
 #[allow(clippy::type_complexity)]
 pub fn polarity_planar_button_handler(
     mut interaction_query: Query<
@@ -253,6 +462,23 @@ pub fn polarity_circular_button_handler(
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub fn polarity_elliptical_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<EllipticalPolarityMarker>),
+    >,
+    mut my_events: EventWriter<PolarityInputEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        polarity_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            PolarityInput::Elliptical,
+        );
+    }
+}
+
 fn polarity_button_handler(
     interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
     my_events: &mut EventWriter<PolarityInputEvent>,
@@ -284,3 +510,200 @@ pub fn listen_polarity_ui_inputs(
         *model = input.polarity;
     }
 }
+
+#[allow(clippy::type_complexity)]
+pub fn handedness_right_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RightHandednessMarker>),
+    >,
+    mut my_events: EventWriter<HandednessInputEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        handedness_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            Handedness::Right,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn handedness_left_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<LeftHandednessMarker>),
+    >,
+    mut my_events: EventWriter<HandednessInputEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        handedness_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            Handedness::Left,
+        );
+    }
+}
+
+fn handedness_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<HandednessInputEvent>,
+    handedness: Handedness,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(HandednessInputEvent { handedness });
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+pub fn listen_handedness_ui_inputs(
+    mut events: EventReader<HandednessInputEvent>,
+    mut model: ResMut<HandednessInput>,
+) {
+    for input in events.read() {
+        model.0 = input.handedness;
+    }
+}
+
+pub fn elliptical_polarity_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<EllipticalPolarityUiInputs>,
+    input_entities: Res<EllipticalPolarityUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.amplitude_y {
+            inputs.amplitude_y = event.value.clone();
+        } else if event.entity == input_entities.amplitude_z {
+            inputs.amplitude_z = event.value.clone();
+        } else if event.entity == input_entities.phase_difference {
+            inputs.phase_difference = event.value.clone();
+        }
+    }
+}
+
+/// parses the jones vector inputs
+// TODO error handling (show on ui)
+pub fn listen_elliptical_polarity_ui_inputs(
+    inputs: Res<EllipticalPolarityUiInputs>,
+    mut model: ResMut<EllipticalPolarityInput>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+    match parse_float(&inputs.amplitude_y) {
+        Ok(f) => model.amplitude_y = f,
+        Err(err) => println!("error: {}", err),
+    }
+    match parse_float(&inputs.amplitude_z) {
+        Ok(f) => model.amplitude_z = f,
+        Err(err) => println!("error: {}", err),
+    }
+    match parse_float(&inputs.phase_difference) {
+        Ok(f) => model.phase_difference = Angle::new::<radian>(f),
+        Err(err) => println!("error: {}", err),
+    }
+}
+
+/// shows orientation and ellipticity of the current polarization
+pub fn update_polarization_ellipse_label(
+    polarity: Res<PolarityInput>,
+    handedness: Res<HandednessInput>,
+    elliptical: Res<EllipticalPolarityInput>,
+    mut label_query: Query<&mut Text, With<PolarizationEllipseMarker>>,
+) {
+    if !(polarity.is_changed() || handedness.is_changed() || elliptical.is_changed()) {
+        return;
+    }
+    let jones = to_jones_vector(*polarity, *handedness, &elliptical);
+    let ellipse = jones.ellipse();
+    let handedness_label = match jones.handedness() {
+        Some(Handedness::Right) => "right",
+        Some(Handedness::Left) => "left",
+        None => "linear",
+    };
+    for mut label in &mut label_query {
+        label.sections[0].value = format!(
+            "orientation: {:.1}°\nellipticity: {:.2}\nχ: {:.1}°\n{}",
+            ellipse.orientation.get::<degree>(),
+            ellipse.ellipticity(),
+            ellipse.ellipticity_angle.get::<degree>(),
+            handedness_label,
+        );
+    }
+}
+
+pub fn magnetic_field_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<MagneticFieldUiInputs>,
+    input_entities: Res<MagneticFieldUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.scale {
+            inputs.scale = event.value.clone();
+        }
+    }
+}
+
+/// parses the magnetic field display scale
+// TODO error handling (show on ui)
+pub fn listen_magnetic_field_ui_inputs(
+    inputs: Res<MagneticFieldUiInputs>,
+    mut model: ResMut<MagneticFieldDisplayScale>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+    match parse_float(&inputs.scale) {
+        Ok(f) => model.0 = f,
+        Err(err) => println!("error: {}", err),
+    }
+}
+
+/// shows the physical magnetic field amplitude, B₀ = E₀/c
+pub fn update_magnetic_field_label(
+    amplitude: Query<&ElectromagneticAmplitude>,
+    mut label_query: Query<&mut Text, With<MagneticFieldInfoMarker>>,
+) {
+    let Ok(amplitude) = amplitude.get_single() else {
+        return;
+    };
+    let magnetic_amplitude: MagneticFluxDensity = amplitude.0 / *SPEED_OF_LIGHT;
+    for mut label in &mut label_query {
+        label.sections[0].value = format!("B₀: {:.3e} T", magnetic_amplitude.get::<tesla>());
+    }
+}
+
+/// shows the time averaged intensity, energy density and radiation pressure
+pub fn update_energy_label(
+    polarity: Res<PolarityInput>,
+    handedness: Res<HandednessInput>,
+    elliptical: Res<EllipticalPolarityInput>,
+    amplitude: Query<&ElectromagneticAmplitude>,
+    mut label_query: Query<&mut Text, With<EnergyInfoMarker>>,
+) {
+    let Ok(amplitude) = amplitude.get_single() else {
+        return;
+    };
+    let jones = to_jones_vector(*polarity, *handedness, &elliptical);
+    let intensity = calculate_intensity(amplitude.0, &jones);
+    let energy_density = calculate_average_energy_density(intensity);
+    let radiation_pressure = calculate_radiation_pressure(intensity);
+    for mut label in &mut label_query {
+        label.sections[0].value = format!(
+            "⟨S⟩: {:.3e} W/m²\n⟨u⟩: {:.3e} J/m³\nP: {:.3e} Pa",
+            intensity.get::<watt_per_square_meter>(),
+            // J/m³ = Pa
+            energy_density.get::<pascal>(),
+            radiation_pressure.get::<pascal>(),
+        );
+    }
+}
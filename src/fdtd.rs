@@ -0,0 +1,333 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    color::palettes::css::{FUCHSIA, YELLOW},
+    prelude::*,
+};
+use uom::si::{
+    angle::radian,
+    electric_field::volt_per_meter,
+    f32::{Angle, ElectricField, Frequency, Length},
+    frequency::hertz,
+    length::meter,
+    time::second,
+};
+use waves_core::{
+    electromagnetic_wave::SPEED_OF_LIGHT,
+    fdtd::{DielectricRegion, Fdtd, FdtdGrid, FdtdSource, SourceShape, Waveform},
+};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::DisplayScale,
+    electromagnetic_wave_ui::ElectromagneticAmplitude,
+    fdtd_ui::{
+        fdtd_text_listener, listen_toggle_fdtd_dimensions_events, listen_toggle_fdtd_events,
+        setup_fdtd_ui, toggle_fdtd_button_handler, toggle_fdtd_dimensions_button_handler,
+        update_fdtd_label, FdtdUiStatus, ToggleFdtdDimensionsEvent, ToggleFdtdEvent,
+    },
+    medium::MediumInput,
+    wave_ui::{add_text_input_plugin, Freq, Phase, WaveLength},
+};
+
+/// color of the simulated E_z
+pub const FDTD_COLOR: Srgba = FUCHSIA;
+/// color of the dielectric region outlines
+const DIELECTRIC_COLOR: Srgba = YELLOW;
+/// resolution of the simulated wave, finer in 1d where cells are cheap
+const CELLS_PER_WAVE_LENGTH_1D: f32 = 30.0;
+const CELLS_PER_WAVE_LENGTH_2D: f32 = 20.0;
+/// upper bounds of the cells across the screen, so a short wave doesn't stall the app
+const MAX_CELLS_1D: f32 = 20_000.0;
+const MAX_CELLS_2D: f32 = 300.0;
+/// cell updates per fixed step, catching up after a jump of the clock takes several fixed steps
+const CELL_UPDATES_PER_TICK: usize = 2_000_000;
+/// the plane wave enters this fraction of the screen right of its left edge, left of it is only
+/// the field scattered back by the dielectric
+const SOURCE_OFFSET: f32 = 0.05;
+/// rows of the 2d heightmap that are drawn
+const HEIGHTMAP_ROWS: usize = 40;
+
+/// finite-difference time-domain simulation of the em wave, drawn over the analytic one
+/// the simulated wave is E_z of a plane wave along +x with the entered amplitude, frequency and
+/// phase, so it's comparable with the analytic wave in linear polarization along z and direction +x
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct FdtdInput {
+    pub enabled: bool,
+    /// the x-y plane instead of the x axis, the wave is uniform along y unless scattered
+    pub two_dimensional: bool,
+    pub dielectric: Option<DielectricRegion>,
+}
+
+/// the running solver, rebuilt when the inputs change or the clock goes back
+#[derive(Resource, Default)]
+pub struct FdtdSimulation {
+    pub fdtd: Option<Fdtd>,
+    /// max |E_z − A cos(kx − ωt + φ)| behind the front, only without dielectric
+    pub deviation: Option<ElectricField>,
+}
+
+/// adds the fdtd panel, the solver steps in the FixedUpdate schedule
+pub fn add_fdtd_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.add_event::<ToggleFdtdEvent>()
+        .add_event::<ToggleFdtdDimensionsEvent>()
+        .init_resource::<FdtdInput>()
+        .init_resource::<FdtdSimulation>()
+        .init_resource::<FdtdUiStatus>()
+        .add_systems(Startup, setup_fdtd_ui)
+        .add_systems(FixedUpdate, step_fdtd)
+        .add_systems(
+            Update,
+            (
+                toggle_fdtd_button_handler,
+                listen_toggle_fdtd_events,
+                toggle_fdtd_dimensions_button_handler,
+                listen_toggle_fdtd_dimensions_events,
+                fdtd_text_listener,
+                update_fdtd_label,
+                draw_fdtd,
+            ),
+        );
+}
+
+/// grid, dielectric and source for the inputs, the solver's are compared with these to detect changes
+/// fails for an empty x range
+pub fn fdtd_setup(
+    input: &FdtdInput,
+    (x_start, x_end): (f32, f32),
+    amplitude: ElectricField,
+    frequency: Frequency,
+    phase: Angle,
+) -> Result<(FdtdGrid, Vec<DielectricRegion>, Vec<FdtdSource>), String> {
+    let (cells_per_wave_length, max_cells) = if input.two_dimensional {
+        (CELLS_PER_WAVE_LENGTH_2D, MAX_CELLS_2D)
+    } else {
+        (CELLS_PER_WAVE_LENGTH_1D, MAX_CELLS_1D)
+    };
+    let range = (Length::new::<meter>(x_start), Length::new::<meter>(x_end));
+    let cell_size = FdtdGrid::fitting_cell_size(
+        frequency,
+        range.1 - range.0,
+        cells_per_wave_length,
+        max_cells,
+    )
+    .ok_or_else(|| format!("The x range {} to {} m is empty", x_start, x_end))?;
+    let grid = FdtdGrid::covering(range, input.two_dimensional.then_some(range), cell_size);
+
+    let source_x = range.0 + (range.1 - range.0) * SOURCE_OFFSET;
+    let source = FdtdSource {
+        shape: SourceShape::PlaneWave { x: source_x },
+        amplitude,
+        // the front leaves the source at t = 0
+        waveform: Waveform::Continuous {
+            frequency,
+            phase,
+            start: -source_x / *SPEED_OF_LIGHT,
+        },
+    };
+    Ok((grid, input.dielectric.into_iter().collect(), vec![source]))
+}
+
+/// runs the solver up to the time on the clock, restarting it if the clock went back
+#[allow(clippy::too_many_arguments)]
+fn step_fdtd(
+    input: Res<FdtdInput>,
+    mut simulation: ResMut<FdtdSimulation>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+    medium: Res<MediumInput>,
+    amplitude: Query<&ElectromagneticAmplitude>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+    phase: Query<&Phase>,
+) {
+    if !input.enabled {
+        if simulation.fdtd.is_some() {
+            *simulation = FdtdSimulation::default();
+        }
+        return;
+    }
+    let (Ok(amplitude), Ok(wave_length), Ok(frequency), Ok(phase)) = (
+        amplitude.get_single(),
+        wave_length.get_single(),
+        frequency.get_single(),
+        phase.get_single(),
+    ) else {
+        return;
+    };
+    let frequency = medium.frequency(wave_length.0, frequency.0);
+    if frequency.get::<hertz>() <= 0.0 {
+        return;
+    }
+
+    let Ok((grid, regions, sources)) = fdtd_setup(
+        &input,
+        display_scale.x_range(),
+        amplitude.0,
+        frequency,
+        phase.0,
+    ) else {
+        return;
+    };
+    let t = display_scale.physical_time(clock.elapsed);
+
+    let outdated = match &simulation.fdtd {
+        Some(fdtd) => {
+            fdtd.grid != grid
+                || fdtd.regions != regions
+                || fdtd.sources != sources
+                || t < fdtd.time()
+        }
+        None => true,
+    };
+    if outdated {
+        simulation.fdtd = Some(Fdtd::new(grid, regions, sources));
+    }
+
+    let Some(fdtd) = &mut simulation.fdtd else {
+        return;
+    };
+    let max_steps = (CELL_UPDATES_PER_TICK / (grid.cells_x * grid.cells_y)).max(1);
+    fdtd.advance_to(t, max_steps);
+
+    let deviation = fdtd
+        .regions
+        .is_empty()
+        .then(|| plane_wave_deviation(fdtd, amplitude.0, frequency, phase.0))
+        .flatten();
+    simulation.deviation = deviation;
+}
+
+/// max deviation from the analytic vacuum wave between the source and one wave length behind the
+/// front, where the switching on still rings, None before the wave got that far
+fn plane_wave_deviation(
+    fdtd: &Fdtd,
+    amplitude: ElectricField,
+    frequency: Frequency,
+    phase: Angle,
+) -> Option<ElectricField> {
+    let SourceShape::PlaneWave { x: source_x } = fdtd.sources.first()?.shape else {
+        return None;
+    };
+    let grid = fdtd.grid;
+    let t = fdtd.time();
+    let wave_length = *SPEED_OF_LIGHT / frequency;
+    let last = grid.x(grid.cells_x - 1 - grid.pml_cells);
+    let end = (source_x + *SPEED_OF_LIGHT * t - wave_length).min(last);
+    if end <= source_x {
+        return None;
+    }
+    let k = 2.0 * PI / wave_length.get::<meter>();
+    let omega = 2.0 * PI * frequency.get::<hertz>();
+    let y = grid.y(grid.cells_y / 2);
+    let max = (grid.column(source_x) + 1..=grid.column(end))
+        .map(|i| {
+            let x = grid.x(i);
+            let analytic = amplitude.get::<volt_per_meter>()
+                * (k * x.get::<meter>() - omega * t.get::<second>() + phase.get::<radian>()).cos();
+            (fdtd.e_z(i, grid.row(y)).get::<volt_per_meter>() - analytic).abs()
+        })
+        .fold(0.0, f32::max);
+    Some(ElectricField::new::<volt_per_meter>(max))
+}
+
+/// E_z along x in 1d, a heightmap over the x-y plane in 2d, and the dielectric outlines
+fn draw_fdtd(
+    mut gizmos: Gizmos,
+    input: Res<FdtdInput>,
+    simulation: Res<FdtdSimulation>,
+    display_scale: Res<DisplayScale>,
+    amplitude: Query<&ElectromagneticAmplitude>,
+) {
+    let Some(fdtd) = &simulation.fdtd else {
+        return;
+    };
+    let grid = fdtd.grid;
+    let (x_start, x_end) = display_scale.x_range();
+    let columns =
+        grid.column(Length::new::<meter>(x_start))..=grid.column(Length::new::<meter>(x_end));
+    // physics z, E_z, is up on screen, physics y towards the viewer
+    let point = |i: usize, j: usize| {
+        Vec3::new(
+            display_scale.x_to_screen(grid.x(i)),
+            display_scale.amplitude_to_screen(fdtd.e_z(i, j).get::<volt_per_meter>()),
+            if grid.is_two_dimensional() {
+                display_scale.x_to_screen(grid.y(j))
+            } else {
+                0.0
+            },
+        )
+    };
+
+    if grid.is_two_dimensional() {
+        let reference = amplitude
+            .get_single()
+            .map(|amplitude| amplitude.0.get::<volt_per_meter>().abs())
+            .unwrap_or(1.0)
+            .max(f32::EPSILON);
+        let rows = grid.row(Length::new::<meter>(x_start))..=grid.row(Length::new::<meter>(x_end));
+        let row_step = (rows.end() - rows.start()).div_ceil(HEIGHTMAP_ROWS).max(1);
+        for j in rows.step_by(row_step) {
+            gizmos.linestrip_gradient(columns.clone().map(|i| {
+                let relative =
+                    (fdtd.e_z(i, j).get::<volt_per_meter>() / reference).clamp(-1.0, 1.0);
+                (point(i, j), heightmap_color(relative))
+            }));
+        }
+    } else {
+        gizmos.linestrip(columns.map(|i| point(i, 0)), FDTD_COLOR);
+    }
+
+    for region in &fdtd.regions {
+        draw_dielectric_outline(&mut gizmos, &display_scale, region, &input);
+    }
+}
+
+/// red for positive, blue for negative E_z, relative in [-1, 1]
+fn heightmap_color(relative: f32) -> Color {
+    Color::srgb(
+        0.5 + 0.5 * relative,
+        0.3 * (1.0 - relative.abs()) + 0.2,
+        0.5 - 0.5 * relative,
+    )
+}
+
+/// the slab's x range as a rectangle in the x-z plane in 1d, the region in the x-y plane in 2d,
+/// clipped to the screen
+fn draw_dielectric_outline(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    region: &DielectricRegion,
+    input: &FdtdInput,
+) {
+    let (start, end) = display_scale.x_range();
+    let clip = |v: Length| {
+        display_scale.x_to_screen(v).clamp(
+            display_scale.x_to_screen(Length::new::<meter>(start)),
+            display_scale.x_to_screen(Length::new::<meter>(end)),
+        )
+    };
+    let (x_min, x_max) = (clip(region.x_min), clip(region.x_max));
+    let corners = if input.two_dimensional {
+        let (y_min, y_max) = (clip(region.y_min), clip(region.y_max));
+        [
+            Vec3::new(x_min, 0.0, y_min),
+            Vec3::new(x_max, 0.0, y_min),
+            Vec3::new(x_max, 0.0, y_max),
+            Vec3::new(x_min, 0.0, y_max),
+        ]
+    } else {
+        let height = display_scale.screen_amplitude * 1.5;
+        [
+            Vec3::new(x_min, -height, 0.0),
+            Vec3::new(x_max, -height, 0.0),
+            Vec3::new(x_max, height, 0.0),
+            Vec3::new(x_min, height, 0.0),
+        ]
+    };
+    gizmos.linestrip(
+        corners.iter().chain(corners.first()).copied(),
+        DIELECTRIC_COLOR,
+    );
+}
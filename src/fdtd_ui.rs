@@ -0,0 +1,228 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{electric_field::volt_per_meter, length::meter, time::second};
+
+use crate::{
+    fdtd::{FdtdInput, FdtdSimulation},
+    wave_ui::{add_button, add_label, generate_input_box, generate_label},
+};
+
+#[derive(Resource)]
+pub struct FdtdUiInputEntities {
+    pub dielectric: Entity,
+}
+
+/// error of the last submitted dielectric region, if any
+#[derive(Resource, Default)]
+pub struct FdtdUiStatus(pub Option<String>);
+
+#[derive(Component, Default)]
+pub struct ToggleFdtdMarker;
+#[derive(Component, Default)]
+pub struct ToggleFdtdDimensionsMarker;
+#[derive(Component, Default)]
+pub struct DielectricInputMarker;
+#[derive(Component, Default)]
+pub struct FdtdInfoMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleFdtdEvent;
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleFdtdDimensionsEvent;
+
+/// adds the fdtd controls, top, next to the direction panel
+pub fn setup_fdtd_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input: Res<FdtdInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            left: Val::Px(500.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "FDTD on/off",
+        ToggleFdtdMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "FDTD 1d/2d",
+        ToggleFdtdDimensionsMarker,
+    );
+
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "e.g. 1 2 4 (slab), 1 2 -1 1 4",
+    );
+
+    let dielectric_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Dielectric x₁ x₂ [y₁ y₂] εᵣ",
+        DielectricInputMarker,
+        input
+            .dielectric
+            .map(|region| region.to_string())
+            .unwrap_or_else(|| "none".to_owned()),
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((FdtdInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(FdtdUiInputEntities {
+        dielectric: dielectric_input,
+    });
+}
+
+pub fn fdtd_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<FdtdUiInputEntities>,
+    mut input: ResMut<FdtdInput>,
+    mut status: ResMut<FdtdUiStatus>,
+) {
+    for event in events.read() {
+        if event.entity != input_entities.dielectric {
+            continue;
+        }
+        let value = event.value.trim();
+        if value.is_empty() || value == "none" {
+            input.dielectric = None;
+            status.0 = None;
+            continue;
+        }
+        match value.parse() {
+            Ok(region) => {
+                input.dielectric = Some(region);
+                status.0 = None;
+            }
+            Err(err) => status.0 = Some(err),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_fdtd_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleFdtdMarker>),
+    >,
+    mut my_events: EventWriter<ToggleFdtdEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleFdtdEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_fdtd_events(
+    mut events: EventReader<ToggleFdtdEvent>,
+    mut input: ResMut<FdtdInput>,
+) {
+    for _ in events.read() {
+        input.enabled = !input.enabled;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_fdtd_dimensions_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleFdtdDimensionsMarker>),
+    >,
+    mut my_events: EventWriter<ToggleFdtdDimensionsEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleFdtdDimensionsEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_fdtd_dimensions_events(
+    mut events: EventReader<ToggleFdtdDimensionsEvent>,
+    mut input: ResMut<FdtdInput>,
+) {
+    for _ in events.read() {
+        input.two_dimensional = !input.two_dimensional;
+    }
+}
+
+/// grid, step, simulated time and, in vacuum, the deviation from the analytic wave
+pub fn update_fdtd_label(
+    input: Res<FdtdInput>,
+    simulation: Res<FdtdSimulation>,
+    status: Res<FdtdUiStatus>,
+    mut label_query: Query<&mut Text, With<FdtdInfoMarker>>,
+) {
+    let text = match (&status.0, &simulation.fdtd) {
+        (Some(err), _) => err.clone(),
+        (None, None) => format!("off, {}", if input.two_dimensional { "2d" } else { "1d" }),
+        (None, Some(fdtd)) => {
+            let grid = fdtd.grid;
+            format!(
+                "cells: {}x{}\nΔx: {:.3e} m\nΔt: {:.3e} s\nsteps: {} t: {:.3e} s\nmax |E - E₀|: {}",
+                grid.cells_x,
+                grid.cells_y,
+                grid.cell_size.get::<meter>(),
+                grid.time_step().get::<second>(),
+                fdtd.steps(),
+                fdtd.time().get::<second>(),
+                simulation
+                    .deviation
+                    .map(|deviation| format!("{:.3} V/m", deviation.get::<volt_per_meter>()))
+                    .unwrap_or_else(|| "-".to_owned()),
+            )
+        }
+    };
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
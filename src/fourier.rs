@@ -0,0 +1,331 @@
+use bevy::{
+    color::palettes::css::{DARK_GRAY, GRAY, ORANGE, WHITE, YELLOW},
+    prelude::*,
+};
+use uom::si::{
+    f32::{Frequency, Length},
+    frequency::hertz,
+    length::meter,
+};
+use waves_core::{
+    fourier::{FourierSeries, Waveform},
+    wave::{calculate_u_raw, calculate_u_superposition},
+};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::{add_display_scale, DisplayScale},
+    fourier_ui::{
+        build_up_button_handler, fewer_harmonics_button_handler, fourier_text_listener,
+        listen_fourier_events, listen_fourier_ui_inputs, more_harmonics_button_handler,
+        setup_fourier_ui, sync_harmonics_input, update_fourier_info_label, FourierEvent,
+        FourierUiInputs,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// harmonics the series is computed with, the partial sums go up to this
+pub const MAX_HARMONICS: usize = 200;
+/// wall clock seconds between two harmonics while building up
+const BUILD_INTERVAL: f32 = 0.5;
+/// components drawn individually, the lowest ones plus the newest
+const DRAWN_COMPONENTS: usize = 12;
+/// points per curve across the screen, enough to show the ripples of many harmonics
+const CURVE_SAMPLES: usize = 1000;
+/// vertical offsets of the plots, in screen units
+const SUM_PLOT_Y: f32 = 150.0;
+const COMPONENTS_PLOT_Y: f32 = -30.0;
+const BARS_BASELINE_Y: f32 = -300.0;
+/// screen units of the largest bar
+const BARS_HEIGHT: f32 = 120.0;
+/// radius of the circles marking the overshoot
+const PEAK_RADIUS: f32 = 5.0;
+const TARGET_COLOR: Srgba = GRAY;
+const SUM_COLOR: Srgba = WHITE;
+const NEWEST_COMPONENT_COLOR: Srgba = ORANGE;
+const GIBBS_COLOR: Srgba = YELLOW;
+
+/// the waveform and how much of its series is shown, as entered in the form
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Fourier {
+    pub waveform: Waveform,
+    /// harmonics in the partial sum
+    pub harmonics: usize,
+    pub amplitude: Length,
+    /// of the fundamental
+    pub wave_length: Length,
+    pub frequency: Frequency,
+    /// adds a harmonic every BUILD_INTERVAL until MAX_HARMONICS
+    pub building: bool,
+}
+
+impl Default for Fourier {
+    fn default() -> Self {
+        Fourier {
+            waveform: Waveform::Square,
+            harmonics: 1,
+            amplitude: Length::new::<meter>(1.0),
+            wave_length: Length::new::<meter>(8.0),
+            frequency: Frequency::new::<hertz>(0.25),
+            building: false,
+        }
+    }
+}
+
+/// series of the current waveform, recomputed when it changes
+#[derive(Resource)]
+pub struct FourierCoefficients {
+    pub series: FourierSeries,
+    /// Gibbs overshoot of the partial sum, relative to the range of the waveform
+    pub overshoot: f32,
+    /// fraction of the period where the partial sum is largest
+    pub peak: f32,
+}
+
+/// a periodic waveform and its fourier series, built up harmonic by harmonic
+pub fn add_fourier_2d_system(app: &mut App) {
+    let fourier = Fourier::default();
+    add_text_input_plugin(app);
+    app.add_event::<FourierEvent>()
+        .insert_resource(FourierUiInputs::from(&fourier))
+        .insert_resource(coefficients(&fourier))
+        .insert_resource(fourier)
+        .add_systems(Startup, setup_fourier_ui)
+        .add_systems(
+            Update,
+            (
+                update_coefficients,
+                build_up,
+                draw_fourier,
+                fourier_text_listener,
+                listen_fourier_ui_inputs,
+                more_harmonics_button_handler,
+                fewer_harmonics_button_handler,
+                build_up_button_handler,
+                listen_fourier_events,
+                sync_harmonics_input,
+                update_fourier_info_label,
+                fit_display_scale,
+            ),
+        );
+    add_display_scale(app, DisplayScale::default_2d());
+}
+
+/// two periods of the fundamental on screen
+fn fit_display_scale(mut display_scale: ResMut<DisplayScale>, fourier: Res<Fourier>) {
+    if !display_scale.auto {
+        return;
+    }
+    let fitted = display_scale.fitted(
+        fourier.wave_length * 2.0,
+        1.0 / fourier.frequency,
+        fourier.amplitude.get::<meter>(),
+    );
+    display_scale.set_if_neq(fitted);
+}
+
+fn coefficients(fourier: &Fourier) -> FourierCoefficients {
+    let series = FourierSeries::of(&fourier.waveform, MAX_HARMONICS);
+    FourierCoefficients {
+        overshoot: series.overshoot(&fourier.waveform, fourier.harmonics),
+        peak: series.peak(fourier.harmonics),
+        series,
+    }
+}
+
+/// the series only depends on the waveform, the overshoot also on the harmonics
+fn update_coefficients(
+    fourier: Res<Fourier>,
+    mut coefficients: ResMut<FourierCoefficients>,
+    mut computed: Local<Option<(Waveform, usize)>>,
+) {
+    let key = (fourier.waveform.clone(), fourier.harmonics);
+    if computed.as_ref() == Some(&key) {
+        return;
+    }
+    if computed.as_ref().map(|(waveform, _)| waveform) != Some(&fourier.waveform) {
+        coefficients.series = FourierSeries::of(&fourier.waveform, MAX_HARMONICS);
+    }
+    coefficients.overshoot = coefficients
+        .series
+        .overshoot(&fourier.waveform, fourier.harmonics);
+    coefficients.peak = coefficients.series.peak(fourier.harmonics);
+    *computed = Some(key);
+}
+
+/// adds the next harmonic every BUILD_INTERVAL, in wall clock time so it doesn't depend on the
+/// time scale
+fn build_up(time: Res<Time>, mut fourier: ResMut<Fourier>, mut since_last: Local<f32>) {
+    if !fourier.building {
+        *since_last = 0.0;
+        return;
+    }
+    *since_last += time.delta_seconds();
+    if *since_last >= BUILD_INTERVAL {
+        *since_last = 0.0;
+        if fourier.harmonics < MAX_HARMONICS {
+            fourier.harmonics += 1;
+        } else {
+            fourier.building = false;
+        }
+    }
+}
+
+/// target and partial sum on top, the components in the middle, the harmonic amplitudes at the bottom
+fn draw_fourier(
+    mut gizmos: Gizmos,
+    fourier: Res<Fourier>,
+    coefficients: Res<FourierCoefficients>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+) {
+    let t = display_scale.physical_time(clock.elapsed);
+    let (x_start, x_end) = display_scale.x_range();
+    let xs: Vec<Length> = (0..=CURVE_SAMPLES)
+        .map(|i| {
+            Length::new::<meter>(x_start + (x_end - x_start) * i as f32 / CURVE_SAMPLES as f32)
+        })
+        .collect();
+    let to_screen = |x: Length, u: f32, offset: f32| {
+        Vec2::new(
+            display_scale.x_to_screen(x),
+            offset + display_scale.amplitude_to_screen(u),
+        )
+    };
+    let series = &coefficients.series;
+    let amplitude = fourier.amplitude.get::<meter>();
+    let waves: Vec<_> = series
+        .harmonics
+        .iter()
+        .take(fourier.harmonics)
+        .map(|harmonic| {
+            FourierSeries::harmonic_wave(
+                harmonic,
+                fourier.amplitude,
+                fourier.wave_length,
+                fourier.frequency,
+            )
+        })
+        .collect();
+    // fraction of the fundamental's period at x, the waveform travels along +x like its harmonics
+    let s =
+        |x: Length| ((x - fourier.frequency * t * fourier.wave_length) / fourier.wave_length).value;
+
+    gizmos.linestrip_2d(
+        xs.iter()
+            .map(|&x| to_screen(x, amplitude * fourier.waveform.value(s(x)), SUM_PLOT_Y)),
+        TARGET_COLOR,
+    );
+    gizmos.linestrip_2d(
+        xs.iter().map(|&x| {
+            let u = calculate_u_superposition(x, t, &waves, Vec3::Y)
+                .y
+                .get::<meter>();
+            to_screen(x, amplitude * series.mean + u, SUM_PLOT_Y)
+        }),
+        SUM_COLOR,
+    );
+
+    // Gibbs: the peaks of the partial sum against the largest value of the waveform
+    if coefficients.overshoot > 0.0 {
+        let (_, max) = waveform_extremes(&fourier.waveform);
+        gizmos.line_2d(
+            to_screen(xs[0], amplitude * max, SUM_PLOT_Y),
+            to_screen(xs[CURVE_SAMPLES], amplitude * max, SUM_PLOT_Y),
+            GIBBS_COLOR.with_alpha(0.4),
+        );
+        let peak_value = amplitude * series.partial_sum(coefficients.peak, fourier.harmonics);
+        let (s_start, s_end) = (s(xs[0]), s(xs[CURVE_SAMPLES]));
+        for period in s_start.floor() as i32..=s_end.ceil() as i32 {
+            let peak_s = period as f32 + coefficients.peak;
+            if (s_start..=s_end).contains(&peak_s) {
+                let x = xs[0] + fourier.wave_length * (peak_s - s_start);
+                gizmos.circle_2d(
+                    to_screen(x, peak_value, SUM_PLOT_Y),
+                    PEAK_RADIUS,
+                    GIBBS_COLOR,
+                );
+            }
+        }
+    }
+
+    let newest = fourier.harmonics.checked_sub(1);
+    for (i, wave) in waves.iter().enumerate() {
+        if i >= DRAWN_COMPONENTS && Some(i) != newest {
+            continue;
+        }
+        let color = if Some(i) == newest {
+            NEWEST_COMPONENT_COLOR
+        } else {
+            component_color(i)
+        };
+        let raw = (*wave).into();
+        gizmos.linestrip_2d(
+            xs.iter()
+                .map(|&x| to_screen(x, calculate_u_raw(x, t, &raw, Vec3::Y).y, COMPONENTS_PLOT_Y)),
+            color,
+        );
+    }
+
+    draw_harmonic_bars(&mut gizmos, &fourier, series, &display_scale);
+}
+
+/// smallest and largest value of one period
+fn waveform_extremes(waveform: &Waveform) -> (f32, f32) {
+    (0..CURVE_SAMPLES)
+        .map(|i| waveform.value(i as f32 / CURVE_SAMPLES as f32))
+        .fold((f32::MAX, f32::MIN), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+/// faint, alternating shades so neighbouring harmonics can be told apart
+fn component_color(i: usize) -> Srgba {
+    let shades = [
+        Srgba::rgb(0.3, 0.6, 1.0),
+        Srgba::rgb(0.3, 0.9, 0.6),
+        Srgba::rgb(0.9, 0.5, 0.9),
+    ];
+    shades[i % shades.len()].with_alpha(0.6)
+}
+
+/// |mean| and Aₙ as bars across the screen, the ones in the partial sum highlighted
+fn draw_harmonic_bars(
+    gizmos: &mut Gizmos,
+    fourier: &Fourier,
+    series: &FourierSeries,
+    display_scale: &DisplayScale,
+) {
+    let bars: Vec<f32> = std::iter::once(series.mean.abs())
+        .chain(series.harmonics.iter().map(|harmonic| harmonic.amplitude))
+        .collect();
+    let largest = bars.iter().copied().fold(0.0, f32::max);
+    if largest <= 0.0 {
+        return;
+    }
+    let width = display_scale.screen_width / bars.len() as f32;
+    let left = -display_scale.screen_width / 2.0;
+    gizmos.line_2d(
+        Vec2::new(left, BARS_BASELINE_Y),
+        Vec2::new(-left, BARS_BASELINE_Y),
+        DARK_GRAY,
+    );
+    for (n, value) in bars.iter().enumerate() {
+        let height = BARS_HEIGHT * value / largest;
+        if height < 0.5 {
+            continue;
+        }
+        let color = if n <= fourier.harmonics {
+            NEWEST_COMPONENT_COLOR
+        } else {
+            DARK_GRAY
+        };
+        let x = left + (n as f32 + 0.5) * width;
+        gizmos.rect_2d(
+            Vec2::new(x, BARS_BASELINE_Y + height / 2.0),
+            0.0,
+            Vec2::new((width * 0.8).max(1.0), height),
+            color,
+        );
+    }
+}
@@ -0,0 +1,392 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::{TextInputSubmitEvent, TextInputValue};
+use uom::si::{
+    angle::radian,
+    f32::{Frequency, Length},
+    frequency::hertz,
+    length::meter,
+};
+
+use crate::{
+    fourier::{Fourier, FourierCoefficients, MAX_HARMONICS},
+    wave_ui::{
+        add_button, add_label, add_warning_label, generate_input_box, generate_label, parse_float,
+        AmplitudeInputMarker, FrequencyInputMarker, WarningMarker, WaveLengthInputMarker,
+    },
+};
+
+/// harmonics listed in the info label
+const LISTED_HARMONICS: usize = 5;
+/// relative to the waveform, below this a harmonic is listed as 0
+const NEGLIGIBLE_AMPLITUDE: f32 = 1e-4;
+
+#[derive(Resource)]
+pub struct FourierUiInputs {
+    pub waveform: String,
+    pub harmonics: String,
+    pub amplitude: String,
+    pub wave_length: String,
+    pub frequency: String,
+}
+
+impl From<&Fourier> for FourierUiInputs {
+    fn from(fourier: &Fourier) -> Self {
+        FourierUiInputs {
+            waveform: fourier.waveform.to_string(),
+            harmonics: fourier.harmonics.to_string(),
+            amplitude: fourier.amplitude.get::<meter>().to_string(),
+            wave_length: fourier.wave_length.get::<meter>().to_string(),
+            frequency: fourier.frequency.get::<hertz>().to_string(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct FourierUiInputEntities {
+    pub waveform: Entity,
+    pub harmonics: Entity,
+    pub amplitude: Entity,
+    pub wave_length: Entity,
+    pub frequency: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct WaveformInputMarker;
+#[derive(Component, Default)]
+pub struct HarmonicsInputMarker;
+#[derive(Component, Default)]
+pub struct MoreHarmonicsMarker;
+#[derive(Component, Default)]
+pub struct FewerHarmonicsMarker;
+#[derive(Component, Default)]
+pub struct BuildUpMarker;
+#[derive(Component, Default)]
+pub struct FourierInfoMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum FourierEvent {
+    More,
+    Fewer,
+    /// starts adding a harmonic at a time from the fundamental, or stops it
+    BuildUp,
+}
+
+/// adds ui to choose the waveform and the partial sum
+pub fn setup_fourier_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<FourierUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let waveform_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Waveform",
+        WaveformInputMarker,
+        form_state.waveform.clone(),
+    );
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "square, sawtooth,\ntriangle, pulse 0.2\nor f(x), x ∈ [0, 1)",
+    );
+    let harmonics_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Harmonics",
+        HarmonicsInputMarker,
+        form_state.harmonics.clone(),
+    );
+    let amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Amplitude (m)",
+        AmplitudeInputMarker,
+        form_state.amplitude.clone(),
+    );
+    let wave_length_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Wave length (m)",
+        WaveLengthInputMarker,
+        form_state.wave_length.clone(),
+    );
+    let frequency_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Frequency (hz)",
+        FrequencyInputMarker,
+        form_state.frequency.clone(),
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "+ harmonic",
+        MoreHarmonicsMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "- harmonic",
+        FewerHarmonicsMarker,
+    );
+    add_button(&mut commands, root_id, &font, "Build up", BuildUpMarker);
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((FourierInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(FourierUiInputEntities {
+        waveform: waveform_input,
+        harmonics: harmonics_input,
+        amplitude: amplitude_input,
+        wave_length: wave_length_input,
+        frequency: frequency_input,
+    });
+}
+
+pub fn fourier_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<FourierUiInputs>,
+    input_entities: Res<FourierUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.waveform {
+            inputs.waveform = event.value.clone();
+        } else if event.entity == input_entities.harmonics {
+            inputs.harmonics = event.value.clone();
+        } else if event.entity == input_entities.amplitude {
+            inputs.amplitude = event.value.clone();
+        } else if event.entity == input_entities.wave_length {
+            inputs.wave_length = event.value.clone();
+        } else if event.entity == input_entities.frequency {
+            inputs.frequency = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the waveform and the partial sum, showing errors in the warning label
+pub fn listen_fourier_ui_inputs(
+    inputs: Res<FourierUiInputs>,
+    mut fourier: ResMut<Fourier>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let warning = match parse_fourier_inputs(&inputs, &fourier) {
+        Ok(parsed) => {
+            fourier.set_if_neq(parsed);
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_fourier_inputs(inputs: &FourierUiInputs, current: &Fourier) -> Result<Fourier, String> {
+    let harmonics = inputs
+        .harmonics
+        .trim()
+        .parse::<usize>()
+        .map_err(|e| format!("Invalid number of harmonics: {}", e))?;
+    if !(1..=MAX_HARMONICS).contains(&harmonics) {
+        return Err(format!("Harmonics must be between 1 and {}", MAX_HARMONICS));
+    }
+    let wave_length = parse_float(&inputs.wave_length)?;
+    let frequency = parse_float(&inputs.frequency)?;
+    if wave_length <= 0.0 || frequency <= 0.0 {
+        return Err("Wave length and frequency must be positive".to_owned());
+    }
+    Ok(Fourier {
+        waveform: inputs.waveform.parse()?,
+        harmonics,
+        amplitude: Length::new::<meter>(parse_float(&inputs.amplitude)?),
+        wave_length: Length::new::<meter>(wave_length),
+        frequency: Frequency::new::<hertz>(frequency),
+        building: current.building,
+    })
+}
+
+/// keeps the harmonics field in step with the buttons and the build up
+pub fn sync_harmonics_input(
+    fourier: Res<Fourier>,
+    mut inputs: ResMut<FourierUiInputs>,
+    input_entities: Res<FourierUiInputEntities>,
+    mut input_values: Query<&mut TextInputValue>,
+) {
+    let harmonics = fourier.harmonics.to_string();
+    if !fourier.is_changed() || inputs.harmonics == harmonics {
+        return;
+    }
+    inputs.harmonics = harmonics;
+    if let Ok(mut input_value) = input_values.get_mut(input_entities.harmonics) {
+        input_value.0 = inputs.harmonics.clone();
+    }
+}
+
+/// partial sum, overshoot and the lowest harmonics
+pub fn update_fourier_info_label(
+    fourier: Res<Fourier>,
+    coefficients: Res<FourierCoefficients>,
+    mut label_query: Query<&mut Text, With<FourierInfoMarker>>,
+) {
+    let series = &coefficients.series;
+    let mut text = format!(
+        "N: {}{}\novershoot: {:.1}%\nmean: {:.3}",
+        fourier.harmonics,
+        if fourier.building { " (building)" } else { "" },
+        coefficients.overshoot * 100.0,
+        series.mean,
+    );
+    for harmonic in series.harmonics.iter().take(LISTED_HARMONICS) {
+        // the phase of a vanishing harmonic is only rounding noise
+        if harmonic.amplitude < NEGLIGIBLE_AMPLITUDE {
+            text.push_str(&format!("\nA{}: 0", harmonic.n));
+        } else {
+            text.push_str(&format!(
+                "\nA{}: {:.3} φ: {:.2}",
+                harmonic.n,
+                harmonic.amplitude,
+                harmonic.phase.get::<radian>(),
+            ));
+        }
+    }
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn more_harmonics_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<MoreHarmonicsMarker>),
+    >,
+    mut my_events: EventWriter<FourierEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        fourier_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            FourierEvent::More,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn fewer_harmonics_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<FewerHarmonicsMarker>),
+    >,
+    mut my_events: EventWriter<FourierEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        fourier_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            FourierEvent::Fewer,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn build_up_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<BuildUpMarker>),
+    >,
+    mut my_events: EventWriter<FourierEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        fourier_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            FourierEvent::BuildUp,
+        );
+    }
+}
+
+fn fourier_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<FourierEvent>,
+    event: FourierEvent,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(event);
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+/// the buttons stop the build up, so the partial sum stays where it was put
+pub fn listen_fourier_events(mut events: EventReader<FourierEvent>, mut fourier: ResMut<Fourier>) {
+    for event in events.read() {
+        match event {
+            FourierEvent::More => {
+                fourier.building = false;
+                fourier.harmonics = (fourier.harmonics + 1).min(MAX_HARMONICS);
+            }
+            FourierEvent::Fewer => {
+                fourier.building = false;
+                fourier.harmonics = fourier.harmonics.saturating_sub(1).max(1);
+            }
+            FourierEvent::BuildUp => {
+                if fourier.building {
+                    fourier.building = false;
+                } else {
+                    fourier.harmonics = 1;
+                    fourier.building = true;
+                }
+            }
+        }
+    }
+}
@@ -4,26 +4,31 @@ use bevy::{
     prelude::Gizmos,
 };
 
+use crate::display_scale::samples;
+
 /// draws function as a line, i.e. draws a line between each 2 consecutive points
+/// points where the function isn't finite, e.g. ln(x) for x ≤ 0, leave a gap
 pub fn draw_line_fn<F>(
     gizmos: &mut Gizmos,
-    range_start: i32,
-    range_end: i32,
-    step_size: usize,
+    range_start: f32,
+    range_end: f32,
     scaling: f32,
-    function: F,
+    mut function: F,
 ) where
-    F: Fn(f32) -> f32,
+    F: FnMut(f32) -> f32,
 {
     let x_scaling = scaling;
     let z_scaling = scaling;
 
     let mut last_point = None;
 
-    for x_int in (range_start..range_end).step_by(step_size) {
-        let x = x_int as f32;
+    for x in samples(range_start, range_end) {
         let z = function(x);
         let y = 0.0;
+        if !z.is_finite() {
+            last_point = None;
+            continue;
+        }
 
         if let Some((last_x, last_z)) = last_point {
             gizmos.line(
@@ -1,6 +1,5 @@
 use bevy::{color::palettes::css::DARK_GRAY, prelude::*};
 
-#[allow(dead_code)]
 pub fn add_grid_2d_system(app: &mut App) {
     app.add_systems(Update, draw_lines);
 }
@@ -0,0 +1,58 @@
+use bevy::{
+    color::palettes::css::{AQUA, ORANGE},
+    prelude::*,
+};
+use uom::si::{angle::degree, f32::Angle};
+use waves_core::fresnel::Interface;
+
+use crate::{
+    interface_ui::{
+        interface_text_listener, listen_toggle_interface_events, setup_interface_ui,
+        toggle_interface_button_handler, update_interface_label, ToggleInterfaceEvent,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// color of the reflected wave, the incident one is drawn like the wave without interface
+pub const REFLECTED_COLOR: Srgba = ORANGE;
+/// color of the transmitted wave
+pub const TRANSMITTED_COLOR: Srgba = AQUA;
+
+/// second medium beyond the plane x=0, replaces the wave along x with an incident, a reflected and
+/// a transmitted wave
+/// the wave entered in the form travels in the first medium
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct InterfaceInput {
+    pub enabled: bool,
+    pub interface: Interface,
+    /// θi, measured from the normal
+    pub incidence: Angle,
+}
+
+impl Default for InterfaceInput {
+    /// air to glass
+    fn default() -> Self {
+        InterfaceInput {
+            enabled: false,
+            interface: Interface { n1: 1.0, n2: 1.5 },
+            incidence: Angle::new::<degree>(45.0),
+        }
+    }
+}
+
+/// adds the interface panel, the em scene's draw system checks InterfaceInput::enabled
+pub fn add_interface_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.add_event::<ToggleInterfaceEvent>()
+        .init_resource::<InterfaceInput>()
+        .add_systems(Startup, setup_interface_ui)
+        .add_systems(
+            Update,
+            (
+                toggle_interface_button_handler,
+                listen_toggle_interface_events,
+                interface_text_listener,
+                update_interface_label,
+            ),
+        );
+}
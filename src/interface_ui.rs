@@ -0,0 +1,228 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{
+    angle::{degree, radian},
+    f32::Angle,
+};
+use waves_core::fresnel::AmplitudeRatio;
+
+use crate::{
+    interface::InterfaceInput,
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float},
+};
+
+/// incidence angles closer than this to Brewster's are reported as Brewster's
+const BREWSTER_TOLERANCE_DEGREES: f32 = 0.5;
+
+#[derive(Resource)]
+pub struct InterfaceUiInputEntities {
+    pub n1: Entity,
+    pub n2: Entity,
+    pub incidence: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct ToggleInterfaceMarker;
+#[derive(Component, Default)]
+pub struct RefractiveIndex1InputMarker;
+#[derive(Component, Default)]
+pub struct RefractiveIndex2InputMarker;
+#[derive(Component, Default)]
+pub struct IncidenceInputMarker;
+#[derive(Component, Default)]
+pub struct InterfaceInfoMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleInterfaceEvent;
+
+/// adds the interface controls, bottom, next to the medium panel
+pub fn setup_interface_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input: Res<InterfaceInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            bottom: Val::Px(0.0),
+            left: Val::Px(970.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Interface on/off",
+        ToggleInterfaceMarker,
+    );
+
+    let n1_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "n₁ (incident side)",
+        RefractiveIndex1InputMarker,
+        input.interface.n1.to_string(),
+    );
+    let n2_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "n₂",
+        RefractiveIndex2InputMarker,
+        input.interface.n2.to_string(),
+    );
+    let incidence_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Incidence θᵢ (°)",
+        IncidenceInputMarker,
+        input.incidence.get::<degree>().to_string(),
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((InterfaceInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(InterfaceUiInputEntities {
+        n1: n1_input,
+        n2: n2_input,
+        incidence: incidence_input,
+    });
+}
+
+pub fn interface_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<InterfaceUiInputEntities>,
+    mut input: ResMut<InterfaceInput>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.n1 || event.entity == input_entities.n2 {
+            match parse_float(&event.value) {
+                Ok(n) if n > 0.0 => {
+                    if event.entity == input_entities.n1 {
+                        input.interface.n1 = n;
+                    } else {
+                        input.interface.n2 = n;
+                    }
+                }
+                Ok(n) => println!("error: refractive index must be positive: {}", n),
+                Err(err) => println!("error: {}", err),
+            }
+        } else if event.entity == input_entities.incidence {
+            match parse_float(&event.value) {
+                Ok(degrees) if (0.0..90.0).contains(&degrees) => {
+                    input.incidence = Angle::new::<degree>(degrees)
+                }
+                Ok(degrees) => println!("error: incidence must be in [0°, 90°): {}", degrees),
+                Err(err) => println!("error: {}", err),
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_interface_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleInterfaceMarker>),
+    >,
+    mut my_events: EventWriter<ToggleInterfaceEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleInterfaceEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_interface_events(
+    mut events: EventReader<ToggleInterfaceEvent>,
+    mut input: ResMut<InterfaceInput>,
+) {
+    for _ in events.read() {
+        input.enabled = !input.enabled;
+    }
+}
+
+/// refraction, Brewster and critical angle, Fresnel coefficients and reflectances
+pub fn update_interface_label(
+    input: Res<InterfaceInput>,
+    mut label_query: Query<&mut Text, With<InterfaceInfoMarker>>,
+) {
+    let interface = input.interface;
+    let coefficients = interface.coefficients(input.incidence);
+    let brewster = interface.brewster_angle().get::<degree>();
+
+    let remark = if coefficients.total_internal_reflection {
+        "\ntotal internal reflection"
+    } else if (input.incidence.get::<degree>() - brewster).abs() < BREWSTER_TOLERANCE_DEGREES {
+        "\nBrewster: p isn't reflected"
+    } else {
+        ""
+    };
+    let text = format!(
+        "{}θₜ: {}\nθB: {:.2}° θc: {}\nr_s: {} r_p: {}\nt_s: {} t_p: {}\nR_s: {:.3} R_p: {:.3}{}",
+        if input.enabled { "" } else { "off\n" },
+        format_angle(interface.refraction_angle(input.incidence)),
+        brewster,
+        format_angle(interface.critical_angle()),
+        format_ratio(&coefficients.r_s),
+        format_ratio(&coefficients.r_p),
+        format_ratio(&coefficients.t_s),
+        format_ratio(&coefficients.t_p),
+        coefficients.reflectance_s(),
+        coefficients.reflectance_p(),
+        remark
+    );
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+fn format_angle(angle: Option<Angle>) -> String {
+    angle
+        .map(|angle| format!("{:.2}°", angle.get::<degree>()))
+        .unwrap_or_else(|| "-".to_owned())
+}
+
+/// real ratios with their sign, complex ones (total internal reflection) with their phase
+fn format_ratio(ratio: &AmplitudeRatio) -> String {
+    let phase = ratio.phase.get::<radian>();
+    if phase == 0.0 {
+        format!("{:.3}", ratio.magnitude)
+    } else if phase == PI {
+        format!("{:.3}", -ratio.magnitude)
+    } else {
+        format!("{:.2}∠{:.0}°", ratio.magnitude, ratio.phase.get::<degree>())
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt;
+
+use bevy::{
+    color::palettes::css::{DARK_GRAY, GRAY, WHITE, YELLOW},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use bevy_simple_text_input::{TextInputPlugin, TextInputSystem};
+use uom::si::{
+    angle::radian,
+    f32::{Angle, Length},
+    length::meter,
+};
+use waves_core::interference::{field, intensity, SourceArray};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::{add_display_scale, DisplayScale},
+    interference_ui::{
+        interference_text_listener, listen_interference_ui_inputs,
+        listen_toggle_interference_view_events, setup_interference_ui,
+        toggle_interference_view_button_handler, update_interference_info_label,
+        InterferenceUiInputs, ToggleInterferenceViewEvent,
+    },
+    wave_ui::{
+        focus, form_state_notifier_system, listen_wave_ui_inputs, text_listener, Freq, Phase,
+        UiInputs, UiInputsEvent, WaveLength,
+    },
+};
+
+/// texels of the heatmap, along x and y
+const HEATMAP_WIDTH: u32 = 200;
+const HEATMAP_HEIGHT: u32 = 150;
+/// screen units the intensity N² of a principal maximum is plotted with, right of the screen
+const SCREEN_PLOT_WIDTH: f32 = 100.0;
+/// points of the intensity plots along the screen
+const SCREEN_PLOT_SAMPLES: usize = 300;
+/// radius of the circles marking the sources
+const SOURCE_RADIUS: f32 = 4.0;
+/// color of the exact intensity on the screen
+const SCREEN_INTENSITY_COLOR: Srgba = WHITE;
+/// color of the far field intensity, the limit for a screen far away compared with d
+const FAR_FIELD_COLOR: Srgba = GRAY;
+/// color of the predicted maxima d·sinθ = mλ
+const MAXIMA_COLOR: Srgba = YELLOW;
+
+/// what the heatmap shows
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InterferenceView {
+    /// the superposed wave at the time on the clock
+    Field,
+    /// its time average, i.e. the fringes
+    #[default]
+    Intensity,
+}
+
+impl InterferenceView {
+    pub fn next(&self) -> InterferenceView {
+        match self {
+            InterferenceView::Field => InterferenceView::Intensity,
+            InterferenceView::Intensity => InterferenceView::Field,
+        }
+    }
+}
+
+impl fmt::Display for InterferenceView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterferenceView::Field => write!(f, "field"),
+            InterferenceView::Intensity => write!(f, "intensity"),
+        }
+    }
+}
+
+/// the sources and the screen as entered in the form, wave length, frequency and phase step come
+/// from the wave inputs
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Interference {
+    pub count: usize,
+    pub separation: Length,
+    pub slit_width: Length,
+    /// from the sources, along x
+    pub screen_distance: Length,
+    pub view: InterferenceView,
+}
+
+impl Default for Interference {
+    /// double slit, point like slits 2 m apart, screen at 8 m
+    fn default() -> Self {
+        Interference {
+            count: 2,
+            separation: Length::new::<meter>(2.0),
+            slit_width: Length::new::<meter>(0.0),
+            screen_distance: Length::new::<meter>(8.0),
+            view: InterferenceView::default(),
+        }
+    }
+}
+
+impl Interference {
+    pub fn array(&self, phase_step: Angle) -> SourceArray {
+        SourceArray {
+            count: self.count,
+            separation: self.separation,
+            slit_width: self.slit_width,
+            phase_step,
+        }
+    }
+
+    /// height of the drawn region, centered at y = 0
+    pub fn height(&self) -> Length {
+        self.screen_distance * (HEATMAP_HEIGHT as f32 / HEATMAP_WIDTH as f32)
+    }
+}
+
+/// the texture the heatmap is drawn into
+#[derive(Component)]
+pub struct InterferenceHeatmap(Handle<Image>);
+
+/// coherent point sources or slits, with the pattern between them and a screen
+pub fn add_interference_2d_system(app: &mut App) {
+    let interference = Interference::default();
+    app.add_event::<UiInputsEvent>()
+        .add_event::<ToggleInterferenceViewEvent>()
+        .add_plugins(TextInputPlugin)
+        .insert_resource(UiInputs {
+            amplitude: "1".to_owned(),
+            wave_length: "0.25".to_owned(),
+            frequency: "1".to_owned(),
+            phase: "0".to_owned(),
+        })
+        .insert_resource(InterferenceUiInputs::from(&interference))
+        .insert_resource(interference)
+        .add_systems(Startup, (setup_interference_ui, setup_heatmap))
+        .add_systems(Update, focus.before(TextInputSystem))
+        .add_systems(
+            Update,
+            (
+                update_heatmap,
+                draw_interference,
+                listen_wave_ui_inputs,
+                text_listener,
+                form_state_notifier_system,
+                interference_text_listener,
+                listen_interference_ui_inputs,
+                toggle_interference_view_button_handler,
+                listen_toggle_interference_view_events,
+                update_interference_info_label,
+                fit_display_scale,
+            ),
+        );
+    add_display_scale(app, DisplayScale::default_2d());
+}
+
+/// sources, screen and the plot right of it on screen, one period in PERIOD_ON_SCREEN
+fn fit_display_scale(
+    mut display_scale: ResMut<DisplayScale>,
+    interference: Res<Interference>,
+    frequency: Query<&Freq>,
+) {
+    if !display_scale.auto {
+        return;
+    }
+    let Ok(frequency) = frequency.get_single() else {
+        return;
+    };
+    let fitted = display_scale.fitted(interference.screen_distance * 1.25, 1.0 / frequency.0, 0.0);
+    display_scale.set_if_neq(fitted);
+}
+
+/// physical position (x from the sources, y) on screen, the region up to the screen is centered
+fn to_screen(
+    display_scale: &DisplayScale,
+    interference: &Interference,
+    (x, y): (f32, f32),
+) -> Vec2 {
+    let half_distance = interference.screen_distance / 2.0;
+    Vec2::new(
+        display_scale.x_to_screen(Length::new::<meter>(x) - half_distance),
+        display_scale.x_to_screen(Length::new::<meter>(y)),
+    )
+}
+
+fn setup_heatmap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d {
+            width: HEATMAP_WIDTH,
+            height: HEATMAP_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let handle = images.add(image);
+    commands.spawn((
+        SpriteBundle {
+            texture: handle.clone(),
+            ..default()
+        },
+        InterferenceHeatmap(handle),
+    ));
+}
+
+/// the field or the intensity between the sources and the screen, the intensity is only
+/// recomputed when the inputs change
+#[allow(clippy::too_many_arguments)]
+fn update_heatmap(
+    interference: Res<Interference>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+    phase: Query<&Phase>,
+    mut images: ResMut<Assets<Image>>,
+    mut heatmaps: Query<(&InterferenceHeatmap, &mut Sprite, &mut Transform)>,
+    mut drawn: Local<Option<(Interference, Length, Angle)>>,
+) {
+    let (Ok(wave_length), Ok(frequency), Ok(phase)) = (
+        wave_length.get_single(),
+        frequency.get_single(),
+        phase.get_single(),
+    ) else {
+        return;
+    };
+    let wave_length = wave_length.0;
+    if wave_length.get::<meter>() <= 0.0 {
+        return;
+    }
+
+    let distance = interference.screen_distance.get::<meter>();
+    let height = interference.height().get::<meter>();
+    for (_, mut sprite, mut transform) in &mut heatmaps {
+        let center = to_screen(&display_scale, &interference, (distance / 2.0, 0.0));
+        transform.translation = center.extend(0.0);
+        sprite.custom_size = Some(Vec2::new(
+            display_scale.x_to_screen(interference.screen_distance),
+            display_scale.x_to_screen(interference.height()),
+        ));
+    }
+
+    let key = (*interference, wave_length, phase.0);
+    if interference.view == InterferenceView::Intensity && *drawn == Some(key) {
+        return;
+    }
+    *drawn = Some(key);
+
+    let sources = interference.array(phase.0).point_sources();
+    // N² at a principal maximum
+    let max_intensity = (interference.count * interference.count) as f32;
+    let t = display_scale.physical_time(clock.elapsed);
+    for (heatmap, _, _) in &heatmaps {
+        let Some(image) = images.get_mut(&heatmap.0) else {
+            continue;
+        };
+        for row in 0..HEATMAP_HEIGHT {
+            // rows run from the top
+            let y = height / 2.0 - (row as f32 + 0.5) / HEATMAP_HEIGHT as f32 * height;
+            for column in 0..HEATMAP_WIDTH {
+                let x = (column as f32 + 0.5) / HEATMAP_WIDTH as f32 * distance;
+                let color = match interference.view {
+                    InterferenceView::Field => field_color(
+                        field(&sources, (x, y), wave_length, frequency.0, t)
+                            / interference.count as f32,
+                    ),
+                    InterferenceView::Intensity => {
+                        intensity_color(intensity(&sources, (x, y), wave_length) / max_intensity)
+                    }
+                };
+                let index = 4 * (row * HEATMAP_WIDTH + column) as usize;
+                image.data[index..index + 4].copy_from_slice(&color.to_srgba().to_u8_array());
+            }
+        }
+    }
+}
+
+/// red for crests, blue for troughs, relative in [-1, 1]
+fn field_color(relative: f32) -> Color {
+    let relative = relative.clamp(-1.0, 1.0);
+    Color::srgb(
+        0.5 + 0.5 * relative,
+        0.3 * (1.0 - relative.abs()) + 0.2,
+        0.5 - 0.5 * relative,
+    )
+}
+
+/// black to light yellow, relative in [0, 1]
+fn intensity_color(relative: f32) -> Color {
+    let relative = relative.clamp(0.0, 1.0);
+    Color::srgb(relative, relative, 0.6 * relative)
+}
+
+/// sources, screen, the intensity on it and the directions of the predicted maxima
+fn draw_interference(
+    mut gizmos: Gizmos,
+    interference: Res<Interference>,
+    display_scale: Res<DisplayScale>,
+    wave_length: Query<&WaveLength>,
+    phase: Query<&Phase>,
+) {
+    let (Ok(wave_length), Ok(phase)) = (wave_length.get_single(), phase.get_single()) else {
+        return;
+    };
+    let wave_length = wave_length.0;
+    if wave_length.get::<meter>() <= 0.0 {
+        return;
+    }
+    let array = interference.array(phase.0);
+    let to_screen = |position: (f32, f32)| to_screen(&display_scale, &interference, position);
+    let distance = interference.screen_distance.get::<meter>();
+    let half_height = interference.height().get::<meter>() / 2.0;
+
+    for n in 0..interference.count {
+        let center = array.center(n).get::<meter>();
+        let slit = interference.slit_width.get::<meter>() / 2.0;
+        if slit > 0.0 {
+            gizmos.line_2d(
+                to_screen((0.0, center - slit)),
+                to_screen((0.0, center + slit)),
+                WHITE,
+            );
+        } else {
+            gizmos.circle_2d(to_screen((0.0, center)), SOURCE_RADIUS, WHITE);
+        }
+    }
+
+    let screen_top = to_screen((distance, half_height));
+    let screen_bottom = to_screen((distance, -half_height));
+    gizmos.line_2d(screen_bottom, screen_top, DARK_GRAY);
+
+    let max_intensity = (interference.count * interference.count) as f32;
+    let sources = array.point_sources();
+    let ys = (0..=SCREEN_PLOT_SAMPLES)
+        .map(|i| -half_height + 2.0 * half_height * i as f32 / SCREEN_PLOT_SAMPLES as f32);
+    gizmos.linestrip_2d(
+        ys.clone().map(|y| {
+            let relative = intensity(&sources, (distance, y), wave_length) / max_intensity;
+            Vec2::new(
+                screen_top.x + relative * SCREEN_PLOT_WIDTH,
+                to_screen((0.0, y)).y,
+            )
+        }),
+        SCREEN_INTENSITY_COLOR,
+    );
+    gizmos.linestrip_2d(
+        ys.map(|y| {
+            let theta = Angle::new::<radian>(y.atan2(distance));
+            let relative = array.far_field_intensity(theta, wave_length) / max_intensity;
+            Vec2::new(
+                screen_top.x + relative * SCREEN_PLOT_WIDTH,
+                to_screen((0.0, y)).y,
+            )
+        }),
+        FAR_FIELD_COLOR,
+    );
+
+    for (_, theta) in array.maxima(wave_length) {
+        let y = distance * theta.get::<radian>().tan();
+        if y.abs() > half_height {
+            continue;
+        }
+        let on_screen = to_screen((distance, y));
+        gizmos.line_2d(
+            to_screen((0.0, 0.0)),
+            on_screen,
+            MAXIMA_COLOR.with_alpha(0.4),
+        );
+        gizmos.line_2d(
+            on_screen,
+            on_screen + Vec2::new(SCREEN_PLOT_WIDTH, 0.0),
+            MAXIMA_COLOR,
+        );
+    }
+}
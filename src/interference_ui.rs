@@ -0,0 +1,326 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{angle::degree, f32::Length, length::meter};
+
+use crate::{
+    interference::Interference,
+    wave_ui::{
+        add_button, add_label, add_warning_label, generate_input_box, generate_label, parse_float,
+        Amplitude, AmplitudeInputMarker, FrequencyInputMarker, Phase, PhaseMarker, UiInputEntities,
+        UiInputs, WarningMarker, WaveLength, WaveLengthInputMarker,
+    },
+};
+
+/// more sources make the pattern hard to compute every frame
+pub const MAX_SOURCES: usize = 20;
+
+#[derive(Resource)]
+pub struct InterferenceUiInputs {
+    pub count: String,
+    pub separation: String,
+    pub slit_width: String,
+    pub screen_distance: String,
+}
+
+impl From<&Interference> for InterferenceUiInputs {
+    fn from(interference: &Interference) -> Self {
+        InterferenceUiInputs {
+            count: interference.count.to_string(),
+            separation: interference.separation.get::<meter>().to_string(),
+            slit_width: interference.slit_width.get::<meter>().to_string(),
+            screen_distance: interference.screen_distance.get::<meter>().to_string(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct InterferenceUiInputEntities {
+    pub count: Entity,
+    pub separation: Entity,
+    pub slit_width: Entity,
+    pub screen_distance: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct SourceCountInputMarker;
+#[derive(Component, Default)]
+pub struct SeparationInputMarker;
+#[derive(Component, Default)]
+pub struct SlitWidthInputMarker;
+#[derive(Component, Default)]
+pub struct ScreenDistanceInputMarker;
+#[derive(Component, Default)]
+pub struct ToggleInterferenceViewMarker;
+#[derive(Component, Default)]
+pub struct InterferenceInfoMarker;
+
+/// switches the heatmap between the field and the intensity
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleInterferenceViewEvent;
+
+/// adds the wave inputs shared by all sources and the ones of the array and the screen
+pub fn setup_interference_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    wave_form_state: Res<UiInputs>,
+    form_state: Res<InterferenceUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Amplitude (m)",
+        AmplitudeInputMarker,
+        wave_form_state.amplitude.clone(),
+    );
+    let wave_length_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Wave length (m)",
+        WaveLengthInputMarker,
+        wave_form_state.wave_length.clone(),
+    );
+    let frequency_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Frequency (hz)",
+        FrequencyInputMarker,
+        wave_form_state.frequency.clone(),
+    );
+    let phase_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Phase step (rad)",
+        PhaseMarker,
+        wave_form_state.phase.clone(),
+    );
+    let count_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Sources",
+        SourceCountInputMarker,
+        form_state.count.clone(),
+    );
+    let separation_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Separation d (m)",
+        SeparationInputMarker,
+        form_state.separation.clone(),
+    );
+    let slit_width_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Slit width (m)",
+        SlitWidthInputMarker,
+        form_state.slit_width.clone(),
+    );
+    add_label(&mut commands, root_id, &font, "0 for point sources");
+    let screen_distance_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Screen at (m)",
+        ScreenDistanceInputMarker,
+        form_state.screen_distance.clone(),
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Field/Intensity",
+        ToggleInterferenceViewMarker,
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((InterferenceInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(UiInputEntities {
+        amplitude: amplitude_input,
+        wave_length: wave_length_input,
+        frequency: frequency_input,
+        phase: phase_input,
+    });
+    commands.insert_resource(InterferenceUiInputEntities {
+        count: count_input,
+        separation: separation_input,
+        slit_width: slit_width_input,
+        screen_distance: screen_distance_input,
+    });
+}
+
+pub fn interference_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<InterferenceUiInputs>,
+    input_entities: Res<InterferenceUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.count {
+            inputs.count = event.value.clone();
+        } else if event.entity == input_entities.separation {
+            inputs.separation = event.value.clone();
+        } else if event.entity == input_entities.slit_width {
+            inputs.slit_width = event.value.clone();
+        } else if event.entity == input_entities.screen_distance {
+            inputs.screen_distance = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the array and the screen, showing errors in the warning label
+pub fn listen_interference_ui_inputs(
+    inputs: Res<InterferenceUiInputs>,
+    mut interference: ResMut<Interference>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let warning = match parse_interference_inputs(&inputs, &interference) {
+        Ok(parsed) => {
+            interference.set_if_neq(parsed);
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_interference_inputs(
+    inputs: &InterferenceUiInputs,
+    current: &Interference,
+) -> Result<Interference, String> {
+    let count = inputs
+        .count
+        .trim()
+        .parse::<usize>()
+        .map_err(|e| format!("Invalid number of sources: {}", e))?;
+    if !(1..=MAX_SOURCES).contains(&count) {
+        return Err(format!("Sources must be between 1 and {}", MAX_SOURCES));
+    }
+    let separation = parse_float(&inputs.separation)?;
+    let slit_width = parse_float(&inputs.slit_width)?;
+    let screen_distance = parse_float(&inputs.screen_distance)?;
+    if separation <= 0.0 || screen_distance <= 0.0 {
+        return Err("Separation and screen must be positive".to_owned());
+    }
+    if !(0.0..=separation).contains(&slit_width) {
+        return Err("Slit width must be between 0 and d".to_owned());
+    }
+    Ok(Interference {
+        count,
+        separation: Length::new::<meter>(separation),
+        slit_width: Length::new::<meter>(slit_width),
+        screen_distance: Length::new::<meter>(screen_distance),
+        view: current.view,
+    })
+}
+
+/// fringe spacing and the predicted maxima
+pub fn update_interference_info_label(
+    interference: Res<Interference>,
+    wave_length: Query<&WaveLength>,
+    phase: Query<&Phase>,
+    amplitude: Query<&Amplitude>,
+    mut label_query: Query<&mut Text, With<InterferenceInfoMarker>>,
+) {
+    let (Ok(wave_length), Ok(phase), Ok(amplitude)) = (
+        wave_length.get_single(),
+        phase.get_single(),
+        amplitude.get_single(),
+    ) else {
+        return;
+    };
+    let wave_length = wave_length.0;
+    let array = interference.array(phase.0);
+    let maxima = array.maxima(wave_length);
+    let mut text = format!(
+        "view: {}\nLλ/d: {:.3} m\nI max: {:.3e} m²\nd·sinθ = mλ:",
+        interference.view,
+        (interference.screen_distance * wave_length / interference.separation).get::<meter>(),
+        // N²A², at a principal maximum
+        (amplitude.0.get::<meter>() * interference.count as f32).powi(2),
+    );
+    // the lowest orders, the list gets long for d ≫ λ
+    for (m, theta) in maxima.iter().filter(|(m, _)| m.abs() <= 3) {
+        text.push_str(&format!("\n m={}: {:.1}°", m, theta.get::<degree>()));
+    }
+    if maxima.is_empty() {
+        text.push_str(" -");
+    }
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_interference_view_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleInterferenceViewMarker>),
+    >,
+    mut my_events: EventWriter<ToggleInterferenceViewEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleInterferenceViewEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_interference_view_events(
+    mut events: EventReader<ToggleInterferenceViewEvent>,
+    mut interference: ResMut<Interference>,
+) {
+    for _ in events.read() {
+        interference.view = interference.view.next();
+    }
+}
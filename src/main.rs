@@ -1,49 +1,202 @@
+mod attenuation;
+mod attenuation_ui;
 mod camera_controller;
+mod cli;
+mod clock;
+mod clock_ui;
+mod curve;
+mod curve_ui;
 mod curves_2d;
 mod curves_3d;
 mod defocus;
+mod display_scale;
+mod display_scale_ui;
 mod electromagnetic_wave;
 mod electromagnetic_wave_ui;
+mod fdtd;
+mod fdtd_ui;
+mod fourier;
+mod fourier_ui;
 mod functions;
 mod grid_2d;
+mod interface;
+mod interface_ui;
+mod interference;
+mod interference_ui;
+mod medium;
+mod medium_ui;
+mod membrane;
+mod membrane_ui;
+mod oscilloscope;
+mod oscilloscope_ui;
+mod preset;
+mod preset_ui;
+mod propagation;
+mod propagation_ui;
 mod rotator;
+mod sample;
+mod sampled_wave;
+mod slider;
+mod spectrum;
+mod spectrum_ui;
+mod standing_wave;
+mod standing_wave_ui;
 mod system_2d;
 mod system_3d;
+mod vibrating_string;
+mod vibrating_string_ui;
 mod wave;
+mod wave_packet;
+mod wave_packet_ui;
 mod wave_ui;
 
 use bevy::app::App;
-#[allow(unused_imports)]
+use clap::Parser;
+use cli::{Cli, Command, RunArgs, Scene};
+use clock::add_clock_system;
+use curves_2d::add_curves_2d_system;
 use curves_3d::add_curves_3d_system;
-#[allow(unused_imports)]
 use electromagnetic_wave::add_electromagnetic_wave;
-#[allow(unused_imports)]
+use electromagnetic_wave_ui::PolarityInput;
+use fourier::add_fourier_2d_system;
 use grid_2d::add_grid_2d_system;
+use interference::add_interference_2d_system;
+use membrane::add_membrane_system;
+use preset::{add_preset_system, apply_preset, load_preset};
+use slider::add_slider_system;
+use standing_wave::add_standing_wave_2d_system;
+use standing_wave_ui::StandingWaveUiInputs;
 use system_2d::add_2d_axes;
 use system_2d::add_2d_space;
 use system_3d::add_3d_space;
+use vibrating_string::add_vibrating_string_2d_system;
+use vibrating_string_ui::VibratingStringUiInputs;
 use wave::add_wave_2d_system;
+use wave_ui::UiInputs;
 
 fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Some(Command::Sample(args)) => {
+            if let Err(e) = sample::run_sample(&args) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Run(args)) => {
+            if let Err(e) = run(&args) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        None => run(&RunArgs::default()).expect("default arguments are valid"),
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let preset = args.preset.as_deref().map(load_preset).transpose()?;
+    let scene = args
+        .scene
+        .or(preset.as_ref().map(|preset| preset.scene))
+        .unwrap_or_default();
+
     let app = &mut App::new();
-    // create_2d(app);
-    create_3d(app);
+    match scene {
+        Scene::EmWave => create_3d(app, add_electromagnetic_wave),
+        Scene::Wave2d => create_2d(app, args.grid, add_wave_2d_system),
+        Scene::StandingWave => create_2d(app, args.grid, add_standing_wave_2d_system),
+        Scene::VibratingString => create_2d(app, args.grid, add_vibrating_string_2d_system),
+        Scene::Interference => create_2d(app, args.grid, add_interference_2d_system),
+        Scene::Fourier => create_2d(app, args.grid, add_fourier_2d_system),
+        Scene::Membrane => create_3d(app, add_membrane_system),
+        Scene::Curves2d => create_2d(app, args.grid, add_curves_2d_system),
+        Scene::Curves3d => create_3d(app, add_curves_3d_system),
+    }
+    if args.grid && !scene.is_2d() {
+        eprintln!("warning: --grid is only supported in 2d scenes");
+    }
+    add_clock_system(app);
+    add_slider_system(app);
+    add_preset_system(app, scene, args.preset.clone());
+    if let Some(preset) = &preset {
+        if preset.scene != scene {
+            eprintln!("warning: the preset is for the {:?} scene", preset.scene);
+        }
+        for section in
+            apply_preset(app.world_mut(), preset).map_err(|e| format!("Invalid preset: {}", e))?
+        {
+            eprintln!(
+                "warning: preset section {} is ignored in this scene",
+                section
+            );
+        }
+    }
+    seed_inputs(app, args);
     app.run();
+    Ok(())
 }
 
-#[allow(dead_code)]
-fn create_2d(app: &mut App) {
+fn create_2d(app: &mut App, grid: bool, add_scene: fn(&mut App)) {
     add_2d_space(app);
-    // add_grid_2d_system(app);
+    if grid {
+        add_grid_2d_system(app);
+    }
     // grid completely hiding axes so draw axes on top.
     add_2d_axes(app);
-    // add_curves_2d_system(app);
-    add_wave_2d_system(app);
+    add_scene(app);
 }
 
-#[allow(dead_code)]
-fn create_3d(app: &mut App) {
+fn create_3d(app: &mut App, add_scene: fn(&mut App)) {
     add_3d_space(app);
-    // add_curves_3d_system(app);
-    add_electromagnetic_wave(app);
+    add_scene(app);
+}
+
+/// overrides the initial ui inputs of the scene with the ones passed via command line
+fn seed_inputs(app: &mut App, args: &RunArgs) {
+    let world = app.world_mut();
+
+    if let Some(mut inputs) = world.get_resource_mut::<UiInputs>() {
+        if let Some(amplitude) = args.amplitude {
+            inputs.amplitude = amplitude.to_string();
+        }
+        if let Some(wave_length) = args.wave_length {
+            inputs.wave_length = wave_length.to_string();
+        }
+        if let Some(frequency) = args.frequency {
+            inputs.frequency = frequency.to_string();
+        }
+        if let Some(phase) = args.phase {
+            inputs.phase = phase.to_string();
+        }
+    } else if let Some(mut inputs) = world.get_resource_mut::<StandingWaveUiInputs>() {
+        // wave length and frequency follow from the harmonic
+        if let Some(amplitude) = args.amplitude {
+            inputs.amplitude = amplitude.to_string();
+        }
+        if args.wave_length.is_some() || args.frequency.is_some() || args.phase.is_some() {
+            eprintln!("warning: only --amplitude is supported in the standing wave scene");
+        }
+    } else if let Some(mut inputs) = world.get_resource_mut::<VibratingStringUiInputs>() {
+        // the wave speed follows from tension and density
+        if let Some(amplitude) = args.amplitude {
+            inputs.amplitude = amplitude.to_string();
+        }
+        if args.wave_length.is_some() || args.frequency.is_some() || args.phase.is_some() {
+            eprintln!("warning: only --amplitude is supported in the vibrating string scene");
+        }
+    } else if args.amplitude.is_some()
+        || args.wave_length.is_some()
+        || args.frequency.is_some()
+        || args.phase.is_some()
+    {
+        eprintln!("warning: the selected scene has no wave parameters");
+    }
+
+    if let Some(polarity) = args.polarity {
+        match world.get_resource_mut::<PolarityInput>() {
+            Some(mut polarity_input) => *polarity_input = polarity.into(),
+            None => eprintln!("warning: --polarity is only supported in the em-wave scene"),
+        }
+    }
 }
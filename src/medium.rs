@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+use uom::si::f32::{Frequency, Length};
+use waves_core::medium::Medium;
+
+use crate::{
+    medium_ui::{medium_text_listener, setup_medium_ui, update_medium_label, MediumUiStatus},
+    wave_ui::add_text_input_plugin,
+};
+
+/// medium the wave of the scene travels in
+/// without medium the wave has the frequency entered in the form, with one the frequency follows
+/// from the entered wave length and the medium's dispersion relation
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct MediumInput(pub Option<Medium>);
+
+impl MediumInput {
+    /// frequency of a wave with the given wave length, the entered one if there's no medium or the
+    /// medium doesn't support the wave
+    pub fn frequency(&self, wave_length: Length, entered: Frequency) -> Frequency {
+        self.0
+            .and_then(|medium| medium.frequency(wave_length))
+            .unwrap_or(entered)
+    }
+}
+
+/// adds the medium panel, the scenes read MediumInput when building their waves
+pub fn add_medium_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.init_resource::<MediumInput>()
+        .init_resource::<MediumUiStatus>()
+        .add_systems(Startup, setup_medium_ui)
+        .add_systems(Update, (medium_text_listener, update_medium_label));
+}
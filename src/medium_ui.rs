@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{f32::Velocity, frequency::hertz, velocity::meter_per_second};
+
+use crate::{
+    medium::MediumInput,
+    wave_ui::{add_label, generate_input_box, generate_label, WaveLength},
+};
+
+#[derive(Resource)]
+pub struct MediumUiInputEntities {
+    pub medium: Entity,
+}
+
+/// error of the last submitted medium, if any
+#[derive(Resource, Default)]
+pub struct MediumUiStatus(pub Option<String>);
+
+#[derive(Component, Default)]
+pub struct MediumInputMarker;
+#[derive(Component, Default)]
+pub struct MediumInfoMarker;
+
+/// adds the medium input, bottom, next to the wave packet panel
+pub fn setup_medium_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    medium: Res<MediumInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            bottom: Val::Px(0.0),
+            left: Val::Px(750.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "e.g. cauchy 1.458 0.00354\nplasma 9e6, deep_water",
+    );
+
+    let medium_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Medium (none: as entered)",
+        MediumInputMarker,
+        medium
+            .0
+            .map(|medium| medium.to_string())
+            .unwrap_or_else(|| "none".to_owned()),
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((MediumInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(MediumUiInputEntities {
+        medium: medium_input,
+    });
+}
+
+pub fn medium_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<MediumUiInputEntities>,
+    mut medium: ResMut<MediumInput>,
+    mut status: ResMut<MediumUiStatus>,
+) {
+    for event in events.read() {
+        if event.entity != input_entities.medium {
+            continue;
+        }
+        let value = event.value.trim();
+        if value.is_empty() || value == "none" {
+            medium.0 = None;
+            status.0 = None;
+            continue;
+        }
+        match value.parse() {
+            Ok(parsed) => {
+                medium.0 = Some(parsed);
+                status.0 = None;
+            }
+            Err(err) => status.0 = Some(err),
+        }
+    }
+}
+
+/// refractive index, phase and group velocity at the wave length entered in the form
+pub fn update_medium_label(
+    medium: Res<MediumInput>,
+    status: Res<MediumUiStatus>,
+    wave_length: Query<&WaveLength>,
+    mut label_query: Query<&mut Text, With<MediumInfoMarker>>,
+) {
+    let text = match (&status.0, medium.0) {
+        (Some(err), _) => err.clone(),
+        (None, None) => "no dispersion".to_owned(),
+        (None, Some(medium)) => match wave_length.get_single() {
+            Ok(wave_length) => {
+                let frequency = medium.frequency(wave_length.0);
+                let format_velocity = |velocity: Option<Velocity>| {
+                    velocity
+                        .map(|v| format!("{:e} m/s", v.get::<meter_per_second>()))
+                        .unwrap_or_else(|| "-".to_owned())
+                };
+                format!(
+                    "f: {}\nn: {}\nv_p: {}\nv_g: {}",
+                    frequency
+                        .map(|f| format!("{:e} Hz", f.get::<hertz>()))
+                        .unwrap_or_else(|| "not supported".to_owned()),
+                    frequency
+                        .and_then(|f| medium.refractive_index(f))
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| "-".to_owned()),
+                    format_velocity(medium.phase_velocity(wave_length.0)),
+                    format_velocity(medium.group_velocity(wave_length.0)),
+                )
+            }
+            Err(_) => return,
+        },
+    };
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+    window::PrimaryWindow,
+};
+use uom::si::{
+    f32::{Frequency, Length, Time, Velocity},
+    frequency::hertz,
+    length::meter,
+    velocity::meter_per_second,
+};
+use waves_core::membrane::{
+    MembraneEdge, MembraneParameters, MembraneShape, MembraneSimulation, MembraneSource,
+    SourceGeometry,
+};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::{add_display_scale, DisplayScale},
+    membrane_ui::{
+        edge_button_handler, listen_edge_events, listen_membrane_ui_inputs,
+        listen_restart_membrane_events, membrane_text_listener, restart_membrane_button_handler,
+        setup_membrane_ui, update_membrane_info_label, MembraneUiInputs, RestartMembraneEvent,
+        ToggleEdgeEvent,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// cells along the larger side of the membrane
+const CELLS: usize = 160;
+/// upper bound of the steps per fixed step, catching up after a jump of the clock takes longer
+const MAX_STEPS_PER_TICK: usize = 2_000;
+/// 1/e radius of a dropped disturbance, in cells
+const DROP_WIDTH_CELLS: f32 = 3.0;
+/// mouse button dropping a disturbance, the left one grabs the camera
+const DROP_BUTTON: MouseButton = MouseButton::Right;
+const SURFACE_COLOR: Color = Color::srgb(0.2, 0.45, 0.8);
+
+/// the membrane as entered in the form
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Membrane {
+    pub parameters: MembraneParameters,
+    pub sources: Vec<MembraneSource>,
+    /// height of a disturbance dropped with the mouse
+    pub drop_amplitude: Length,
+}
+
+impl Default for Membrane {
+    /// ripple tank, 4 m x 4 m, c = 1 m/s, with two point sources 1 m apart
+    fn default() -> Self {
+        let source = |x: f32| MembraneSource {
+            geometry: SourceGeometry::Point {
+                x: Length::new::<meter>(x),
+                y: Length::new::<meter>(-1.0),
+            },
+            frequency: Frequency::new::<hertz>(2.0),
+            amplitude: Length::new::<meter>(0.05),
+        };
+        Membrane {
+            parameters: MembraneParameters {
+                shape: MembraneShape::Rectangle {
+                    width: Length::new::<meter>(4.0),
+                    depth: Length::new::<meter>(4.0),
+                },
+                wave_speed: Velocity::new::<meter_per_second>(1.0),
+                edge: MembraneEdge::Absorbing,
+            },
+            sources: vec![source(-0.5), source(0.5)],
+            drop_amplitude: Length::new::<meter>(0.1),
+        }
+    }
+}
+
+/// the running solver, the surface starts moving at `origin` on the clock
+#[derive(Resource, Default)]
+pub struct MembraneSolver {
+    pub simulation: Option<MembraneSimulation>,
+    /// physical time of the clock at which the surface was flat
+    pub origin: Time,
+}
+
+impl MembraneSolver {
+    /// flattens the surface and starts again at t
+    pub fn restart(&mut self, t: Time) {
+        self.simulation = None;
+        self.origin = t;
+    }
+}
+
+/// the mesh showing the surface
+#[derive(Component)]
+pub struct MembraneSurface {
+    /// cells and shape the triangles were built for
+    layout: Option<((usize, usize), MembraneShape)>,
+}
+
+/// membrane or ripple tank solved with finite differences, drawn as a lit mesh
+pub fn add_membrane_system(app: &mut App) {
+    let membrane = Membrane::default();
+    add_text_input_plugin(app);
+    app.add_event::<ToggleEdgeEvent>()
+        .add_event::<RestartMembraneEvent>()
+        .insert_resource(MembraneUiInputs::from(&membrane))
+        .insert_resource(membrane)
+        .init_resource::<MembraneSolver>()
+        .add_systems(Startup, (setup_membrane_ui, setup_membrane_surface))
+        .add_systems(FixedUpdate, step_membrane)
+        .add_systems(
+            Update,
+            (
+                update_membrane_surface,
+                drop_with_mouse,
+                membrane_text_listener,
+                listen_membrane_ui_inputs,
+                edge_button_handler,
+                listen_edge_events,
+                restart_membrane_button_handler,
+                listen_restart_membrane_events,
+                update_membrane_info_label,
+                fit_display_scale,
+            ),
+        );
+    add_display_scale(app, DisplayScale::default_3d());
+}
+
+/// fits the membrane on screen, one period of the first source, or the time to cross the membrane
+fn fit_display_scale(mut display_scale: ResMut<DisplayScale>, membrane: Res<Membrane>) {
+    if !display_scale.auto {
+        return;
+    }
+    let parameters = membrane.parameters;
+    let (width, depth) = parameters.shape.size();
+    let span = if width > depth { width } else { depth };
+    let period = match membrane.sources.first() {
+        Some(source) if source.frequency.get::<hertz>() > 0.0 => 1.0 / source.frequency,
+        _ => span / parameters.wave_speed,
+    };
+    let amplitude = membrane
+        .sources
+        .iter()
+        .map(|source| source.amplitude.get::<meter>().abs())
+        .fold(membrane.drop_amplitude.get::<meter>().abs(), f32::max);
+    let fitted = display_scale.fitted(span, period, amplitude);
+    display_scale.set_if_neq(fitted);
+}
+
+/// restarts the solver when the membrane changes, then runs it up to the time on the clock
+fn step_membrane(
+    membrane: Res<Membrane>,
+    mut state: ResMut<MembraneSolver>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+) {
+    let t = display_scale.physical_time(clock.elapsed);
+    if membrane.is_changed() {
+        state.restart(t);
+    }
+    let elapsed = t - state.origin;
+
+    let outdated = match &state.simulation {
+        Some(simulation) => elapsed < simulation.time() && simulation.steps() > 0,
+        None => true,
+    };
+    if outdated {
+        state.simulation = Some(MembraneSimulation::new(
+            membrane.parameters,
+            membrane.sources.clone(),
+            CELLS,
+        ));
+    }
+    if let Some(simulation) = &mut state.simulation {
+        simulation.advance_to(elapsed, MAX_STEPS_PER_TICK);
+    }
+}
+
+fn setup_membrane_surface(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(mesh),
+            material: materials.add(StandardMaterial {
+                base_color: SURFACE_COLOR,
+                perceptual_roughness: 0.3,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            }),
+            ..default()
+        },
+        MembraneSurface { layout: None },
+    ));
+}
+
+/// moves the vertices to the displacement of the nodes, physical x and y along screen x and z,
+/// the triangles are rebuilt when the grid changes
+fn update_membrane_surface(
+    state: Res<MembraneSolver>,
+    display_scale: Res<DisplayScale>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut surfaces: Query<(&Handle<Mesh>, &mut MembraneSurface)>,
+) {
+    let Some(simulation) = &state.simulation else {
+        return;
+    };
+    let (cells_x, cells_y) = simulation.cells();
+    let row = cells_x + 1;
+    let dx = display_scale.x_to_screen(simulation.cell_size());
+
+    let mut positions = Vec::with_capacity(row * (cells_y + 1));
+    for j in 0..=cells_y {
+        for i in 0..=cells_x {
+            let (x, y) = simulation.position(i, j);
+            positions.push([
+                display_scale.x_to_screen(x),
+                display_scale.amplitude_to_screen(simulation.displacement(i, j).get::<meter>()),
+                display_scale.x_to_screen(y),
+            ]);
+        }
+    }
+    // central differences of the screen heights, one-sided along the border of the grid
+    let height = |i: usize, j: usize| positions[j * row + i][1];
+    let mut normals = Vec::with_capacity(positions.len());
+    for j in 0..=cells_y {
+        for i in 0..=cells_x {
+            let (left, right) = (i.saturating_sub(1), (i + 1).min(cells_x));
+            let (back, front) = (j.saturating_sub(1), (j + 1).min(cells_y));
+            let slope_x = (height(right, j) - height(left, j)) / (dx * (right - left) as f32);
+            let slope_z = (height(i, front) - height(i, back)) / (dx * (front - back) as f32);
+            normals.push(Vec3::new(-slope_x, 1.0, -slope_z).normalize().to_array());
+        }
+    }
+
+    let layout = (simulation.cells(), simulation.parameters.shape);
+    for (handle, mut surface) in &mut surfaces {
+        let Some(mesh) = meshes.get_mut(handle) else {
+            continue;
+        };
+        if surface.layout != Some(layout) {
+            mesh.insert_indices(Indices::U32(triangles(simulation)));
+            surface.layout = Some(layout);
+        }
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions.clone());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals.clone());
+    }
+}
+
+/// two triangles per cell whose corners are all on the membrane
+fn triangles(simulation: &MembraneSimulation) -> Vec<u32> {
+    let (cells_x, cells_y) = simulation.cells();
+    let row = cells_x + 1;
+    let mut indices = vec![];
+    for j in 0..cells_y {
+        for i in 0..cells_x {
+            let corners = [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)];
+            if !corners.iter().all(|&(i, j)| simulation.contains(i, j)) {
+                continue;
+            }
+            let [a, b, c, d] = corners.map(|(i, j)| (j * row + i) as u32);
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+    indices
+}
+
+/// the drop button puts a gaussian bump where the cursor ray hits the resting surface
+fn drop_with_mouse(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    display_scale: Res<DisplayScale>,
+    membrane: Res<Membrane>,
+    mut state: ResMut<MembraneSolver>,
+) {
+    if !mouse_button_input.just_pressed(DROP_BUTTON) {
+        return;
+    }
+    let (Ok(window), Some(simulation)) = (windows.get_single(), &mut state.simulation) else {
+        return;
+    };
+    // the scene has one camera that renders to the window, the axis labels have their own
+    let Some((camera, camera_transform)) = cameras
+        .iter()
+        .find(|(camera, _)| camera.order == 0 && camera.is_active)
+    else {
+        return;
+    };
+    let Some(ray) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
+    else {
+        return;
+    };
+    let Some(distance) = ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y)) else {
+        return;
+    };
+    let point = ray.get_point(distance);
+    let to_meters = |screen: f32| Length::new::<meter>(screen / display_scale.length);
+    simulation.drop(
+        (to_meters(point.x), to_meters(point.z)),
+        membrane.drop_amplitude,
+        simulation.cell_size() * DROP_WIDTH_CELLS,
+    );
+}
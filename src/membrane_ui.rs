@@ -0,0 +1,323 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{
+    f32::{Length, Velocity},
+    frequency::hertz,
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+use waves_core::membrane::{format_sources, fundamental_frequency, parse_sources};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::DisplayScale,
+    membrane::{Membrane, MembraneSolver},
+    wave_ui::{
+        add_button, add_label, add_warning_label, generate_input_box, generate_label, parse_float,
+        WarningMarker,
+    },
+};
+
+#[derive(Resource)]
+pub struct MembraneUiInputs {
+    pub shape: String,
+    pub wave_speed: String,
+    pub sources: String,
+    pub drop_amplitude: String,
+}
+
+impl From<&Membrane> for MembraneUiInputs {
+    fn from(membrane: &Membrane) -> Self {
+        MembraneUiInputs {
+            shape: membrane.parameters.shape.to_string(),
+            wave_speed: membrane
+                .parameters
+                .wave_speed
+                .get::<meter_per_second>()
+                .to_string(),
+            sources: format_sources(&membrane.sources),
+            drop_amplitude: membrane.drop_amplitude.get::<meter>().to_string(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct MembraneUiInputEntities {
+    pub shape: Entity,
+    pub wave_speed: Entity,
+    pub sources: Entity,
+    pub drop_amplitude: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct MembraneShapeInputMarker;
+#[derive(Component, Default)]
+pub struct MembraneWaveSpeedInputMarker;
+#[derive(Component, Default)]
+pub struct MembraneSourcesInputMarker;
+#[derive(Component, Default)]
+pub struct DropAmplitudeInputMarker;
+#[derive(Component, Default)]
+pub struct MembraneEdgeMarker;
+#[derive(Component, Default)]
+pub struct RestartMembraneMarker;
+#[derive(Component, Default)]
+pub struct MembraneInfoMarker;
+
+/// switches the edge between fixed and absorbing
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ToggleEdgeEvent;
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RestartMembraneEvent;
+
+/// adds ui to configure the membrane
+pub fn setup_membrane_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<MembraneUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let shape_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Shape (m)",
+        MembraneShapeInputMarker,
+        form_state.shape.clone(),
+    );
+    add_label(&mut commands, root_id, &font, "rect 4 3\ndrum 2");
+    let wave_speed_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Speed (m/s)",
+        MembraneWaveSpeedInputMarker,
+        form_state.wave_speed.clone(),
+    );
+    let sources_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Sources",
+        MembraneSourcesInputMarker,
+        form_state.sources.clone(),
+    );
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "point x y f A\nline x₁ y₁ x₂ y₂ f A\n; separated, none",
+    );
+    let drop_amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Drop (m)",
+        DropAmplitudeInputMarker,
+        form_state.drop_amplitude.clone(),
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    add_button(&mut commands, root_id, &font, "Edge", MembraneEdgeMarker);
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Restart",
+        RestartMembraneMarker,
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((MembraneInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(MembraneUiInputEntities {
+        shape: shape_input,
+        wave_speed: wave_speed_input,
+        sources: sources_input,
+        drop_amplitude: drop_amplitude_input,
+    });
+}
+
+pub fn membrane_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<MembraneUiInputs>,
+    input_entities: Res<MembraneUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.shape {
+            inputs.shape = event.value.clone();
+        } else if event.entity == input_entities.wave_speed {
+            inputs.wave_speed = event.value.clone();
+        } else if event.entity == input_entities.sources {
+            inputs.sources = event.value.clone();
+        } else if event.entity == input_entities.drop_amplitude {
+            inputs.drop_amplitude = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the membrane, showing errors in the warning label
+pub fn listen_membrane_ui_inputs(
+    inputs: Res<MembraneUiInputs>,
+    mut membrane: ResMut<Membrane>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let warning = match parse_membrane_inputs(&inputs, &membrane) {
+        Ok(parsed) => {
+            membrane.set_if_neq(parsed);
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_membrane_inputs(
+    inputs: &MembraneUiInputs,
+    current: &Membrane,
+) -> Result<Membrane, String> {
+    let wave_speed = parse_float(&inputs.wave_speed)?;
+    if wave_speed <= 0.0 {
+        return Err("Speed must be positive".to_owned());
+    }
+    let mut membrane = current.clone();
+    membrane.parameters.shape = inputs.shape.parse()?;
+    membrane.parameters.wave_speed = Velocity::new::<meter_per_second>(wave_speed);
+    membrane.sources = parse_sources(&inputs.sources)?;
+    membrane.drop_amplitude = Length::new::<meter>(parse_float(&inputs.drop_amplitude)?);
+    Ok(membrane)
+}
+
+/// edge, lowest mode, grid and the largest displacement
+pub fn update_membrane_info_label(
+    membrane: Res<Membrane>,
+    state: Res<MembraneSolver>,
+    mut label_query: Query<&mut Text, With<MembraneInfoMarker>>,
+) {
+    let mut text = format!(
+        "edge: {}\nf₁: {}",
+        membrane.parameters.edge,
+        fundamental_frequency(&membrane.parameters)
+            .map(|f| format!("{:.3} hz", f.get::<hertz>()))
+            .unwrap_or_else(|| "-".to_owned()),
+    );
+    if let Some(simulation) = &state.simulation {
+        let (cells_x, cells_y) = simulation.cells();
+        text.push_str(&format!(
+            "\ncells: {}x{}\nΔx: {:.3} m\nΔt: {:.2e} s\nt: {:.2} s\nmax |u|: {:.3} m",
+            cells_x,
+            cells_y,
+            simulation.cell_size().get::<meter>(),
+            simulation.time_step().get::<second>(),
+            simulation.time().get::<second>(),
+            simulation.max_displacement().get::<meter>(),
+        ));
+    }
+    text.push_str("\nright click: drop");
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn edge_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<MembraneEdgeMarker>),
+    >,
+    mut my_events: EventWriter<ToggleEdgeEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(ToggleEdgeEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn restart_membrane_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RestartMembraneMarker>),
+    >,
+    mut my_events: EventWriter<RestartMembraneEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(RestartMembraneEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_edge_events(
+    mut events: EventReader<ToggleEdgeEvent>,
+    mut membrane: ResMut<Membrane>,
+) {
+    for _ in events.read() {
+        membrane.parameters.edge = membrane.parameters.edge.next();
+    }
+}
+
+/// flattens the surface, clearing the drops, the sources start again from now on
+pub fn listen_restart_membrane_events(
+    mut events: EventReader<RestartMembraneEvent>,
+    mut state: ResMut<MembraneSolver>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+) {
+    for _ in events.read() {
+        state.restart(display_scale.physical_time(clock.elapsed));
+    }
+}
@@ -0,0 +1,383 @@
+use std::{collections::VecDeque, f32::consts::PI};
+
+use bevy::{
+    color::palettes::css::{AQUA, DARK_GRAY, FUCHSIA, LIME, ORANGE},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use uom::si::{angle::radian, f32::Angle, time::second};
+use waves_core::spectrum::{dominant, resample, spectrum, Window};
+
+use crate::{
+    display_scale::DisplayScale,
+    oscilloscope_ui::{
+        listen_oscilloscope_events, oscilloscope_component_button_handler,
+        oscilloscope_text_listener, setup_oscilloscope_ui, update_oscilloscope_label,
+        OscilloscopeEvent, OscilloscopeUiStatus,
+    },
+    sampled_wave::{add_sampled_wave, FieldComponent, SampledWave},
+    wave_ui::add_text_input_plugin,
+};
+
+pub const MAX_PROBES: usize = 4;
+/// colors of the probes, their markers and traces
+pub const PROBE_COLORS: [Srgba; MAX_PROBES] = [ORANGE, AQUA, FUCHSIA, LIME];
+/// values kept per probe, about half a minute at 60 frames per second
+const TRACE_CAPACITY: usize = 2048;
+/// a phase lag needs at least this many values of both probes
+const MIN_TRACE_VALUES: usize = 16;
+/// pixels of the screen
+pub const SCOPE_WIDTH: u32 = 320;
+pub const SCOPE_HEIGHT: u32 = 140;
+/// pixels kept free above and below the largest value
+const SCOPE_MARGIN: f32 = 4.0;
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct OscilloscopeInput {
+    /// m, along the direction the wave is drawn in
+    pub probes: Vec<f32>,
+    pub component: FieldComponent,
+    /// seconds of animation time across the screen, so it doesn't depend on the scale
+    pub duration: f32,
+}
+
+impl Default for OscilloscopeInput {
+    fn default() -> Self {
+        OscilloscopeInput {
+            probes: vec![],
+            component: FieldComponent::default(),
+            duration: 4.0,
+        }
+    }
+}
+
+/// "none" or the positions separated by spaces or commas, e.g. "0 0.5"
+pub fn parse_probes(s: &str) -> Result<Vec<f32>, String> {
+    let s = s.trim();
+    if s.is_empty() || s == "none" {
+        return Ok(vec![]);
+    }
+    let probes = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<f32>()
+                .map_err(|e| format!("Invalid probe {}: {}", part, e))
+        })
+        .collect::<Result<Vec<f32>, String>>()?;
+    if probes.len() > MAX_PROBES {
+        return Err(format!("At most {} probes", MAX_PROBES));
+    }
+    Ok(probes)
+}
+
+pub fn format_probes(probes: &[f32]) -> String {
+    if probes.is_empty() {
+        return "none".to_owned();
+    }
+    probes
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// keeps the last `capacity` values, dropping the oldest
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    values: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        self.values.pop_front()
+    }
+
+    pub fn oldest(&self) -> Option<&T> {
+        self.values.front()
+    }
+
+    pub fn newest(&self) -> Option<&T> {
+        self.values.back()
+    }
+
+    /// oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter()
+    }
+}
+
+/// (physical time in s, u or E) at each probe, in the order of OscilloscopeInput::probes
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ProbeTraces(pub Vec<RingBuffer<(f32, Vec3)>>);
+
+impl ProbeTraces {
+    pub fn reset(&mut self, probes: usize) {
+        self.0 = (0..probes)
+            .map(|_| RingBuffer::new(TRACE_CAPACITY))
+            .collect();
+    }
+}
+
+/// the texture the traces are drawn into
+#[derive(Component)]
+pub struct OscilloscopeScreen(pub Handle<Image>);
+
+/// probes along the wave the scene draws, with their values over time as oscilloscope traces
+/// the renderer of the scene writes SampledWave
+pub fn add_oscilloscope_system(app: &mut App) {
+    add_text_input_plugin(app);
+    add_sampled_wave(app);
+    app.add_event::<OscilloscopeEvent>()
+        .init_resource::<OscilloscopeInput>()
+        .init_resource::<ProbeTraces>()
+        .init_resource::<OscilloscopeUiStatus>()
+        .add_systems(Startup, setup_oscilloscope_ui)
+        .add_systems(
+            Update,
+            (
+                (record_probe_traces, draw_oscilloscope).chain(),
+                draw_probe_markers,
+                oscilloscope_text_listener,
+                oscilloscope_component_button_handler,
+                listen_oscilloscope_events,
+                update_oscilloscope_label,
+            ),
+        );
+}
+
+/// appends the wave at each probe, starting over when the clock goes back
+/// probes off screen get no values
+fn record_probe_traces(
+    input: Res<OscilloscopeInput>,
+    sampled: Res<SampledWave>,
+    display_scale: Res<DisplayScale>,
+    mut traces: ResMut<ProbeTraces>,
+) {
+    if traces.0.len() != input.probes.len() {
+        traces.reset(input.probes.len());
+    }
+    if sampled.values.is_empty() {
+        return;
+    }
+    let t = sampled.t.get::<second>();
+    let last = traces
+        .0
+        .iter()
+        .filter_map(|trace| trace.newest().map(|&(t, _)| t))
+        .fold(f32::MIN, f32::max);
+    if t < last {
+        traces.reset(input.probes.len());
+    } else if t == last {
+        // paused
+        return;
+    }
+    let window_start = t - display_scale.physical_time(input.duration).get::<second>();
+    for (x, trace) in input.probes.iter().zip(traces.0.iter_mut()) {
+        if let Some(value) = sampled.at(*x) {
+            trace.push((t, value));
+        }
+        while trace.oldest().is_some_and(|&(t, _)| t < window_start) {
+            trace.pop_oldest();
+        }
+    }
+}
+
+/// the newest value on the right, a grid line every second of animation time
+fn draw_oscilloscope(
+    input: Res<OscilloscopeInput>,
+    traces: Res<ProbeTraces>,
+    sampled: Res<SampledWave>,
+    display_scale: Res<DisplayScale>,
+    mut images: ResMut<Assets<Image>>,
+    mut screens: Query<(&OscilloscopeScreen, &mut Style)>,
+) {
+    for (screen, mut style) in &mut screens {
+        let display = if input.probes.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        if style.display != display {
+            style.display = display;
+        }
+        if input.probes.is_empty() {
+            continue;
+        }
+        let Some(image) = images.get_mut(&screen.0) else {
+            continue;
+        };
+        for pixel in image.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[0, 0, 0, 255]);
+        }
+        let mut set_pixel = |column: i32, row: i32, color: Srgba| {
+            if (0..SCOPE_WIDTH as i32).contains(&column) && (0..SCOPE_HEIGHT as i32).contains(&row)
+            {
+                let index = 4 * (row as u32 * SCOPE_WIDTH + column as u32) as usize;
+                image.data[index..index + 4].copy_from_slice(&color.to_u8_array());
+            }
+        };
+
+        let middle = SCOPE_HEIGHT as i32 / 2;
+        for column in 0..SCOPE_WIDTH as i32 {
+            set_pixel(column, middle, DARK_GRAY);
+        }
+        let end = sampled.t.get::<second>();
+        let span = display_scale.physical_time(input.duration).get::<second>();
+        let to_column = |t: f32| ((t - (end - span)) / span * (SCOPE_WIDTH - 1) as f32).round();
+        let shown_second = display_scale.physical_time(1.0).get::<second>();
+        for i in 0..input.duration.floor() as i32 + 1 {
+            let column = to_column(end - i as f32 * shown_second) as i32;
+            for row in (0..SCOPE_HEIGHT as i32).step_by(3) {
+                set_pixel(column, row, DARK_GRAY);
+            }
+        }
+
+        let largest = traces
+            .0
+            .iter()
+            .flat_map(|trace| trace.iter())
+            .map(|&(_, value)| input.component.of(value).abs())
+            .fold(0.0, f32::max);
+        if largest <= 0.0 || span <= 0.0 {
+            continue;
+        }
+        let to_row =
+            |value: f32| (middle as f32 - value / largest * (middle as f32 - SCOPE_MARGIN)).round();
+        for (trace, color) in traces.0.iter().zip(PROBE_COLORS) {
+            let points: Vec<(i32, i32)> = trace
+                .iter()
+                .map(|&(t, value)| {
+                    (
+                        to_column(t) as i32,
+                        to_row(input.component.of(value)) as i32,
+                    )
+                })
+                .collect();
+            for pair in points.windows(2) {
+                draw_line(pair[0], pair[1], |column, row| {
+                    set_pixel(column, row, color)
+                });
+            }
+        }
+    }
+}
+
+/// Bresenham, both ends included
+fn draw_line<F>(from: (i32, i32), to: (i32, i32), mut set_pixel: F)
+where
+    F: FnMut(i32, i32),
+{
+    let (mut x, mut y) = from;
+    let (dx, dy) = ((to.0 - x).abs(), -(to.1 - y).abs());
+    let (step_x, step_y) = ((to.0 - x).signum(), (to.1 - y).signum());
+    let mut error = dx + dy;
+    loop {
+        set_pixel(x, y);
+        if (x, y) == to {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+fn draw_probe_markers(
+    mut gizmos: Gizmos,
+    input: Res<OscilloscopeInput>,
+    sampled: Res<SampledWave>,
+    display_scale: Res<DisplayScale>,
+) {
+    if sampled.values.is_empty() {
+        return;
+    }
+    let up = sampled.up_axis * display_scale.screen_amplitude * 1.5;
+    for (x, color) in input.probes.iter().zip(PROBE_COLORS) {
+        let center = sampled.x_axis * (x * display_scale.length);
+        gizmos.line(center - up, center + up, color);
+    }
+}
+
+/// how much the other probe lags behind the reference, at the strongest frequency of the
+/// reference over the time both have values, in (−π, π]
+/// for a wave cos(kx − ωt + φ) this is k(x_other − x_reference), up to multiples of 2π
+pub fn phase_lag(
+    reference: &RingBuffer<(f32, Vec3)>,
+    other: &RingBuffer<(f32, Vec3)>,
+    component: FieldComponent,
+) -> Option<Angle> {
+    let (&(reference_start, _), &(reference_end, _)) = (reference.oldest()?, reference.newest()?);
+    let (&(other_start, _), &(other_end, _)) = (other.oldest()?, other.newest()?);
+    let (start, end) = (
+        reference_start.max(other_start),
+        reference_end.min(other_end),
+    );
+    let overlap = |trace: &RingBuffer<(f32, Vec3)>| -> Vec<(f32, f32)> {
+        trace
+            .iter()
+            .filter(|(t, _)| (start..=end).contains(t))
+            .map(|&(t, value)| (t, component.of(value)))
+            .collect()
+    };
+    let (reference, other) = (overlap(reference), overlap(other));
+    if reference.len() < MIN_TRACE_VALUES || other.len() < MIN_TRACE_VALUES || end <= start {
+        return None;
+    }
+    let count = reference.len().next_power_of_two();
+    let spacing = (end - start) / (count - 1) as f32;
+    let reference = spectrum(&resample(&reference, count), spacing, Window::Hann);
+    let other = spectrum(&resample(&other, count), spacing, Window::Hann);
+    let peak = dominant(&reference)?;
+    let index = reference.iter().position(|line| line == peak)?;
+    let lag = peak.phase.get::<radian>() - other.get(index)?.phase.get::<radian>();
+    Some(Angle::new::<radian>(wrap_phase(lag)))
+}
+
+/// into (−π, π]
+pub fn wrap_phase(phase: f32) -> f32 {
+    let wrapped = (phase + PI).rem_euclid(2.0 * PI) - PI;
+    if wrapped == -PI {
+        PI
+    } else {
+        wrapped
+    }
+}
+
+pub fn new_screen_image() -> Image {
+    Image::new_fill(
+        Extent3d {
+            width: SCOPE_WIDTH,
+            height: SCOPE_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
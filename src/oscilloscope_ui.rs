@@ -0,0 +1,233 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{angle::radian, length::meter};
+
+use crate::{
+    oscilloscope::{
+        format_probes, new_screen_image, parse_probes, phase_lag, wrap_phase, OscilloscopeInput,
+        OscilloscopeScreen, ProbeTraces, SCOPE_HEIGHT, SCOPE_WIDTH,
+    },
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float, WaveLength},
+};
+
+/// names of the probe colors, in the order of PROBE_COLORS
+const PROBE_COLOR_NAMES: [&str; 4] = ["orange", "aqua", "fuchsia", "lime"];
+
+#[derive(Resource)]
+pub struct OscilloscopeUiInputEntities {
+    pub probes: Entity,
+    pub duration: Entity,
+}
+
+/// error of the last submitted input, if any
+#[derive(Resource, Default)]
+pub struct OscilloscopeUiStatus(pub Option<String>);
+
+#[derive(Component, Default)]
+pub struct ProbesInputMarker;
+#[derive(Component, Default)]
+pub struct OscilloscopeDurationInputMarker;
+#[derive(Component, Default)]
+pub struct OscilloscopeComponentMarker;
+#[derive(Component, Default)]
+pub struct OscilloscopeInfoMarker;
+
+/// switches the traced component of the sampled vectors
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OscilloscopeEvent;
+
+/// adds the probe inputs and the screen, top, left of the side panel under the scale
+pub fn setup_oscilloscope_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    input: Res<OscilloscopeInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(80.0),
+            right: Val::Px(140.0),
+            width: Val::Px(SCOPE_WIDTH as f32),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let probes_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Probes x (m), e.g. 0 0.5",
+        ProbesInputMarker,
+        format_probes(&input.probes),
+    );
+    let duration_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Trace (s shown)",
+        OscilloscopeDurationInputMarker,
+        input.duration.to_string(),
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Component",
+        OscilloscopeComponentMarker,
+    );
+
+    let image = images.add(new_screen_image());
+    let screen = commands
+        .spawn((
+            OscilloscopeScreen(image.clone()),
+            ImageBundle {
+                image: UiImage::new(image),
+                style: Style {
+                    width: Val::Px(SCOPE_WIDTH as f32),
+                    height: Val::Px(SCOPE_HEIGHT as f32),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((OscilloscopeInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[screen, spawned_info_label]);
+
+    commands.insert_resource(OscilloscopeUiInputEntities {
+        probes: probes_input,
+        duration: duration_input,
+    });
+}
+
+pub fn oscilloscope_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<OscilloscopeUiInputEntities>,
+    mut input: ResMut<OscilloscopeInput>,
+    mut traces: ResMut<ProbeTraces>,
+    mut status: ResMut<OscilloscopeUiStatus>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.probes {
+            match parse_probes(&event.value) {
+                Ok(probes) => {
+                    traces.reset(probes.len());
+                    input.probes = probes;
+                    status.0 = None;
+                }
+                Err(err) => status.0 = Some(err),
+            }
+        } else if event.entity == input_entities.duration {
+            match parse_float(&event.value) {
+                Ok(f) if f > 0.0 => {
+                    input.duration = f;
+                    status.0 = None;
+                }
+                Ok(_) => status.0 = Some("Trace duration must be positive".to_owned()),
+                Err(err) => status.0 = Some(err),
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn oscilloscope_component_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<OscilloscopeComponentMarker>),
+    >,
+    mut my_events: EventWriter<OscilloscopeEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(OscilloscopeEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+/// the traces keep all components, so they don't start over
+pub fn listen_oscilloscope_events(
+    mut events: EventReader<OscilloscopeEvent>,
+    mut input: ResMut<OscilloscopeInput>,
+) {
+    for _ in events.read() {
+        input.component = input.component.next();
+    }
+}
+
+/// probes with their colors and the phase lag behind the first one, measured and expected
+pub fn update_oscilloscope_label(
+    input: Res<OscilloscopeInput>,
+    traces: Res<ProbeTraces>,
+    status: Res<OscilloscopeUiStatus>,
+    wave_length: Query<&WaveLength>,
+    mut label_query: Query<&mut Text, With<OscilloscopeInfoMarker>>,
+) {
+    let text = if let Some(err) = &status.0 {
+        err.clone()
+    } else if input.probes.is_empty() {
+        "no probes".to_owned()
+    } else {
+        let mut text = format!("{} component, grid: 1 s shown", input.component);
+        let wave_number = wave_length
+            .get_single()
+            .ok()
+            .map(|wave_length| 2.0 * PI / wave_length.0.get::<meter>());
+        for (i, (x, name)) in input.probes.iter().zip(PROBE_COLOR_NAMES).enumerate() {
+            text.push_str(&format!("\n{}: x = {} m", name, x));
+            if i == 0 {
+                continue;
+            }
+            let measured = traces
+                .0
+                .first()
+                .zip(traces.0.get(i))
+                .and_then(|(reference, other)| phase_lag(reference, other, input.component));
+            text.push_str(&format!(
+                ", Δφ: {}",
+                measured
+                    .map(|lag| format!("{:.2} rad", lag.get::<radian>()))
+                    .unwrap_or_else(|| "-".to_owned())
+            ));
+            if let Some(k) = wave_number {
+                text.push_str(&format!(
+                    " (kΔx: {:.2})",
+                    wrap_phase(k * (x - input.probes[0]))
+                ));
+            }
+        }
+        text
+    };
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
@@ -0,0 +1,1301 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInputValue;
+use ron::{extensions::Extensions, ser::PrettyConfig};
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    angle::{degree, radian},
+    electrical_conductivity::siemens_per_meter,
+    f32::{Angle, ElectricalConductivity, Frequency, Length, ReciprocalLength},
+    force::newton,
+    frequency::hertz,
+    length::meter,
+    linear_mass_density::kilogram_per_meter,
+    reciprocal_length::reciprocal_meter,
+    velocity::meter_per_second,
+};
+use waves_core::{
+    electromagnetic_wave::Handedness,
+    fdtd::DielectricRegion,
+    fresnel::Interface,
+    medium::Medium,
+    membrane::{format_sources, MembraneEdge},
+    standing_wave::BoundaryConditions,
+    vibrating_string::{InitialShape, StringEnd},
+    wave::SphericalDirection,
+};
+
+use crate::{
+    attenuation::AttenuationInput,
+    attenuation_ui::AttenuationUiInputEntities,
+    camera_controller::CameraController,
+    cli::{PolarityArg, Scene},
+    clock::SimulationClock,
+    display_scale::DisplayScale,
+    electromagnetic_wave_ui::{
+        EllipticalPolarityInput, EllipticalPolarityUiInputEntities, EllipticalPolarityUiInputs,
+        HandednessInput, MagneticFieldDisplayScale, MagneticFieldUiInputEntities,
+        MagneticFieldUiInputs, PolarityInput,
+    },
+    fdtd::FdtdInput,
+    fdtd_ui::FdtdUiInputEntities,
+    fourier::Fourier,
+    fourier_ui::{FourierUiInputEntities, FourierUiInputs},
+    interface::InterfaceInput,
+    interface_ui::InterfaceUiInputEntities,
+    interference::{Interference, InterferenceView},
+    interference_ui::{InterferenceUiInputEntities, InterferenceUiInputs},
+    medium::MediumInput,
+    medium_ui::MediumUiInputEntities,
+    membrane::Membrane,
+    membrane_ui::{MembraneUiInputEntities, MembraneUiInputs},
+    preset_ui::{
+        listen_preset_ui_inputs, load_preset_button_handler, preset_text_listener,
+        save_preset_button_handler, setup_preset_ui, PresetUiEvent, PresetUiInputs,
+    },
+    propagation::PropagationInput,
+    propagation_ui::PropagationUiInputEntities,
+    rotator::Rotator,
+    standing_wave_ui::{StandingWave, StandingWaveUiInputEntities, StandingWaveUiInputs},
+    vibrating_string::VibratingString,
+    vibrating_string_ui::{VibratingStringUiInputEntities, VibratingStringUiInputs},
+    wave::WaveUserParameters,
+    wave_packet::WavePacketInput,
+    wave_packet_ui::WavePacketUiInputEntities,
+    wave_ui::{
+        add_text_input_plugin, parse_float, Amplitude, ComponentWaves, Freq, Phase,
+        UiInputEntities, UiInputs, WaveLength,
+    },
+};
+
+/// everything needed to reproduce a scene: scene type, wave parameters, polarity, camera, display scaling and time
+/// stored as ron or toml, depending on the file extension
+/// sections are optional, missing ones keep the defaults of the scene
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ScenePreset {
+    pub scene: Scene,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wave: Option<WavePreset>,
+    /// waves added to the superposition, only used in the wave-2d scene
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub superposition: Vec<WavePreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standing_wave: Option<StandingWavePreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vibrating_string: Option<VibratingStringPreset>,
+    /// sources and screen, the wave section sets wave length, frequency and phase step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interference: Option<InterferencePreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fourier: Option<FourierPreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub membrane: Option<MembranePreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub polarity: Option<PolarityPreset>,
+    /// gaussian packet around the wave, wave-2d and em-wave scenes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packet: Option<PacketPreset>,
+    /// dispersion model and coefficients, e.g. "cauchy 1.458 0.00354", or "none"
+    /// wave-2d and em-wave scenes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<String>,
+    /// absorption and damping, wave-2d and em-wave scenes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attenuation: Option<AttenuationPreset>,
+    /// second medium beyond the plane x=0, em-wave scene only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<InterfacePreset>,
+    /// propagation direction of the wave, em-wave scene only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<DirectionPreset>,
+    /// finite-difference simulation drawn over the wave, em-wave scene only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fdtd: Option<FdtdPreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraPreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayPreset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockPreset>,
+}
+
+impl ScenePreset {
+    /// rejects values the form inputs can't hold and that would get the scene stuck, e.g. a clock
+    /// that doesn't run
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(medium) = &self.medium {
+            parse_medium_preset(medium)?;
+        }
+        if let Some(fdtd) = &self.fdtd {
+            parse_dielectric_preset(&fdtd.dielectric)?;
+        }
+        if let Some(display) = &self.display {
+            let scales = [
+                ("length", display.length),
+                ("amplitude", display.amplitude),
+                ("time", display.time),
+            ];
+            for (name, scale) in scales {
+                if let Some(scale) = scale.filter(|scale| !scale.is_finite() || *scale <= 0.0) {
+                    return Err(format!(
+                        "display.{} must be finite and > 0, got {}",
+                        name, scale
+                    ));
+                }
+            }
+        }
+        if let Some(clock) = &self.clock {
+            if !clock.t.is_finite() {
+                return Err(format!("clock.t must be finite, got {}", clock.t));
+            }
+            if !clock.rate.is_finite() || clock.rate == 0.0 {
+                return Err(format!(
+                    "clock.rate must be finite and not 0, got {}",
+                    clock.rate
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// "none" or a medium like in the form
+fn parse_medium_preset(medium: &str) -> Result<Option<Medium>, String> {
+    match medium.trim() {
+        "none" => Ok(None),
+        spec => spec.parse().map(Some).map_err(|e| format!("medium: {}", e)),
+    }
+}
+
+/// "none" or a dielectric region like in the form
+fn parse_dielectric_preset(dielectric: &str) -> Result<Option<DielectricRegion>, String> {
+    match dielectric.trim() {
+        "none" => Ok(None),
+        spec => spec
+            .parse()
+            .map(Some)
+            .map_err(|e| format!("fdtd.dielectric: {}", e)),
+    }
+}
+
+/// amplitude in m for mechanical waves, V/m for the electromagnetic wave
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct WavePreset {
+    pub amplitude: f32,
+    /// m
+    pub wave_length: f32,
+    /// hz
+    pub frequency: f32,
+    /// rad
+    #[serde(default)]
+    pub phase: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct StandingWavePreset {
+    /// m
+    pub amplitude: f32,
+    /// m
+    pub length: f32,
+    /// m/s
+    pub wave_speed: f32,
+    pub harmonic: u32,
+    #[serde(default)]
+    pub boundary_conditions: BoundaryConditionsPreset,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryConditionsPreset {
+    #[default]
+    FixedFixed,
+    FixedFree,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VibratingStringPreset {
+    /// m
+    pub amplitude: f32,
+    /// m
+    pub length: f32,
+    /// N
+    pub tension: f32,
+    /// kg/m
+    pub linear_density: f32,
+    /// e.g. "pluck 0.25", "drawn" uses `drawn`
+    pub shape: String,
+    /// e.g. "rest", "right" or "2 gaussian 0.5 0.05"
+    #[serde(default = "default_velocity")]
+    pub velocity: String,
+    #[serde(default)]
+    pub left_end: StringEndPreset,
+    #[serde(default)]
+    pub right_end: StringEndPreset,
+    /// points (x/L, u/amplitude) of a drawn shape
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drawn: Vec<[f32; 2]>,
+}
+
+fn default_velocity() -> String {
+    "rest".to_owned()
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum StringEndPreset {
+    #[default]
+    Fixed,
+    Free,
+    Absorbing,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct InterferencePreset {
+    pub sources: usize,
+    /// m
+    pub separation: f32,
+    /// m, 0 for point sources
+    #[serde(default)]
+    pub slit_width: f32,
+    /// m
+    pub screen_distance: f32,
+    #[serde(default)]
+    pub view: InterferenceViewPreset,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum InterferenceViewPreset {
+    Field,
+    #[default]
+    Intensity,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FourierPreset {
+    /// "square", "sawtooth", "triangle", "pulse <duty>" or an expression in x ∈ [0, 1)
+    pub waveform: String,
+    /// in the partial sum
+    pub harmonics: usize,
+    /// m
+    pub amplitude: f32,
+    /// m, of the fundamental
+    pub wave_length: f32,
+    /// hz, of the fundamental
+    pub frequency: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MembranePreset {
+    /// e.g. "rect 4 3" or "drum 2", in m
+    pub shape: String,
+    /// m/s
+    pub wave_speed: f32,
+    #[serde(default)]
+    pub edge: MembraneEdgePreset,
+    /// e.g. "point -0.5 -1 2 0.05; point 0.5 -1 2 0.05", or "none"
+    #[serde(default = "default_sources")]
+    pub sources: String,
+    /// m, height of a disturbance dropped with the mouse
+    #[serde(default = "default_drop_amplitude")]
+    pub drop_amplitude: f32,
+}
+
+fn default_sources() -> String {
+    "none".to_owned()
+}
+
+fn default_drop_amplitude() -> f32 {
+    0.1
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum MembraneEdgePreset {
+    #[default]
+    Fixed,
+    Absorbing,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct PolarityPreset {
+    pub kind: PolarityArg,
+    #[serde(default)]
+    pub handedness: HandednessPreset,
+    /// only used for elliptical polarity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jones: Option<JonesPreset>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum HandednessPreset {
+    #[default]
+    Right,
+    Left,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct PacketPreset {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// in wave lengths
+    pub envelope_width: f32,
+    /// group velocity / phase velocity
+    pub group_velocity_ratio: f32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// fields that aren't set keep the scene's current value
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct AttenuationPreset {
+    /// 1/m, mechanical waves only, the em wave derives α from σ and εᵣ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpha: Option<f32>,
+    /// 1/s
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gamma: Option<f32>,
+    /// S/m, em wave only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conductivity: Option<f32>,
+    /// em wave only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_permittivity: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct InterfacePreset {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub n1: f32,
+    pub n2: f32,
+    /// degrees, from the normal
+    pub incidence: f32,
+}
+
+/// spherical angles of k̂, see SphericalDirection
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct DirectionPreset {
+    /// degrees from z
+    pub theta: f32,
+    /// degrees from x towards y
+    pub phi: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FdtdPreset {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub two_dimensional: bool,
+    /// "x_min x_max εᵣ" or "x_min x_max y_min y_max εᵣ", or "none"
+    #[serde(default = "default_dielectric")]
+    pub dielectric: String,
+}
+
+fn default_dielectric() -> String {
+    "none".to_owned()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct JonesPreset {
+    pub y: f32,
+    pub z: f32,
+    /// rad
+    pub phase_difference: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct CameraPreset {
+    pub translation: [f32; 3],
+    /// quaternion x, y, z, w
+    pub rotation: [f32; 4],
+}
+
+/// fields that aren't set keep the scene's current value
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+#[serde(default)]
+pub struct DisplayPreset {
+    /// see MagneticFieldDisplayScale, em wave only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magnetic_field_scale: Option<f32>,
+    /// screen units per m, see DisplayScale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<f32>,
+    /// screen units per unit of the wave quantity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amplitude: Option<f32>,
+    /// physical seconds per second of animation time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f32>,
+    /// if not set, scales given in the preset turn the automatic scaling off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto: Option<bool>,
+}
+
+/// e.g. to start frozen at a specific t
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ClockPreset {
+    /// s
+    pub t: f32,
+    #[serde(default = "default_rate")]
+    pub rate: f32,
+    #[serde(default)]
+    pub paused: bool,
+}
+
+fn default_rate() -> f32 {
+    1.0
+}
+
+/// the scene the app was started with, scenes can't be switched at runtime
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct CurrentScene(pub Scene);
+
+/// camera of a preset applied before the camera was spawned
+#[derive(Resource, Debug, Clone, Copy)]
+struct PendingCameraPreset(CameraPreset);
+
+/// adds the ui to load and save presets, for any scene
+pub fn add_preset_system(app: &mut App, scene: Scene, path: Option<PathBuf>) {
+    add_text_input_plugin(app);
+    app.add_event::<PresetUiEvent>()
+        .insert_resource(CurrentScene(scene))
+        .insert_resource(PresetUiInputs {
+            path: path
+                .unwrap_or_else(|| PathBuf::from("preset.ron"))
+                .display()
+                .to_string(),
+        })
+        .add_systems(Startup, setup_preset_ui)
+        .add_systems(PostStartup, apply_pending_camera_preset)
+        .add_systems(
+            Update,
+            (
+                preset_text_listener,
+                load_preset_button_handler,
+                save_preset_button_handler,
+                listen_preset_ui_inputs,
+            ),
+        );
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PresetFormat {
+    Ron,
+    Toml,
+}
+
+impl PresetFormat {
+    fn from_path(path: &Path) -> Result<PresetFormat, String> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ron") => Ok(PresetFormat::Ron),
+            Some("toml") => Ok(PresetFormat::Toml),
+            _ => Err(format!(
+                "Unknown preset format: {}, expected .ron or .toml",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// allows writing `wave: (...)` instead of `wave: Some((...))`
+fn ron_options() -> ron::Options {
+    ron::Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+pub fn load_preset(path: &Path) -> Result<ScenePreset, String> {
+    let format = PresetFormat::from_path(path)?;
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    match format {
+        PresetFormat::Ron => ron_options().from_str(&content).map_err(|e| e.to_string()),
+        PresetFormat::Toml => toml::from_str(&content).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+pub fn save_preset(preset: &ScenePreset, path: &Path) -> Result<(), String> {
+    let format = PresetFormat::from_path(path)?;
+    let content = match format {
+        PresetFormat::Ron => ron_options()
+            .to_string_pretty(preset, PrettyConfig::default())
+            .map_err(|e| e.to_string()),
+        PresetFormat::Toml => toml::to_string_pretty(preset).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| format!("Failed to serialize preset: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// sets the inputs of the running scene to the preset
+/// returns the names of the sections the scene doesn't have, which are ignored, or the first
+/// invalid value, in which case nothing is applied
+pub fn apply_preset(world: &mut World, preset: &ScenePreset) -> Result<Vec<&'static str>, String> {
+    preset.validate()?;
+    let mut ignored = vec![];
+
+    if let Some(wave) = &preset.wave {
+        match world.get_resource_mut::<UiInputs>() {
+            Some(mut inputs) => {
+                inputs.amplitude = wave.amplitude.to_string();
+                inputs.wave_length = wave.wave_length.to_string();
+                inputs.frequency = wave.frequency.to_string();
+                inputs.phase = wave.phase.to_string();
+            }
+            None => ignored.push("wave"),
+        }
+        if let Some(entities) = world.get_resource::<UiInputEntities>() {
+            let values = [
+                (entities.amplitude, wave.amplitude),
+                (entities.wave_length, wave.wave_length),
+                (entities.frequency, wave.frequency),
+                (entities.phase, wave.phase),
+            ];
+            set_input_values(world, &values);
+        }
+    }
+
+    if !preset.superposition.is_empty() {
+        match world.get_resource_mut::<ComponentWaves>() {
+            Some(mut component_waves) => {
+                component_waves.0 = preset.superposition.iter().map(|&w| w.into()).collect();
+            }
+            None => ignored.push("superposition"),
+        }
+    }
+
+    if let Some(standing_wave) = &preset.standing_wave {
+        match world.get_resource_mut::<StandingWaveUiInputs>() {
+            Some(mut inputs) => {
+                inputs.amplitude = standing_wave.amplitude.to_string();
+                inputs.length = standing_wave.length.to_string();
+                inputs.wave_speed = standing_wave.wave_speed.to_string();
+                inputs.harmonic = standing_wave.harmonic.to_string();
+            }
+            None => ignored.push("standing_wave"),
+        }
+        if let Some(mut model) = world.get_resource_mut::<StandingWave>() {
+            model.0.boundary_conditions = standing_wave.boundary_conditions.into();
+        }
+        if let Some(entities) = world.get_resource::<StandingWaveUiInputEntities>() {
+            let values = [
+                (entities.amplitude, standing_wave.amplitude),
+                (entities.length, standing_wave.length),
+                (entities.wave_speed, standing_wave.wave_speed),
+            ];
+            let harmonic = entities.harmonic;
+            set_input_values(world, &values);
+            set_input_value(world, harmonic, standing_wave.harmonic.to_string());
+        }
+    }
+
+    if let Some(string) = &preset.vibrating_string {
+        // the drawn shape and the ends aren't in the form, "drawn" keeps the model's shape
+        if let Some(mut model) = world.get_resource_mut::<VibratingString>() {
+            model.left_end = string.left_end.into();
+            model.right_end = string.right_end.into();
+            if !string.drawn.is_empty() {
+                model.initial.shape =
+                    InitialShape::Drawn(string.drawn.iter().map(|&[x, u]| (x, u)).collect());
+            }
+        }
+        match world.get_resource_mut::<VibratingStringUiInputs>() {
+            Some(mut inputs) => {
+                inputs.amplitude = string.amplitude.to_string();
+                inputs.length = string.length.to_string();
+                inputs.tension = string.tension.to_string();
+                inputs.linear_density = string.linear_density.to_string();
+                inputs.shape = string.shape.clone();
+                inputs.velocity = string.velocity.clone();
+            }
+            None => ignored.push("vibrating_string"),
+        }
+        if let Some(entities) = world.get_resource::<VibratingStringUiInputEntities>() {
+            let values = [
+                (entities.amplitude, string.amplitude),
+                (entities.length, string.length),
+                (entities.tension, string.tension),
+                (entities.linear_density, string.linear_density),
+            ];
+            let (shape, velocity) = (entities.shape, entities.velocity);
+            set_input_values(world, &values);
+            set_input_value(world, shape, string.shape.clone());
+            set_input_value(world, velocity, string.velocity.clone());
+        }
+    }
+
+    if let Some(interference) = &preset.interference {
+        // the view isn't in the form
+        if let Some(mut model) = world.get_resource_mut::<Interference>() {
+            model.view = interference.view.into();
+        }
+        match world.get_resource_mut::<InterferenceUiInputs>() {
+            Some(mut inputs) => {
+                inputs.count = interference.sources.to_string();
+                inputs.separation = interference.separation.to_string();
+                inputs.slit_width = interference.slit_width.to_string();
+                inputs.screen_distance = interference.screen_distance.to_string();
+            }
+            None => ignored.push("interference"),
+        }
+        if let Some(entities) = world.get_resource::<InterferenceUiInputEntities>() {
+            let values = [
+                (entities.separation, interference.separation),
+                (entities.slit_width, interference.slit_width),
+                (entities.screen_distance, interference.screen_distance),
+            ];
+            let count = entities.count;
+            set_input_values(world, &values);
+            set_input_value(world, count, interference.sources.to_string());
+        }
+    }
+
+    if let Some(fourier) = &preset.fourier {
+        match world.get_resource_mut::<FourierUiInputs>() {
+            Some(mut inputs) => {
+                inputs.waveform = fourier.waveform.clone();
+                inputs.harmonics = fourier.harmonics.to_string();
+                inputs.amplitude = fourier.amplitude.to_string();
+                inputs.wave_length = fourier.wave_length.to_string();
+                inputs.frequency = fourier.frequency.to_string();
+            }
+            None => ignored.push("fourier"),
+        }
+        if let Some(entities) = world.get_resource::<FourierUiInputEntities>() {
+            let values = [
+                (entities.amplitude, fourier.amplitude),
+                (entities.wave_length, fourier.wave_length),
+                (entities.frequency, fourier.frequency),
+            ];
+            let (waveform, harmonics) = (entities.waveform, entities.harmonics);
+            set_input_values(world, &values);
+            set_input_value(world, waveform, fourier.waveform.clone());
+            set_input_value(world, harmonics, fourier.harmonics.to_string());
+        }
+    }
+
+    if let Some(membrane) = &preset.membrane {
+        // the edge isn't in the form
+        if let Some(mut model) = world.get_resource_mut::<Membrane>() {
+            model.parameters.edge = membrane.edge.into();
+        }
+        match world.get_resource_mut::<MembraneUiInputs>() {
+            Some(mut inputs) => {
+                inputs.shape = membrane.shape.clone();
+                inputs.wave_speed = membrane.wave_speed.to_string();
+                inputs.sources = membrane.sources.clone();
+                inputs.drop_amplitude = membrane.drop_amplitude.to_string();
+            }
+            None => ignored.push("membrane"),
+        }
+        if let Some(entities) = world.get_resource::<MembraneUiInputEntities>() {
+            let values = [
+                (entities.wave_speed, membrane.wave_speed),
+                (entities.drop_amplitude, membrane.drop_amplitude),
+            ];
+            let (shape, sources) = (entities.shape, entities.sources);
+            set_input_values(world, &values);
+            set_input_value(world, shape, membrane.shape.clone());
+            set_input_value(world, sources, membrane.sources.clone());
+        }
+    }
+
+    if let Some(polarity) = &preset.polarity {
+        match world.get_resource_mut::<PolarityInput>() {
+            Some(mut polarity_input) => *polarity_input = polarity.kind.into(),
+            None => ignored.push("polarity"),
+        }
+        if let Some(mut handedness) = world.get_resource_mut::<HandednessInput>() {
+            handedness.0 = polarity.handedness.into();
+        }
+        if let Some(jones) = &polarity.jones {
+            if let Some(mut inputs) = world.get_resource_mut::<EllipticalPolarityUiInputs>() {
+                inputs.amplitude_y = jones.y.to_string();
+                inputs.amplitude_z = jones.z.to_string();
+                inputs.phase_difference = jones.phase_difference.to_string();
+            }
+            if let Some(entities) = world.get_resource::<EllipticalPolarityUiInputEntities>() {
+                let values = [
+                    (entities.amplitude_y, jones.y),
+                    (entities.amplitude_z, jones.z),
+                    (entities.phase_difference, jones.phase_difference),
+                ];
+                set_input_values(world, &values);
+            }
+        }
+    }
+
+    if let Some(packet) = &preset.packet {
+        match world.get_resource_mut::<WavePacketInput>() {
+            Some(mut input) => {
+                input.enabled = packet.enabled;
+                input.envelope_width = packet.envelope_width;
+                input.group_velocity_ratio = packet.group_velocity_ratio;
+            }
+            None => ignored.push("packet"),
+        }
+        if let Some(entities) = world.get_resource::<WavePacketUiInputEntities>() {
+            let values = [
+                (entities.envelope_width, packet.envelope_width),
+                (entities.group_velocity_ratio, packet.group_velocity_ratio),
+            ];
+            set_input_values(world, &values);
+        }
+    }
+
+    if let Some(medium) = &preset.medium {
+        let parsed = parse_medium_preset(medium)?;
+        match world.get_resource_mut::<MediumInput>() {
+            Some(mut input) => input.0 = parsed,
+            None => ignored.push("medium"),
+        }
+        if let Some(entities) = world.get_resource::<MediumUiInputEntities>() {
+            let entity = entities.medium;
+            set_input_value(world, entity, medium.clone());
+        }
+    }
+
+    if let Some(attenuation) = &preset.attenuation {
+        match world.get_resource_mut::<AttenuationInput>() {
+            Some(mut input) => apply_attenuation_preset(&mut input, attenuation, &mut ignored),
+            None => ignored.push("attenuation"),
+        }
+        if let Some(entities) = world.get_resource::<AttenuationUiInputEntities>() {
+            let values: Vec<(Entity, f32)> = [
+                (entities.alpha, attenuation.alpha),
+                (Some(entities.gamma), attenuation.gamma),
+                (entities.conductivity, attenuation.conductivity),
+                (
+                    entities.relative_permittivity,
+                    attenuation.relative_permittivity,
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(entity, value)| entity.zip(value))
+            .collect();
+            set_input_values(world, &values);
+        }
+    }
+
+    if let Some(interface) = &preset.interface {
+        match world.get_resource_mut::<InterfaceInput>() {
+            Some(mut input) => {
+                input.enabled = interface.enabled;
+                input.interface = Interface {
+                    n1: interface.n1,
+                    n2: interface.n2,
+                };
+                input.incidence = Angle::new::<degree>(interface.incidence);
+            }
+            None => ignored.push("interface"),
+        }
+        if let Some(entities) = world.get_resource::<InterfaceUiInputEntities>() {
+            let values = [
+                (entities.n1, interface.n1),
+                (entities.n2, interface.n2),
+                (entities.incidence, interface.incidence),
+            ];
+            set_input_values(world, &values);
+        }
+    }
+
+    if let Some(direction) = &preset.direction {
+        match world.get_resource_mut::<PropagationInput>() {
+            Some(mut input) => {
+                input.0 = SphericalDirection {
+                    theta: Angle::new::<degree>(direction.theta),
+                    phi: Angle::new::<degree>(direction.phi),
+                }
+            }
+            None => ignored.push("direction"),
+        }
+        if let Some(entities) = world.get_resource::<PropagationUiInputEntities>() {
+            let values = [
+                (entities.theta, direction.theta),
+                (entities.phi, direction.phi),
+            ];
+            set_input_values(world, &values);
+        }
+    }
+
+    if let Some(fdtd) = &preset.fdtd {
+        let dielectric = parse_dielectric_preset(&fdtd.dielectric)?;
+        match world.get_resource_mut::<FdtdInput>() {
+            Some(mut input) => {
+                *input = FdtdInput {
+                    enabled: fdtd.enabled,
+                    two_dimensional: fdtd.two_dimensional,
+                    dielectric,
+                }
+            }
+            None => ignored.push("fdtd"),
+        }
+        if let Some(entities) = world.get_resource::<FdtdUiInputEntities>() {
+            let entity = entities.dielectric;
+            set_input_value(world, entity, fdtd.dielectric.clone());
+        }
+    }
+
+    if let Some(display) = &preset.display {
+        if let Some(magnetic_field_scale) = display.magnetic_field_scale {
+            match world.get_resource_mut::<MagneticFieldUiInputs>() {
+                Some(mut inputs) => inputs.scale = magnetic_field_scale.to_string(),
+                None => ignored.push("display.magnetic_field_scale"),
+            }
+            if let Some(entities) = world.get_resource::<MagneticFieldUiInputEntities>() {
+                let values = [(entities.scale, magnetic_field_scale)];
+                set_input_values(world, &values);
+            }
+        }
+        match world.get_resource_mut::<DisplayScale>() {
+            Some(mut display_scale) => apply_display_preset(&mut display_scale, display),
+            None => ignored.push("display"),
+        }
+    }
+
+    if let Some(clock) = &preset.clock {
+        match world.get_resource_mut::<SimulationClock>() {
+            Some(mut simulation_clock) => {
+                simulation_clock.elapsed = clock.t;
+                simulation_clock.rate = clock.rate;
+                simulation_clock.paused = clock.paused;
+            }
+            None => ignored.push("clock"),
+        }
+    }
+
+    if let Some(camera) = preset.camera {
+        if !apply_camera_preset(world, &camera) {
+            // not spawned yet, i.e. preset passed on the command line
+            world.insert_resource(PendingCameraPreset(camera));
+        }
+    }
+
+    Ok(ignored)
+}
+
+/// the current state of the running scene as preset
+pub fn current_preset(world: &mut World) -> Result<ScenePreset, String> {
+    let scene = world.resource::<CurrentScene>().0;
+
+    let wave = world
+        .get_resource::<UiInputs>()
+        .map(WavePreset::try_from)
+        .transpose()?;
+
+    let superposition = world
+        .get_resource::<ComponentWaves>()
+        .map(|waves| waves.0.iter().map(|&w| w.into()).collect())
+        .unwrap_or_default();
+
+    let standing_wave =
+        world
+            .get_resource::<StandingWave>()
+            .map(|standing_wave| StandingWavePreset {
+                amplitude: standing_wave.0.amplitude.get::<meter>(),
+                length: standing_wave.0.length.get::<meter>(),
+                wave_speed: standing_wave.0.wave_speed.get::<meter_per_second>(),
+                harmonic: standing_wave.0.harmonic,
+                boundary_conditions: standing_wave.0.boundary_conditions.into(),
+            });
+
+    let vibrating_string =
+        world
+            .get_resource::<VibratingString>()
+            .map(|string| VibratingStringPreset {
+                amplitude: string.initial.amplitude.get::<meter>(),
+                length: string.parameters.length.get::<meter>(),
+                tension: string.parameters.tension.get::<newton>(),
+                linear_density: string.parameters.linear_density.get::<kilogram_per_meter>(),
+                shape: string.initial.shape.to_string(),
+                velocity: string.initial.velocity.to_string(),
+                left_end: string.left_end.into(),
+                right_end: string.right_end.into(),
+                drawn: match &string.initial.shape {
+                    InitialShape::Drawn(points) => points.iter().map(|&(x, u)| [x, u]).collect(),
+                    _ => vec![],
+                },
+            });
+
+    let interference =
+        world
+            .get_resource::<Interference>()
+            .map(|interference| InterferencePreset {
+                sources: interference.count,
+                separation: interference.separation.get::<meter>(),
+                slit_width: interference.slit_width.get::<meter>(),
+                screen_distance: interference.screen_distance.get::<meter>(),
+                view: interference.view.into(),
+            });
+
+    let fourier = world
+        .get_resource::<Fourier>()
+        .map(|fourier| FourierPreset {
+            waveform: fourier.waveform.to_string(),
+            harmonics: fourier.harmonics,
+            amplitude: fourier.amplitude.get::<meter>(),
+            wave_length: fourier.wave_length.get::<meter>(),
+            frequency: fourier.frequency.get::<hertz>(),
+        });
+
+    let membrane = world
+        .get_resource::<Membrane>()
+        .map(|membrane| MembranePreset {
+            shape: membrane.parameters.shape.to_string(),
+            wave_speed: membrane.parameters.wave_speed.get::<meter_per_second>(),
+            edge: membrane.parameters.edge.into(),
+            sources: format_sources(&membrane.sources),
+            drop_amplitude: membrane.drop_amplitude.get::<meter>(),
+        });
+
+    let polarity = match (
+        world.get_resource::<PolarityInput>(),
+        world.get_resource::<HandednessInput>(),
+        world.get_resource::<EllipticalPolarityInput>(),
+    ) {
+        (Some(polarity), Some(handedness), Some(elliptical)) => Some(PolarityPreset {
+            kind: (*polarity).into(),
+            handedness: handedness.0.into(),
+            jones: Some(JonesPreset {
+                y: elliptical.amplitude_y,
+                z: elliptical.amplitude_z,
+                phase_difference: elliptical.phase_difference.get::<radian>(),
+            }),
+        }),
+        _ => None,
+    };
+
+    let packet = world
+        .get_resource::<WavePacketInput>()
+        .map(|input| PacketPreset {
+            enabled: input.enabled,
+            envelope_width: input.envelope_width,
+            group_velocity_ratio: input.group_velocity_ratio,
+        });
+
+    let medium = world.get_resource::<MediumInput>().map(|input| {
+        input
+            .0
+            .map(|medium| medium.to_string())
+            .unwrap_or_else(|| "none".to_owned())
+    });
+
+    let attenuation = world
+        .get_resource::<AttenuationInput>()
+        .map(|input| AttenuationPreset {
+            alpha: input
+                .lossy_medium
+                .is_none()
+                .then(|| input.attenuation.alpha.get::<reciprocal_meter>()),
+            gamma: Some(input.attenuation.gamma.get::<hertz>()),
+            conductivity: input
+                .lossy_medium
+                .map(|medium| medium.conductivity.get::<siemens_per_meter>()),
+            relative_permittivity: input
+                .lossy_medium
+                .map(|medium| medium.relative_permittivity),
+        });
+
+    let interface = world
+        .get_resource::<InterfaceInput>()
+        .map(|input| InterfacePreset {
+            enabled: input.enabled,
+            n1: input.interface.n1,
+            n2: input.interface.n2,
+            incidence: input.incidence.get::<degree>(),
+        });
+
+    let direction = world
+        .get_resource::<PropagationInput>()
+        .map(|input| DirectionPreset {
+            theta: input.0.theta.get::<degree>(),
+            phi: input.0.phi.get::<degree>(),
+        });
+
+    let fdtd = world.get_resource::<FdtdInput>().map(|input| FdtdPreset {
+        enabled: input.enabled,
+        two_dimensional: input.two_dimensional,
+        dielectric: input
+            .dielectric
+            .map(|region| region.to_string())
+            .unwrap_or_else(default_dielectric),
+    });
+
+    let display = world
+        .get_resource::<DisplayScale>()
+        .map(|display_scale| DisplayPreset {
+            magnetic_field_scale: world
+                .get_resource::<MagneticFieldDisplayScale>()
+                .map(|scale| scale.0),
+            length: Some(display_scale.length),
+            amplitude: Some(display_scale.amplitude),
+            time: Some(display_scale.time),
+            auto: Some(display_scale.auto),
+        });
+
+    let clock = world
+        .get_resource::<SimulationClock>()
+        .map(|clock| ClockPreset {
+            t: clock.elapsed,
+            rate: clock.rate,
+            paused: clock.paused,
+        });
+
+    let camera = world
+        .query_filtered::<&Transform, With<Camera>>()
+        .get_single(world)
+        .ok()
+        .map(|transform| CameraPreset {
+            translation: transform.translation.to_array(),
+            rotation: transform.rotation.to_array(),
+        });
+
+    Ok(ScenePreset {
+        scene,
+        wave,
+        superposition,
+        standing_wave,
+        vibrating_string,
+        interference,
+        fourier,
+        membrane,
+        polarity,
+        packet,
+        medium,
+        attenuation,
+        interface,
+        direction,
+        fdtd,
+        camera,
+        display,
+        clock,
+    })
+}
+
+fn apply_attenuation_preset(
+    input: &mut AttenuationInput,
+    attenuation: &AttenuationPreset,
+    ignored: &mut Vec<&'static str>,
+) {
+    if let Some(gamma) = attenuation.gamma {
+        input.attenuation.gamma = Frequency::new::<hertz>(gamma);
+    }
+    // the em wave derives α from σ and εᵣ, mechanical waves have no conductivity
+    match &mut input.lossy_medium {
+        Some(medium) => {
+            if let Some(conductivity) = attenuation.conductivity {
+                medium.conductivity =
+                    ElectricalConductivity::new::<siemens_per_meter>(conductivity);
+            }
+            if let Some(relative_permittivity) = attenuation.relative_permittivity {
+                medium.relative_permittivity = relative_permittivity;
+            }
+            if attenuation.alpha.is_some() {
+                ignored.push("attenuation.alpha");
+            }
+        }
+        None => {
+            if let Some(alpha) = attenuation.alpha {
+                input.attenuation.alpha = ReciprocalLength::new::<reciprocal_meter>(alpha);
+            }
+            if attenuation.conductivity.is_some() || attenuation.relative_permittivity.is_some() {
+                ignored.push("attenuation.conductivity");
+            }
+        }
+    }
+}
+
+fn apply_display_preset(display_scale: &mut DisplayScale, display: &DisplayPreset) {
+    let manual = display.length.is_some() || display.amplitude.is_some() || display.time.is_some();
+    if let Some(length) = display.length {
+        display_scale.length = length;
+    }
+    if let Some(amplitude) = display.amplitude {
+        display_scale.amplitude = amplitude;
+    }
+    if let Some(time) = display.time {
+        display_scale.time = time;
+    }
+    display_scale.auto = display.auto.unwrap_or(display_scale.auto && !manual);
+}
+
+fn set_input_values(world: &mut World, values: &[(Entity, f32)]) {
+    for (entity, value) in values {
+        set_input_value(world, *entity, value.to_string());
+    }
+}
+
+/// updates the text shown in an input box
+fn set_input_value(world: &mut World, entity: Entity, value: String) {
+    if let Some(mut input_value) = world.get_mut::<TextInputValue>(entity) {
+        input_value.0 = value;
+    }
+}
+
+/// returns false if there's no camera yet
+fn apply_camera_preset(world: &mut World, camera: &CameraPreset) -> bool {
+    let mut query = world.query_filtered::<(
+        &mut Transform,
+        Option<&mut CameraController>,
+        Option<&mut Rotator>,
+    ), With<Camera>>();
+    match query.get_single_mut(world) {
+        Ok((mut transform, controller, rotator)) => {
+            transform.translation = Vec3::from_array(camera.translation);
+            transform.rotation = Quat::from_array(camera.rotation).normalize();
+            // controllers derive their yaw and pitch from the transform again
+            if let Some(mut controller) = controller {
+                controller.initialized = false;
+            }
+            if let Some(mut rotator) = rotator {
+                rotator.initialized = false;
+            }
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn apply_pending_camera_preset(world: &mut World) {
+    if let Some(PendingCameraPreset(camera)) = world.remove_resource::<PendingCameraPreset>() {
+        if !apply_camera_preset(world, &camera) {
+            warn!("No camera to apply the preset to");
+        }
+    }
+}
+
+impl TryFrom<&UiInputs> for WavePreset {
+    type Error = String;
+
+    fn try_from(inputs: &UiInputs) -> Result<Self, Self::Error> {
+        Ok(WavePreset {
+            amplitude: parse_float(&inputs.amplitude)?,
+            wave_length: parse_float(&inputs.wave_length)?,
+            frequency: parse_float(&inputs.frequency)?,
+            phase: parse_float(&inputs.phase)?,
+        })
+    }
+}
+
+impl From<WaveUserParameters> for WavePreset {
+    fn from(p: WaveUserParameters) -> Self {
+        WavePreset {
+            amplitude: p.amplitude.0.get::<meter>(),
+            wave_length: p.wave_length.0.get::<meter>(),
+            frequency: p.frequency.0.get::<hertz>(),
+            phase: p.phase.0.get::<radian>(),
+        }
+    }
+}
+
+impl From<WavePreset> for WaveUserParameters {
+    fn from(p: WavePreset) -> Self {
+        WaveUserParameters {
+            amplitude: Amplitude(Length::new::<meter>(p.amplitude)),
+            wave_length: WaveLength(Length::new::<meter>(p.wave_length)),
+            frequency: Freq(Frequency::new::<hertz>(p.frequency)),
+            phase: Phase(Angle::new::<radian>(p.phase)),
+        }
+    }
+}
+
+impl From<BoundaryConditionsPreset> for BoundaryConditions {
+    fn from(b: BoundaryConditionsPreset) -> Self {
+        match b {
+            BoundaryConditionsPreset::FixedFixed => BoundaryConditions::FixedFixed,
+            BoundaryConditionsPreset::FixedFree => BoundaryConditions::FixedFree,
+        }
+    }
+}
+
+impl From<BoundaryConditions> for BoundaryConditionsPreset {
+    fn from(b: BoundaryConditions) -> Self {
+        match b {
+            BoundaryConditions::FixedFixed => BoundaryConditionsPreset::FixedFixed,
+            BoundaryConditions::FixedFree => BoundaryConditionsPreset::FixedFree,
+        }
+    }
+}
+
+impl From<StringEndPreset> for StringEnd {
+    fn from(end: StringEndPreset) -> Self {
+        match end {
+            StringEndPreset::Fixed => StringEnd::Fixed,
+            StringEndPreset::Free => StringEnd::Free,
+            StringEndPreset::Absorbing => StringEnd::Absorbing,
+        }
+    }
+}
+
+impl From<StringEnd> for StringEndPreset {
+    fn from(end: StringEnd) -> Self {
+        match end {
+            StringEnd::Fixed => StringEndPreset::Fixed,
+            StringEnd::Free => StringEndPreset::Free,
+            StringEnd::Absorbing => StringEndPreset::Absorbing,
+        }
+    }
+}
+
+impl From<InterferenceViewPreset> for InterferenceView {
+    fn from(view: InterferenceViewPreset) -> Self {
+        match view {
+            InterferenceViewPreset::Field => InterferenceView::Field,
+            InterferenceViewPreset::Intensity => InterferenceView::Intensity,
+        }
+    }
+}
+
+impl From<InterferenceView> for InterferenceViewPreset {
+    fn from(view: InterferenceView) -> Self {
+        match view {
+            InterferenceView::Field => InterferenceViewPreset::Field,
+            InterferenceView::Intensity => InterferenceViewPreset::Intensity,
+        }
+    }
+}
+
+impl From<MembraneEdgePreset> for MembraneEdge {
+    fn from(edge: MembraneEdgePreset) -> Self {
+        match edge {
+            MembraneEdgePreset::Fixed => MembraneEdge::Fixed,
+            MembraneEdgePreset::Absorbing => MembraneEdge::Absorbing,
+        }
+    }
+}
+
+impl From<MembraneEdge> for MembraneEdgePreset {
+    fn from(edge: MembraneEdge) -> Self {
+        match edge {
+            MembraneEdge::Fixed => MembraneEdgePreset::Fixed,
+            MembraneEdge::Absorbing => MembraneEdgePreset::Absorbing,
+        }
+    }
+}
+
+impl From<HandednessPreset> for Handedness {
+    fn from(h: HandednessPreset) -> Self {
+        match h {
+            HandednessPreset::Right => Handedness::Right,
+            HandednessPreset::Left => Handedness::Left,
+        }
+    }
+}
+
+impl From<Handedness> for HandednessPreset {
+    fn from(h: Handedness) -> Self {
+        match h {
+            Handedness::Right => HandednessPreset::Right,
+            Handedness::Left => HandednessPreset::Left,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+
+use crate::{
+    preset::{apply_preset, current_preset, load_preset, save_preset, CurrentScene},
+    wave_ui::{add_button, generate_input_box, generate_label},
+};
+
+#[derive(Resource)]
+pub struct PresetUiInputs {
+    pub path: String,
+}
+
+#[derive(Resource)]
+pub struct PresetUiInputEntities {
+    pub path: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct PresetPathInputMarker;
+#[derive(Component, Default)]
+pub struct LoadPresetMarker;
+#[derive(Component, Default)]
+pub struct SavePresetMarker;
+#[derive(Component, Default)]
+pub struct PresetStatusMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum PresetUiEvent {
+    Load,
+    Save,
+}
+
+/// adds ui to load and save presets, bottom left
+pub fn setup_preset_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<PresetUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            bottom: Val::Px(0.0),
+            left: Val::Px(0.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let path_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Preset (.ron/.toml)",
+        PresetPathInputMarker,
+        form_state.path.clone(),
+    );
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Load preset",
+        LoadPresetMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Save preset",
+        SavePresetMarker,
+    );
+
+    let status_label = generate_label(&font, "");
+    let spawned_status_label = commands.spawn((PresetStatusMarker, status_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_status_label]);
+
+    commands.insert_resource(PresetUiInputEntities { path: path_input });
+}
+
+pub fn preset_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<PresetUiInputs>,
+    input_entities: Res<PresetUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.path {
+            inputs.path = event.value.clone();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn load_preset_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<LoadPresetMarker>),
+    >,
+    mut my_events: EventWriter<PresetUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        preset_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            PresetUiEvent::Load,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn save_preset_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<SavePresetMarker>),
+    >,
+    mut my_events: EventWriter<PresetUiEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        preset_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            PresetUiEvent::Save,
+        );
+    }
+}
+
+fn preset_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<PresetUiEvent>,
+    event: PresetUiEvent,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(event);
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+/// loads or saves the preset at the entered path, showing the outcome in the status label
+/// exclusive, since a preset touches the resources of every scene
+pub fn listen_preset_ui_inputs(world: &mut World) {
+    let events: Vec<PresetUiEvent> = world
+        .resource_mut::<Events<PresetUiEvent>>()
+        .drain()
+        .collect();
+    if events.is_empty() {
+        return;
+    }
+    let path = PathBuf::from(world.resource::<PresetUiInputs>().path.clone());
+
+    for event in events {
+        let status = match event {
+            PresetUiEvent::Load => load_and_apply_preset(world, &path),
+            PresetUiEvent::Save => current_preset(world)
+                .and_then(|preset| save_preset(&preset, &path))
+                .map(|_| format!("Saved {}", path.display())),
+        }
+        .unwrap_or_else(|e| e);
+
+        let mut label_query = world.query_filtered::<&mut Text, With<PresetStatusMarker>>();
+        for mut label in label_query.iter_mut(world) {
+            label.sections[0].value = status.clone();
+        }
+    }
+}
+
+fn load_and_apply_preset(world: &mut World, path: &Path) -> Result<String, String> {
+    let preset = load_preset(path)?;
+    let scene = world.resource::<CurrentScene>().0;
+    if preset.scene != scene {
+        return Err(format!(
+            "Preset is for the {:?} scene, start with: run --preset {}",
+            preset.scene,
+            path.display()
+        ));
+    }
+    let ignored = apply_preset(world, &preset)
+        .map_err(|e| format!("Invalid preset {}: {}", path.display(), e))?;
+    if ignored.is_empty() {
+        Ok(format!("Loaded {}", path.display()))
+    } else {
+        Ok(format!(
+            "Loaded {}, ignored: {}",
+            path.display(),
+            ignored.join(", ")
+        ))
+    }
+}
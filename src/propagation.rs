@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+use waves_core::wave::SphericalDirection;
+
+use crate::{
+    propagation_ui::{propagation_text_listener, setup_propagation_ui, update_propagation_label},
+    wave_ui::add_text_input_plugin,
+};
+
+/// direction the em wave travels in, +x by default
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PropagationInput(pub SphericalDirection);
+
+/// adds the direction panel, the em scene draws its wave along PropagationInput
+pub fn add_propagation_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.init_resource::<PropagationInput>()
+        .add_systems(Startup, setup_propagation_ui)
+        .add_systems(
+            Update,
+            (propagation_text_listener, update_propagation_label),
+        );
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{angle::degree, f32::Angle};
+
+use crate::{
+    propagation::PropagationInput,
+    wave_ui::{generate_input_box, generate_label, parse_float},
+};
+
+#[derive(Resource)]
+pub struct PropagationUiInputEntities {
+    pub theta: Entity,
+    pub phi: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct ThetaInputMarker;
+#[derive(Component, Default)]
+pub struct PhiInputMarker;
+#[derive(Component, Default)]
+pub struct PropagationInfoMarker;
+
+/// adds the direction inputs, top, next to the key bindings
+pub fn setup_propagation_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input: Res<PropagationInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            left: Val::Px(280.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let theta_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Direction θ (° from z)",
+        ThetaInputMarker,
+        input.0.theta.get::<degree>().to_string(),
+    );
+    let phi_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Direction φ (° from x)",
+        PhiInputMarker,
+        input.0.phi.get::<degree>().to_string(),
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((PropagationInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(PropagationUiInputEntities {
+        theta: theta_input,
+        phi: phi_input,
+    });
+}
+
+pub fn propagation_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<PropagationUiInputEntities>,
+    mut input: ResMut<PropagationInput>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.theta {
+            match parse_float(&event.value) {
+                Ok(degrees) if (0.0..=180.0).contains(&degrees) => {
+                    input.0.theta = Angle::new::<degree>(degrees)
+                }
+                Ok(degrees) => println!("error: θ must be in [0°, 180°]: {}", degrees),
+                Err(err) => println!("error: {}", err),
+            }
+        } else if event.entity == input_entities.phi {
+            match parse_float(&event.value) {
+                Ok(degrees) => input.0.phi = Angle::new::<degree>(degrees),
+                Err(err) => println!("error: {}", err),
+            }
+        }
+    }
+}
+
+/// k̂ and the transverse axes the jones vector refers to
+pub fn update_propagation_label(
+    input: Res<PropagationInput>,
+    mut label_query: Query<&mut Text, With<PropagationInfoMarker>>,
+) {
+    let (e1, e2) = input.0.transverse_unit_vectors();
+    let format_vec = |v: Vec3| format!("({:.2}, {:.2}, {:.2})", v.x, v.y, v.z);
+    let text = format!(
+        "k̂: {}\nJones y: {}\nJones z: {}",
+        format_vec(input.0.unit_vector()),
+        format_vec(e1),
+        format_vec(e2)
+    );
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use bevy::math::Vec3;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use uom::si::{
+    angle::radian,
+    electric_field::volt_per_meter,
+    f32::{Angle, ElectricField, Frequency, Length, Time},
+    frequency::hertz,
+    length::meter,
+    time::second,
+};
+use waves_core::{
+    electromagnetic_wave::{
+        calculate_u_circular, calculate_u_planar, ElectromagneticWaveParameters, Handedness,
+    },
+    fdtd::{DielectricRegion, Fdtd},
+    wave::{calculate_u, PropagationDirection, WaveParameters},
+};
+
+use crate::fdtd::{fdtd_setup, FdtdInput};
+
+/// cell updates the fdtd wave may take to reach the last sampled time, a few seconds of work
+const MAX_FDTD_CELL_UPDATES: usize = 2_000_000_000;
+
+/// rows written at most, a few gigabytes of csv
+const MAX_SAMPLES: usize = 50_000_000;
+
+/// evaluates a wave over an x and t grid and writes the samples, without opening a window
+/// uses physical time, i.e. not slowed down like the animation
+#[derive(Args, Debug)]
+pub struct SampleArgs {
+    #[arg(long, value_enum, default_value_t = SampleWave::Mechanical)]
+    pub wave: SampleWave,
+    /// amplitude, m for the mechanical wave, V/m for electromagnetic waves
+    #[arg(long, default_value_t = 1.0)]
+    pub amplitude: f32,
+    /// wave length (m)
+    #[arg(long, default_value_t = 2.0)]
+    pub wave_length: f32,
+    /// frequency (hz)
+    #[arg(long, default_value_t = 0.5)]
+    pub frequency: f32,
+    /// phase (rad)
+    #[arg(long, default_value_t = 0.0)]
+    pub phase: f32,
+    /// only used for the circular wave
+    #[arg(long, value_enum, default_value_t = SampleHandedness::Right)]
+    pub handedness: SampleHandedness,
+    /// only used for the fdtd wave, "x_min x_max εᵣ", e.g. "2 4 2.25"
+    #[arg(long, allow_hyphen_values = true)]
+    pub dielectric: Option<DielectricRegion>,
+    /// first x (m)
+    #[arg(long, default_value_t = -10.0, allow_negative_numbers = true)]
+    pub x_start: f32,
+    /// last x (m), inclusive
+    #[arg(long, default_value_t = 10.0, allow_negative_numbers = true)]
+    pub x_end: f32,
+    /// distance between x samples (m)
+    #[arg(long, default_value_t = 0.1)]
+    pub x_step: f32,
+    /// first t (s)
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub t_start: f32,
+    /// last t (s), inclusive
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub t_end: f32,
+    /// time between samples (s)
+    #[arg(long, default_value_t = 0.1)]
+    pub t_step: f32,
+    #[arg(long, value_enum, default_value_t = SampleFormat::Csv)]
+    pub format: SampleFormat,
+    /// file to write to, stdout if not set
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum SampleWave {
+    /// displacement along y, like the 2d scene
+    Mechanical,
+    /// electric field along z
+    Planar,
+    /// electric field rotating in the yz plane
+    Circular,
+    /// electric field along z simulated with the 1d FDTD solver, the plane wave enters a bit right
+    /// of x_start at t = 0, the wave length follows from the frequency
+    Fdtd,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum SampleHandedness {
+    Right,
+    Left,
+}
+
+impl From<SampleHandedness> for Handedness {
+    fn from(h: SampleHandedness) -> Self {
+        match h {
+            SampleHandedness::Right => Handedness::Right,
+            SampleHandedness::Left => Handedness::Left,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum SampleFormat {
+    Csv,
+    Json,
+}
+
+/// u(x, t), in m for the mechanical wave and V/m for electromagnetic waves
+#[derive(Serialize, Debug)]
+pub struct Sample {
+    pub t: f32,
+    pub x: f32,
+    pub u_x: f32,
+    pub u_y: f32,
+    pub u_z: f32,
+}
+
+pub fn run_sample(args: &SampleArgs) -> Result<(), String> {
+    let xs = steps(args.x_start, args.x_end, args.x_step)?;
+    let ts = steps(args.t_start, args.t_end, args.t_step)?;
+    let count = xs.len().saturating_mul(ts.len());
+    if count > MAX_SAMPLES {
+        return Err(format!(
+            "{} x times {} t values are {} samples, at most {} are allowed",
+            xs.len(),
+            ts.len(),
+            count,
+            MAX_SAMPLES
+        ));
+    }
+    // set up before creating the output, so invalid arguments don't leave an empty file
+    let mut sampler = Sampler::new(args, &ts)?;
+
+    let writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(
+            File::create(path)
+                .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?,
+        ),
+        None => Box::new(io::stdout()),
+    };
+    let mut writer = SampleWriter {
+        writer: BufWriter::new(writer),
+        format: args.format,
+        written: 0,
+    };
+
+    writer.start().map_err(write_error)?;
+    sampler.run(&xs, &ts, |sample| {
+        writer.write(&sample).map_err(write_error)
+    })?;
+    writer.finish().map_err(write_error)
+}
+
+fn write_error(e: io::Error) -> String {
+    format!("Failed to write samples: {}", e)
+}
+
+/// writes the samples as they are produced, so the output doesn't have to fit into memory
+struct SampleWriter<W: Write> {
+    writer: W,
+    format: SampleFormat,
+    written: usize,
+}
+
+impl<W: Write> SampleWriter<W> {
+    fn start(&mut self) -> io::Result<()> {
+        match self.format {
+            SampleFormat::Csv => writeln!(self.writer, "t,x,u_x,u_y,u_z"),
+            SampleFormat::Json => write!(self.writer, "["),
+        }
+    }
+
+    fn write(&mut self, s: &Sample) -> io::Result<()> {
+        match self.format {
+            SampleFormat::Csv => {
+                writeln!(self.writer, "{},{},{},{},{}", s.t, s.x, s.u_x, s.u_y, s.u_z)?
+            }
+            SampleFormat::Json => {
+                let separator = if self.written == 0 { "" } else { "," };
+                write!(self.writer, "{}\n  ", separator)?;
+                serde_json::to_writer(&mut self.writer, s)?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if let SampleFormat::Json = self.format {
+            writeln!(self.writer, "\n]")?;
+        }
+        self.writer.flush()
+    }
+}
+
+/// the wave to sample, set up and checked before anything is written
+enum Sampler {
+    /// u(x, t)
+    Function(Box<dyn Fn(Length, Time) -> Vec3>),
+    /// the solver runs once through the sampled times
+    Fdtd { fdtd: Box<Fdtd>, max_steps: usize },
+}
+
+impl Sampler {
+    fn new(args: &SampleArgs, ts: &[f32]) -> Result<Sampler, String> {
+        let wave_length = Length::new::<meter>(args.wave_length);
+        let frequency = Frequency::new::<hertz>(args.frequency);
+        let phase = Angle::new::<radian>(args.phase);
+
+        let function: Box<dyn Fn(Length, Time) -> Vec3> = match args.wave {
+            SampleWave::Mechanical => {
+                let pars = WaveParameters {
+                    amplitude: Length::new::<meter>(args.amplitude),
+                    wave_length,
+                    frequency,
+                    phase,
+                    direction: PropagationDirection::Forward,
+                };
+                Box::new(move |x, t| calculate_u(x, t, &pars, Vec3::Y).to_vec3())
+            }
+            SampleWave::Planar => {
+                let pars = ElectromagneticWaveParameters {
+                    amplitude: ElectricField::new::<volt_per_meter>(args.amplitude),
+                    wave_length,
+                    frequency,
+                    phase,
+                };
+                Box::new(move |x, t| calculate_u_planar(x, t, &pars, Vec3::Z).to_vec3())
+            }
+            SampleWave::Circular => {
+                let pars = ElectromagneticWaveParameters {
+                    amplitude: ElectricField::new::<volt_per_meter>(args.amplitude),
+                    wave_length,
+                    frequency,
+                    phase,
+                };
+                let handedness = args.handedness.into();
+                Box::new(move |x, t| {
+                    calculate_u_circular(x, t, &pars, Vec3::Y, Vec3::Z, handedness).to_vec3()
+                })
+            }
+            SampleWave::Fdtd => return fdtd_sampler(args, ts, frequency, phase),
+        };
+        Ok(Sampler::Function(function))
+    }
+
+    /// samples row by row, all x at the first t, then all x at the next t, ...
+    fn run<F>(&mut self, xs: &[f32], ts: &[f32], mut emit: F) -> Result<(), String>
+    where
+        F: FnMut(Sample) -> Result<(), String>,
+    {
+        match self {
+            Sampler::Function(function) => {
+                for &t in ts {
+                    for &x in xs {
+                        let u = function(Length::new::<meter>(x), Time::new::<second>(t));
+                        emit(Sample {
+                            t,
+                            x,
+                            u_x: u.x,
+                            u_y: u.y,
+                            u_z: u.z,
+                        })?;
+                    }
+                }
+            }
+            // each sample is at the last step before its t
+            Sampler::Fdtd { fdtd, max_steps } => {
+                let mut steps = 0;
+                for &t in ts {
+                    let t_quantity = Time::new::<second>(t);
+                    steps += fdtd.advance_to(t_quantity, *max_steps - steps);
+                    if fdtd.time() + fdtd.grid.time_step() <= t_quantity {
+                        return Err(format!(
+                            "The fdtd wave stopped at t = {:e} s after {} steps, before t = {} s",
+                            fdtd.time().get::<second>(),
+                            steps,
+                            t
+                        ));
+                    }
+                    for &x in xs {
+                        let e_z = fdtd.e_z_at(Length::new::<meter>(x), Length::new::<meter>(0.0));
+                        emit(Sample {
+                            t,
+                            x,
+                            u_x: 0.0,
+                            u_y: 0.0,
+                            u_z: e_z.get::<volt_per_meter>(),
+                        })?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// sets up the solver and checks that it reaches the last sampled time within the step budget
+fn fdtd_sampler(
+    args: &SampleArgs,
+    ts: &[f32],
+    frequency: Frequency,
+    phase: Angle,
+) -> Result<Sampler, String> {
+    if args.t_start < 0.0 {
+        return Err(format!(
+            "The simulation starts at t = 0, got t_start {}",
+            args.t_start
+        ));
+    }
+    if frequency.get::<hertz>() <= 0.0 {
+        return Err(format!(
+            "Frequency must be positive, got {}",
+            args.frequency
+        ));
+    }
+    if args.x_end <= args.x_start {
+        return Err(format!(
+            "The fdtd wave needs x_end after x_start, got {} to {}",
+            args.x_start, args.x_end
+        ));
+    }
+    let input = FdtdInput {
+        enabled: true,
+        two_dimensional: false,
+        dielectric: args.dielectric,
+    };
+    let (grid, regions, sources) = fdtd_setup(
+        &input,
+        (args.x_start, args.x_end),
+        ElectricField::new::<volt_per_meter>(args.amplitude),
+        frequency,
+        phase,
+    )?;
+    // the time step is the cell size over c, light crosses the grid in a tiny fraction of a
+    // second, so only very short times can be simulated
+    let time_step = grid.time_step().get::<second>();
+    let max_steps = (MAX_FDTD_CELL_UPDATES / (grid.cells_x * grid.cells_y)).max(1);
+    let t_end = ts.last().copied().unwrap_or(0.0);
+    let needed_steps = (t_end / time_step).floor();
+    if needed_steps > max_steps as f32 {
+        return Err(format!(
+            "t_end {} s needs {:.0} fdtd steps of {:e} s, at most {} are allowed",
+            t_end, needed_steps, time_step, max_steps
+        ));
+    }
+    Ok(Sampler::Fdtd {
+        fdtd: Box::new(Fdtd::new(grid, regions, sources)),
+        max_steps,
+    })
+}
+
+/// start, start + step, ... up to and including end
+/// computed from the index to not accumulate rounding errors
+fn steps(start: f32, end: f32, step: f32) -> Result<Vec<f32>, String> {
+    if !(start.is_finite() && end.is_finite() && step.is_finite()) {
+        return Err(format!(
+            "Range values must be finite, got start {}, end {}, step {}",
+            start, end, step
+        ));
+    }
+    if end < start {
+        return Err(format!("Range end {} is before start {}", end, start));
+    }
+    if end == start {
+        return Ok(vec![start]);
+    }
+    if step <= 0.0 {
+        return Err(format!("Step must be positive, got {}", step));
+    }
+    // tolerance for an end that is a multiple of step, but not exactly due to float precision
+    let count = ((end - start) / step + 1e-4).floor() + 1.0;
+    if count > MAX_SAMPLES as f32 {
+        return Err(format!(
+            "Range {} to {} in steps of {} has {:.0} values, at most {} are allowed",
+            start, end, step, count, MAX_SAMPLES
+        ));
+    }
+    let count = count as usize;
+    Ok((0..count).map(|i| start + i as f32 * step).collect())
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use uom::si::f32::Time;
+
+/// points the renderers sample the visible range with, a power of two for the spectrum
+pub const SAMPLED_POINTS: usize = 512;
+
+/// physical component of the sampled vectors, y for the 2d scenes, any for the em wave
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldComponent {
+    X,
+    #[default]
+    Y,
+    Z,
+}
+
+impl FieldComponent {
+    pub fn next(self) -> FieldComponent {
+        match self {
+            FieldComponent::X => FieldComponent::Y,
+            FieldComponent::Y => FieldComponent::Z,
+            FieldComponent::Z => FieldComponent::X,
+        }
+    }
+
+    pub fn of(&self, vec: Vec3) -> f32 {
+        match self {
+            FieldComponent::X => vec.x,
+            FieldComponent::Y => vec.y,
+            FieldComponent::Z => vec.z,
+        }
+    }
+}
+
+impl fmt::Display for FieldComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldComponent::X => write!(f, "x"),
+            FieldComponent::Y => write!(f, "y"),
+            FieldComponent::Z => write!(f, "z"),
+        }
+    }
+}
+
+/// the wave as drawn by the scene in the last frame, written by its renderer
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SampledWave {
+    /// m, along the direction the wave is drawn in
+    pub x_start: f32,
+    pub x_end: f32,
+    pub t: Time,
+    /// SAMPLED_POINTS vectors from x_start up to x_end, m or V/m, empty if nothing is sampled
+    pub values: Vec<Vec3>,
+    /// screen directions of x and of the probe marker
+    pub x_axis: Vec3,
+    pub up_axis: Vec3,
+}
+
+impl SampledWave {
+    pub fn record<F>(&mut self, x_start: f32, x_end: f32, t: Time, function: F)
+    where
+        F: Fn(f32) -> Vec3,
+    {
+        let step = (x_end - x_start) / SAMPLED_POINTS as f32;
+        self.x_start = x_start;
+        self.x_end = x_end;
+        self.t = t;
+        self.values.clear();
+        self.values
+            .extend((0..SAMPLED_POINTS).map(|i| function(x_start + i as f32 * step)));
+    }
+
+    /// e.g. while the scene draws something the spectrum doesn't support
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    pub fn spacing(&self) -> f32 {
+        (self.x_end - self.x_start) / SAMPLED_POINTS as f32
+    }
+
+    /// linearly interpolated, None outside the sampled range
+    pub fn at(&self, x: f32) -> Option<Vec3> {
+        let position = (x - self.x_start) / self.spacing();
+        if self.values.is_empty() || !(0.0..=(self.values.len() - 1) as f32).contains(&position) {
+            return None;
+        }
+        let index = position.floor() as usize;
+        let next = (index + 1).min(self.values.len() - 1);
+        Some(self.values[index].lerp(self.values[next], position - index as f32))
+    }
+}
+
+/// for the panels that analyze the drawn wave, e.g. the spectrum and the oscilloscope
+pub fn add_sampled_wave(app: &mut App) {
+    app.init_resource::<SampledWave>();
+}
@@ -0,0 +1,222 @@
+use bevy::{
+    color::palettes::css::{GRAY, WHITE},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+use bevy_simple_text_input::{TextInputSubmitEvent, TextInputValue};
+
+use crate::wave_ui::{add_text_input_plugin, generate_input_box, parse_float};
+
+/// values a slider can take
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliderRange {
+    pub min: f32,
+    pub max: f32,
+    /// linear: values are multiples of it, log: relative, e.g. 0.01 keeps 3 significant digits
+    pub step: f32,
+    /// evenly spaced exponents instead of values, for ranges over orders of magnitude
+    /// min must be positive
+    pub log: bool,
+}
+
+impl SliderRange {
+    pub const fn linear(min: f32, max: f32, step: f32) -> SliderRange {
+        SliderRange {
+            min,
+            max,
+            step,
+            log: false,
+        }
+    }
+
+    pub const fn log(min: f32, max: f32, step: f32) -> SliderRange {
+        SliderRange {
+            min,
+            max,
+            step,
+            log: true,
+        }
+    }
+
+    /// position along the slider in [0, 1], values outside the range at the ends
+    pub fn fraction(&self, value: f32) -> f32 {
+        let fraction = if self.log {
+            if value <= 0.0 {
+                return 0.0;
+            }
+            (value / self.min).ln() / (self.max / self.min).ln()
+        } else {
+            (value - self.min) / (self.max - self.min)
+        };
+        if fraction.is_finite() {
+            fraction.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// value at a position along the slider, on a step
+    pub fn value(&self, fraction: f32) -> f32 {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let value = if self.log {
+            let value = self.min * (self.max / self.min).powf(fraction);
+            let factor = 10f32.powi(self.decimals(value));
+            (value * factor).round() / factor
+        } else {
+            let value = self.min + fraction * (self.max - self.min);
+            (value / self.step).round() * self.step
+        };
+        value.clamp(self.min, self.max)
+    }
+
+    /// with the decimals of the step, in scientific notation for very small or large values
+    pub fn format(&self, value: f32) -> String {
+        if self.log && value != 0.0 && !(1e-3..1e6).contains(&value.abs()) {
+            format!("{:.*e}", self.step_decimals().max(0) as usize, value)
+        } else {
+            format!("{:.*}", self.decimals(value).max(0) as usize, value)
+        }
+    }
+
+    /// decimals a value on a step has, negative for log values rounded to tens, hundreds, ...
+    fn decimals(&self, value: f32) -> i32 {
+        if self.log && value != 0.0 {
+            // one more significant digit than the relative step, 0.01 steps 1.00 to 1.01
+            self.step_decimals() - value.abs().log10().floor() as i32
+        } else {
+            self.step_decimals()
+        }
+    }
+
+    fn step_decimals(&self) -> i32 {
+        (-self.step.log10()).ceil() as i32
+    }
+}
+
+/// track under a numeric input, dragging along it scrubs the value of the input
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Slider {
+    /// the text input, as returned by generate_input_box
+    pub input: Entity,
+    pub range: SliderRange,
+}
+
+#[derive(Component, Default)]
+pub struct SliderHandleMarker;
+
+/// lets sliders drive the inputs they're attached to, in any scene
+pub fn add_slider_system(app: &mut App) {
+    add_text_input_plugin(app);
+    app.add_systems(Update, (slider_handler, update_slider_handles).chain());
+}
+
+/// an input box with a slider below it, returns the text input like generate_input_box
+pub fn generate_slider_input_box<T>(
+    font: &Handle<Font>,
+    root_id: Entity,
+    commands: &mut Commands,
+    label: &str,
+    marker: T,
+    value: String,
+    range: SliderRange,
+) -> Entity
+where
+    T: Component,
+{
+    let input = generate_input_box(font, root_id, commands, label, marker, value);
+    add_slider(commands, root_id, input, range);
+    input
+}
+
+/// adds a slider for an existing input, e.g. one with a different layout
+pub fn add_slider(commands: &mut Commands, root_id: Entity, input: Entity, range: SliderRange) {
+    let slider = commands
+        .spawn((
+            Slider { input, range },
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(12.0),
+                    margin: UiRect::vertical(Val::Px(3.0)),
+                    ..default()
+                },
+                background_color: GRAY.into(),
+                ..default()
+            },
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+        ))
+        .id();
+    let handle = commands
+        .spawn((
+            SliderHandleMarker,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(0.0),
+                    width: Val::Px(6.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: WHITE.into(),
+                ..default()
+            },
+        ))
+        .id();
+    commands.entity(slider).push_children(&[handle]);
+    commands.entity(root_id).push_children(&[slider]);
+}
+
+/// while a slider is pressed, i.e. also while dragging, writes the value under the cursor into
+/// its input and submits it, so the listeners of the input take it as if it was typed
+fn slider_handler(
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &Slider)>,
+    mut values: Query<&mut TextInputValue>,
+    mut submit_events: EventWriter<TextInputSubmitEvent>,
+) {
+    for (interaction, cursor, slider) in &slider_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+        let Ok(mut value) = values.get_mut(slider.input) else {
+            continue;
+        };
+        let text = slider.range.format(slider.range.value(position.x));
+        if value.0 != text {
+            value.0 = text.clone();
+            submit_events.send(TextInputSubmitEvent {
+                entity: slider.input,
+                value: text,
+            });
+        }
+    }
+}
+
+/// moves the handles to the values of the inputs, also typed ones, and leaves them where they
+/// are while the input doesn't hold a number
+fn update_slider_handles(
+    slider_query: Query<(&Slider, &Children)>,
+    values: Query<&TextInputValue>,
+    mut handle_query: Query<&mut Style, With<SliderHandleMarker>>,
+) {
+    for (slider, children) in &slider_query {
+        let Some(value) = values
+            .get(slider.input)
+            .ok()
+            .and_then(|value| parse_float(value.0.trim()).ok())
+        else {
+            continue;
+        };
+        let left = Val::Percent(slider.range.fraction(value) * 100.0);
+        for child in children {
+            if let Ok(mut style) = handle_query.get_mut(*child) {
+                if style.left != left {
+                    style.left = left;
+                }
+            }
+        }
+    }
+}
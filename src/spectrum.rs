@@ -0,0 +1,324 @@
+use std::{collections::VecDeque, f32::consts::PI, fmt};
+
+use bevy::{
+    color::palettes::css::{DARK_GRAY, WHITE, YELLOW},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use uom::si::{angle::radian, time::second};
+use waves_core::spectrum::{resample, spectrum, SpectrumLine, Window};
+
+use crate::{
+    display_scale::DisplayScale,
+    sampled_wave::{add_sampled_wave, FieldComponent, SampledWave},
+    spectrum_ui::{
+        listen_spectrum_events, setup_spectrum_ui, spectrum_component_button_handler,
+        spectrum_domain_button_handler, spectrum_text_listener, spectrum_window_button_handler,
+        toggle_spectrum_button_handler, update_spectrum_label, SpectrumEvent, SpectrumUiStatus,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// probe values kept, about a minute at 60 frames per second
+const PROBE_HISTORY: usize = 4096;
+/// a time spectrum needs at least this many probe values
+const MIN_PROBE_VALUES: usize = 16;
+/// pixels of the plot, magnitude on top of the phase
+pub const PLOT_WIDTH: u32 = 256;
+pub const PLOT_HEIGHT: u32 = 160;
+const MAGNITUDE_HEIGHT: u32 = 100;
+/// the plot shows at least this many lines, zoomed in to twice the highest significant one
+const MIN_SHOWN_LINES: usize = 16;
+/// relative to the largest line, smaller lines don't count for the zoom and have no phase dot
+const SIGNIFICANT_MAGNITUDE: f32 = 0.02;
+
+/// what the transform is taken of
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpectrumDomain {
+    /// the wave across the visible x range at the current time, spatial frequencies 1/λ
+    #[default]
+    Space,
+    /// the wave at the probe over the last time window, frequencies f
+    Time,
+}
+
+impl SpectrumDomain {
+    pub fn next(self) -> SpectrumDomain {
+        match self {
+            SpectrumDomain::Space => SpectrumDomain::Time,
+            SpectrumDomain::Time => SpectrumDomain::Space,
+        }
+    }
+
+    /// of the frequencies
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SpectrumDomain::Space => "1/m",
+            SpectrumDomain::Time => "hz",
+        }
+    }
+}
+
+impl fmt::Display for SpectrumDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpectrumDomain::Space => write!(f, "space"),
+            SpectrumDomain::Time => write!(f, "time at probe"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumInput {
+    pub enabled: bool,
+    pub domain: SpectrumDomain,
+    pub component: FieldComponent,
+    pub window: Window,
+    /// m, where the time domain samples the wave
+    pub probe_x: f32,
+    /// seconds of animation time the time domain looks back, so it doesn't depend on the scale
+    pub duration: f32,
+}
+
+impl Default for SpectrumInput {
+    fn default() -> Self {
+        SpectrumInput {
+            enabled: false,
+            domain: SpectrumDomain::default(),
+            component: FieldComponent::default(),
+            window: Window::default(),
+            probe_x: 0.0,
+            duration: 8.0,
+        }
+    }
+}
+
+/// (physical time in s, value) at the probe, once per frame
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ProbeHistory(pub VecDeque<(f32, f32)>);
+
+/// lines of the current transform
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Spectrum {
+    pub lines: Vec<SpectrumLine>,
+    /// probe values or sampled points that went into it
+    pub samples: usize,
+}
+
+/// the texture the spectrum is drawn into
+#[derive(Component)]
+pub struct SpectrumPlot(pub Handle<Image>);
+
+/// side panel with the magnitude and phase spectrum of the wave the scene draws
+/// the renderer of the scene writes SampledWave
+pub fn add_spectrum_system(app: &mut App) {
+    add_text_input_plugin(app);
+    add_sampled_wave(app);
+    app.add_event::<SpectrumEvent>()
+        .init_resource::<SpectrumInput>()
+        .init_resource::<ProbeHistory>()
+        .init_resource::<Spectrum>()
+        .init_resource::<SpectrumUiStatus>()
+        .add_systems(Startup, setup_spectrum_ui)
+        .add_systems(
+            Update,
+            (
+                (record_probe, update_spectrum, draw_spectrum_plot).chain(),
+                draw_probe_marker,
+                spectrum_text_listener,
+                toggle_spectrum_button_handler,
+                spectrum_domain_button_handler,
+                spectrum_component_button_handler,
+                spectrum_window_button_handler,
+                listen_spectrum_events,
+                update_spectrum_label,
+            ),
+        );
+}
+
+/// appends the wave at the probe, starting over when the clock goes back
+fn record_probe(
+    input: Res<SpectrumInput>,
+    sampled: Res<SampledWave>,
+    display_scale: Res<DisplayScale>,
+    mut history: ResMut<ProbeHistory>,
+) {
+    if !input.enabled || input.domain != SpectrumDomain::Time {
+        return;
+    }
+    let Some(value) = sampled.at(input.probe_x) else {
+        history.0.clear();
+        return;
+    };
+    let t = sampled.t.get::<second>();
+    match history.0.back() {
+        Some(&(last, _)) if t < last => history.0.clear(),
+        // paused
+        Some(&(last, _)) if t == last => return,
+        _ => {}
+    }
+    history.0.push_back((t, input.component.of(value)));
+    let window_start = t - display_scale.physical_time(input.duration).get::<second>();
+    while history.0.len() > PROBE_HISTORY
+        || history.0.front().is_some_and(|&(t, _)| t < window_start)
+    {
+        history.0.pop_front();
+    }
+}
+
+fn update_spectrum(
+    input: Res<SpectrumInput>,
+    sampled: Res<SampledWave>,
+    history: Res<ProbeHistory>,
+    mut spectrum_state: ResMut<Spectrum>,
+) {
+    if !input.enabled {
+        return;
+    }
+    let (lines, samples) = match input.domain {
+        SpectrumDomain::Space => {
+            let samples: Vec<f32> = sampled
+                .values
+                .iter()
+                .map(|value| input.component.of(*value))
+                .collect();
+            (
+                spectrum(&samples, sampled.spacing(), input.window),
+                samples.len(),
+            )
+        }
+        SpectrumDomain::Time if history.0.len() >= MIN_PROBE_VALUES => {
+            let points: Vec<(f32, f32)> = history.0.iter().copied().collect();
+            // frames aren't evenly spaced, resampled to about as many values as there are
+            let count = points.len().next_power_of_two();
+            let duration = points[points.len() - 1].0 - points[0].0;
+            (
+                spectrum(
+                    &resample(&points, count),
+                    duration / (count - 1) as f32,
+                    input.window,
+                ),
+                points.len(),
+            )
+        }
+        SpectrumDomain::Time => (vec![], history.0.len()),
+    };
+    spectrum_state.lines = lines;
+    spectrum_state.samples = samples;
+}
+
+/// lines up to where the spectrum gets insignificant, at least MIN_SHOWN_LINES
+pub fn shown_lines(lines: &[SpectrumLine]) -> usize {
+    let largest = lines.iter().map(|line| line.magnitude).fold(0.0, f32::max);
+    let highest = lines
+        .iter()
+        .rposition(|line| line.magnitude > largest * SIGNIFICANT_MAGNITUDE)
+        .unwrap_or(0);
+    (2 * (highest + 1))
+        .next_power_of_two()
+        .max(MIN_SHOWN_LINES)
+        .min(lines.len())
+}
+
+/// magnitudes as bars relative to the largest, phases in [−π, π] as dots below
+fn draw_spectrum_plot(
+    input: Res<SpectrumInput>,
+    spectrum_state: Res<Spectrum>,
+    mut images: ResMut<Assets<Image>>,
+    mut plots: Query<(&SpectrumPlot, &mut Style)>,
+) {
+    for (plot, mut style) in &mut plots {
+        let display = if input.enabled {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+        if !input.enabled {
+            continue;
+        }
+        let Some(image) = images.get_mut(&plot.0) else {
+            continue;
+        };
+        for pixel in image.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[0, 0, 0, 255]);
+        }
+        let mut set_pixel = |column: u32, row: u32, color: Srgba| {
+            if column < PLOT_WIDTH && row < PLOT_HEIGHT {
+                let index = 4 * (row * PLOT_WIDTH + column) as usize;
+                image.data[index..index + 4].copy_from_slice(&color.to_u8_array());
+            }
+        };
+        for column in 0..PLOT_WIDTH {
+            set_pixel(column, MAGNITUDE_HEIGHT, DARK_GRAY);
+        }
+
+        let lines = &spectrum_state.lines;
+        let largest = lines.iter().map(|line| line.magnitude).fold(0.0, f32::max);
+        if largest <= 0.0 {
+            continue;
+        }
+        let shown = shown_lines(lines);
+        let phase_height = PLOT_HEIGHT - MAGNITUDE_HEIGHT - 1;
+        for (k, line) in lines.iter().take(shown).enumerate() {
+            let relative = line.magnitude / largest;
+            let left = k as u32 * PLOT_WIDTH / shown as u32;
+            let right = ((k + 1) as u32 * PLOT_WIDTH / shown as u32).max(left + 1);
+            let height = (relative * (MAGNITUDE_HEIGHT - 2) as f32).round() as u32;
+            // a pixel gap between the bars if there's room
+            let bar_right = if right - left > 2 { right - 1 } else { right };
+            for column in left..bar_right {
+                for row in MAGNITUDE_HEIGHT - height..MAGNITUDE_HEIGHT {
+                    set_pixel(column, row, WHITE);
+                }
+            }
+            if relative > SIGNIFICANT_MAGNITUDE {
+                // π at the top, −π at the bottom
+                let phase = line.phase.get::<radian>();
+                let row = MAGNITUDE_HEIGHT
+                    + 1
+                    + ((PI - phase) / (2.0 * PI) * (phase_height - 1) as f32).round() as u32;
+                let center = (left + right) / 2;
+                for column in center.saturating_sub(1)..=center + 1 {
+                    for row in row.saturating_sub(1)..=row + 1 {
+                        set_pixel(column, row, YELLOW);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// where the time domain samples the wave
+fn draw_probe_marker(
+    mut gizmos: Gizmos,
+    input: Res<SpectrumInput>,
+    sampled: Res<SampledWave>,
+    display_scale: Res<DisplayScale>,
+) {
+    if !input.enabled || input.domain != SpectrumDomain::Time || sampled.values.is_empty() {
+        return;
+    }
+    let center = sampled.x_axis * (input.probe_x * display_scale.length);
+    let up = sampled.up_axis * display_scale.screen_amplitude * 1.5;
+    gizmos.line(center - up, center + up, YELLOW);
+}
+
+pub fn new_plot_image() -> Image {
+    Image::new_fill(
+        Extent3d {
+            width: PLOT_WIDTH,
+            height: PLOT_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
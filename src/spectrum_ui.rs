@@ -0,0 +1,344 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::angle::radian;
+use waves_core::spectrum::dominant;
+
+use crate::{
+    spectrum::{
+        new_plot_image, shown_lines, ProbeHistory, Spectrum, SpectrumDomain, SpectrumInput,
+        SpectrumPlot, PLOT_HEIGHT, PLOT_WIDTH,
+    },
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float},
+};
+
+#[derive(Resource)]
+pub struct SpectrumUiInputEntities {
+    pub probe_x: Entity,
+    pub duration: Entity,
+}
+
+/// error of the last submitted input, if any
+#[derive(Resource, Default)]
+pub struct SpectrumUiStatus(pub Option<String>);
+
+#[derive(Component, Default)]
+pub struct ToggleSpectrumMarker;
+#[derive(Component, Default)]
+pub struct SpectrumDomainMarker;
+#[derive(Component, Default)]
+pub struct SpectrumComponentMarker;
+#[derive(Component, Default)]
+pub struct SpectrumWindowMarker;
+#[derive(Component, Default)]
+pub struct ProbeXInputMarker;
+#[derive(Component, Default)]
+pub struct SpectrumDurationInputMarker;
+#[derive(Component, Default)]
+pub struct SpectrumInfoMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum SpectrumEvent {
+    Toggle,
+    Domain,
+    Component,
+    Window,
+}
+
+/// adds the spectrum panel, left, below the info labels of the 3d scenes
+pub fn setup_spectrum_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    input: Res<SpectrumInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(150.0),
+            left: Val::Px(0.0),
+            width: Val::Px(PLOT_WIDTH as f32),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Spectrum on/off",
+        ToggleSpectrumMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Space/Time",
+        SpectrumDomainMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Component",
+        SpectrumComponentMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Window",
+        SpectrumWindowMarker,
+    );
+
+    let probe_x_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Probe x (m)",
+        ProbeXInputMarker,
+        input.probe_x.to_string(),
+    );
+    let duration_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Time window (s shown)",
+        SpectrumDurationInputMarker,
+        input.duration.to_string(),
+    );
+
+    let image = images.add(new_plot_image());
+    let plot = commands
+        .spawn((
+            SpectrumPlot(image.clone()),
+            ImageBundle {
+                image: UiImage::new(image),
+                style: Style {
+                    width: Val::Px(PLOT_WIDTH as f32),
+                    height: Val::Px(PLOT_HEIGHT as f32),
+                    display: Display::None,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((SpectrumInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[plot, spawned_info_label]);
+
+    commands.insert_resource(SpectrumUiInputEntities {
+        probe_x: probe_x_input,
+        duration: duration_input,
+    });
+}
+
+pub fn spectrum_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<SpectrumUiInputEntities>,
+    mut input: ResMut<SpectrumInput>,
+    mut history: ResMut<ProbeHistory>,
+    mut status: ResMut<SpectrumUiStatus>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.probe_x {
+            match parse_float(&event.value) {
+                Ok(f) => {
+                    input.probe_x = f;
+                    history.0.clear();
+                    status.0 = None;
+                }
+                Err(err) => status.0 = Some(err),
+            }
+        } else if event.entity == input_entities.duration {
+            match parse_float(&event.value) {
+                Ok(f) if f > 0.0 => {
+                    input.duration = f;
+                    status.0 = None;
+                }
+                Ok(_) => status.0 = Some("Time window must be positive".to_owned()),
+                Err(err) => status.0 = Some(err),
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_spectrum_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ToggleSpectrumMarker>),
+    >,
+    mut my_events: EventWriter<SpectrumEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        spectrum_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            SpectrumEvent::Toggle,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn spectrum_domain_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<SpectrumDomainMarker>),
+    >,
+    mut my_events: EventWriter<SpectrumEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        spectrum_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            SpectrumEvent::Domain,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn spectrum_component_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<SpectrumComponentMarker>),
+    >,
+    mut my_events: EventWriter<SpectrumEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        spectrum_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            SpectrumEvent::Component,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn spectrum_window_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<SpectrumWindowMarker>),
+    >,
+    mut my_events: EventWriter<SpectrumEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        spectrum_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            SpectrumEvent::Window,
+        );
+    }
+}
+
+fn spectrum_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<SpectrumEvent>,
+    event: SpectrumEvent,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(event);
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+/// the probe values are only collected while they're shown, and only for one component
+pub fn listen_spectrum_events(
+    mut events: EventReader<SpectrumEvent>,
+    mut input: ResMut<SpectrumInput>,
+    mut history: ResMut<ProbeHistory>,
+) {
+    for event in events.read() {
+        match event {
+            SpectrumEvent::Toggle => input.enabled = !input.enabled,
+            SpectrumEvent::Domain => input.domain = input.domain.next(),
+            SpectrumEvent::Component => input.component = input.component.next(),
+            SpectrumEvent::Window => input.window = input.window.next(),
+        }
+        history.0.clear();
+    }
+}
+
+/// settings, resolution and the strongest line
+pub fn update_spectrum_label(
+    input: Res<SpectrumInput>,
+    spectrum: Res<Spectrum>,
+    status: Res<SpectrumUiStatus>,
+    mut label_query: Query<&mut Text, With<SpectrumInfoMarker>>,
+) {
+    let text = if let Some(err) = &status.0 {
+        err.clone()
+    } else if !input.enabled {
+        "off".to_owned()
+    } else {
+        let unit = input.domain.unit();
+        let mut text = format!(
+            "{}, {} component\n{} window, {} samples",
+            input.domain, input.component, input.window, spectrum.samples
+        );
+        let (period, period_unit) = match input.domain {
+            SpectrumDomain::Space => ("λ", "m"),
+            SpectrumDomain::Time => ("T", "s"),
+        };
+        match (spectrum.lines.get(1), dominant(&spectrum.lines)) {
+            (Some(first), Some(peak)) => {
+                let shown = shown_lines(&spectrum.lines);
+                text.push_str(&format!(
+                    "\nΔ: {} {}, up to {} {}\npeak: {} {} ({} {} {})\n|A|: {} φ: {:.2} rad",
+                    format_value(first.frequency),
+                    unit,
+                    format_value(spectrum.lines[shown - 1].frequency),
+                    unit,
+                    format_value(peak.frequency),
+                    unit,
+                    period,
+                    format_value(1.0 / peak.frequency),
+                    period_unit,
+                    format_value(peak.magnitude),
+                    peak.phase.get::<radian>(),
+                ));
+            }
+            _ if input.domain == SpectrumDomain::Time && spectrum.samples > 0 => {
+                text.push_str("\ncollecting...")
+            }
+            _ => text.push_str("\nnothing sampled"),
+        }
+        text
+    };
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+/// 3 decimals, scientific notation only for very small or large values
+fn format_value(value: f32) -> String {
+    if value != 0.0 && !(1e-3..1e4).contains(&value.abs()) {
+        format!("{:.3e}", value)
+    } else {
+        format!("{:.3}", value)
+    }
+}
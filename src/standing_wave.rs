@@ -0,0 +1,161 @@
+use bevy::{
+    color::palettes::css::{AQUA, DARK_GRAY, ORANGE, RED, WHITE, YELLOW},
+    prelude::*,
+};
+use bevy_simple_text_input::{TextInputPlugin, TextInputSystem};
+use uom::si::{
+    f32::{Length, Velocity},
+    length::meter,
+    velocity::meter_per_second,
+};
+use waves_core::{
+    standing_wave::{calculate_u_standing, BoundaryConditions, StandingWaveParameters},
+    wave::calculate_u,
+};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::{add_display_scale, DisplayScale},
+    standing_wave_ui::{
+        fixed_fixed_button_handler, fixed_free_button_handler,
+        listen_boundary_conditions_ui_inputs, listen_standing_wave_ui_inputs,
+        setup_standing_wave_ui, standing_wave_text_listener, update_standing_wave_info_label,
+        BoundaryConditionsInputEvent, StandingWave, StandingWaveUiInputs,
+    },
+    wave::draw_planar_fn_as_vert_vecs,
+    wave_ui::focus,
+};
+
+/// standing wave on a string, as sum of a forward and a reflected wave
+pub fn add_standing_wave_2d_system(app: &mut App) {
+    let standing_wave = StandingWaveParameters {
+        amplitude: Length::new::<meter>(1.0),
+        length: Length::new::<meter>(8.0),
+        wave_speed: Velocity::new::<meter_per_second>(4.0),
+        harmonic: 1,
+        boundary_conditions: BoundaryConditions::FixedFixed,
+    };
+
+    app.add_event::<BoundaryConditionsInputEvent>()
+        .add_plugins(TextInputPlugin)
+        .insert_resource(StandingWaveUiInputs {
+            amplitude: standing_wave.amplitude.get::<meter>().to_string(),
+            length: standing_wave.length.get::<meter>().to_string(),
+            wave_speed: standing_wave
+                .wave_speed
+                .get::<meter_per_second>()
+                .to_string(),
+            harmonic: standing_wave.harmonic.to_string(),
+        })
+        .insert_resource(StandingWave(standing_wave))
+        .add_systems(Startup, setup_standing_wave_ui)
+        .add_systems(Update, focus.before(TextInputSystem))
+        .add_systems(
+            Update,
+            (
+                draw_standing_wave,
+                standing_wave_text_listener,
+                listen_standing_wave_ui_inputs,
+                fixed_fixed_button_handler,
+                fixed_free_button_handler,
+                listen_boundary_conditions_ui_inputs,
+                update_standing_wave_info_label,
+                fit_display_scale,
+            ),
+        );
+    add_display_scale(app, DisplayScale::default_2d());
+}
+
+/// fits the medium on screen, with some margin
+fn fit_display_scale(mut display_scale: ResMut<DisplayScale>, standing_wave: Res<StandingWave>) {
+    if !display_scale.auto {
+        return;
+    }
+    let pars = standing_wave.0;
+    let fitted = display_scale.fitted(
+        pars.length * 1.25,
+        1.0 / pars.frequency(),
+        // forward and reflected wave add up
+        2.0 * pars.amplitude.get::<meter>(),
+    );
+    display_scale.set_if_neq(fitted);
+}
+
+fn draw_standing_wave(
+    mut gizmos: Gizmos,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+    standing_wave: Res<StandingWave>,
+) {
+    let pars = standing_wave.0;
+    let display_scale = *display_scale;
+
+    let t = display_scale.physical_time(clock.elapsed);
+
+    // the medium spans [0, L], centered on screen
+    let half_length = pars.length.get::<meter>() / 2.0;
+
+    let forward = pars.forward_wave();
+    let reflected = pars.reflected_wave();
+
+    draw_planar_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        -half_length,
+        half_length,
+        ORANGE,
+        |x: f32| {
+            let x = x + half_length;
+            let vec3 = calculate_u(Length::new::<meter>(x), t, &forward, Vec3::Y).to_vec3();
+            Vec2::new(vec3.x, vec3.y)
+        },
+    );
+
+    draw_planar_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        -half_length,
+        half_length,
+        AQUA,
+        |x: f32| {
+            let x = x + half_length;
+            let vec3 = calculate_u(Length::new::<meter>(x), t, &reflected, Vec3::Y).to_vec3();
+            Vec2::new(vec3.x, vec3.y)
+        },
+    );
+
+    // sum on top
+    draw_planar_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        -half_length,
+        half_length,
+        WHITE,
+        |x: f32| {
+            let x = x + half_length;
+            let vec3 = calculate_u_standing(Length::new::<meter>(x), t, &pars, Vec3::Y).to_vec3();
+            Vec2::new(vec3.x, vec3.y)
+        },
+    );
+
+    // ends of the medium, as high as the maximum displacement
+    let end_height = display_scale.amplitude_to_screen(2.0 * pars.amplitude.get::<meter>());
+    for end in [-half_length, half_length] {
+        let x = end * display_scale.length;
+        gizmos.line_2d(
+            Vec2::new(x, -end_height),
+            Vec2::new(x, end_height),
+            DARK_GRAY,
+        );
+    }
+
+    for node in pars.nodes() {
+        let x = display_scale.x_to_screen(node - pars.length / 2.0);
+        gizmos.circle_2d(Vec2::new(x, 0.0), 5.0, RED);
+    }
+
+    for antinode in pars.antinodes() {
+        let x = display_scale.x_to_screen(antinode - pars.length / 2.0);
+        gizmos.circle_2d(Vec2::new(x, 0.0), 5.0, YELLOW);
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{
+    f32::{Length, Velocity},
+    frequency::hertz,
+    length::meter,
+    velocity::meter_per_second,
+};
+use waves_core::standing_wave::{BoundaryConditions, StandingWaveParameters};
+
+use crate::wave_ui::{
+    add_button, add_label, add_warning_label, generate_input_box, generate_label, parse_float,
+    AmplitudeInputMarker, WarningMarker,
+};
+
+#[derive(Resource)]
+pub struct StandingWaveUiInputs {
+    pub amplitude: String,
+    pub length: String,
+    pub wave_speed: String,
+    pub harmonic: String,
+}
+
+#[derive(Resource)]
+pub struct StandingWaveUiInputEntities {
+    pub amplitude: Entity,
+    pub length: Entity,
+    pub wave_speed: Entity,
+    pub harmonic: Entity,
+}
+
+/// the standing wave being drawn
+#[derive(Resource, Debug)]
+pub struct StandingWave(pub StandingWaveParameters);
+
+#[derive(Component, Default)]
+pub struct MediumLengthInputMarker;
+#[derive(Component, Default)]
+pub struct WaveSpeedInputMarker;
+#[derive(Component, Default)]
+pub struct HarmonicInputMarker;
+#[derive(Component, Default)]
+pub struct FixedFixedMarker;
+#[derive(Component, Default)]
+pub struct FixedFreeMarker;
+#[derive(Component, Default)]
+pub struct StandingWaveInfoMarker;
+
+#[derive(Event, Default, Debug)]
+pub struct BoundaryConditionsInputEvent {
+    pub boundary_conditions: BoundaryConditions,
+}
+
+/// adds ui to configure standing wave
+pub fn setup_standing_wave_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<StandingWaveUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Amplitude (m)",
+        AmplitudeInputMarker,
+        form_state.amplitude.clone(),
+    );
+    let length_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Length (m)",
+        MediumLengthInputMarker,
+        form_state.length.clone(),
+    );
+    let wave_speed_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Wave speed (m/s)",
+        WaveSpeedInputMarker,
+        form_state.wave_speed.clone(),
+    );
+    let harmonic_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Harmonic n",
+        HarmonicInputMarker,
+        form_state.harmonic.clone(),
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    add_label(&mut commands, root_id, &font, "Ends");
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Fixed-fixed",
+        FixedFixedMarker,
+    );
+    add_button(&mut commands, root_id, &font, "Fixed-free", FixedFreeMarker);
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((StandingWaveInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(StandingWaveUiInputEntities {
+        amplitude: amplitude_input,
+        length: length_input,
+        wave_speed: wave_speed_input,
+        harmonic: harmonic_input,
+    });
+}
+
+pub fn standing_wave_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<StandingWaveUiInputs>,
+    input_entities: Res<StandingWaveUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.amplitude {
+            inputs.amplitude = event.value.clone();
+        } else if event.entity == input_entities.length {
+            inputs.length = event.value.clone();
+        } else if event.entity == input_entities.wave_speed {
+            inputs.wave_speed = event.value.clone();
+        } else if event.entity == input_entities.harmonic {
+            inputs.harmonic = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the standing wave model, showing errors in the warning label
+pub fn listen_standing_wave_ui_inputs(
+    inputs: Res<StandingWaveUiInputs>,
+    mut standing_wave: ResMut<StandingWave>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let result = parse_standing_wave_inputs(&inputs);
+
+    let warning = match result {
+        Ok((amplitude, length, wave_speed, harmonic)) => {
+            let pars = &mut standing_wave.0;
+            pars.amplitude = amplitude;
+            pars.length = length;
+            pars.wave_speed = wave_speed;
+            pars.harmonic = harmonic;
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_standing_wave_inputs(
+    inputs: &StandingWaveUiInputs,
+) -> Result<(Length, Length, Velocity, u32), String> {
+    let amplitude = Length::new::<meter>(parse_float(&inputs.amplitude)?);
+    let length = parse_float(&inputs.length)?;
+    if length <= 0.0 {
+        return Err("Length must be positive".to_string());
+    }
+    let wave_speed = parse_float(&inputs.wave_speed)?;
+    if wave_speed <= 0.0 {
+        return Err("Wave speed must be positive".to_string());
+    }
+    let harmonic = match inputs.harmonic.parse::<u32>() {
+        Ok(n) if n >= 1 => n,
+        _ => return Err("Harmonic must be an integer >= 1".to_string()),
+    };
+    Ok((
+        amplitude,
+        Length::new::<meter>(length),
+        Velocity::new::<meter_per_second>(wave_speed),
+        harmonic,
+    ))
+}
+
+/// shows the derived wave length and frequency of the selected harmonic
+pub fn update_standing_wave_info_label(
+    standing_wave: Res<StandingWave>,
+    mut label_query: Query<&mut Text, With<StandingWaveInfoMarker>>,
+) {
+    if !standing_wave.is_changed() {
+        return;
+    }
+    let pars = &standing_wave.0;
+    for mut label in &mut label_query {
+        label.sections[0].value = format!(
+            "λ: {} m\nf: {} hz\nnodes: {}",
+            pars.wave_length().get::<meter>(),
+            pars.frequency().get::<hertz>(),
+            pars.nodes().len(),
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn fixed_fixed_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<FixedFixedMarker>),
+    >,
+    mut my_events: EventWriter<BoundaryConditionsInputEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        boundary_conditions_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            BoundaryConditions::FixedFixed,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn fixed_free_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<FixedFreeMarker>),
+    >,
+    mut my_events: EventWriter<BoundaryConditionsInputEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        boundary_conditions_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            BoundaryConditions::FixedFree,
+        );
+    }
+}
+
+fn boundary_conditions_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<BoundaryConditionsInputEvent>,
+    boundary_conditions: BoundaryConditions,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(BoundaryConditionsInputEvent {
+                boundary_conditions,
+            });
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+pub fn listen_boundary_conditions_ui_inputs(
+    mut events: EventReader<BoundaryConditionsInputEvent>,
+    mut standing_wave: ResMut<StandingWave>,
+) {
+    for input in events.read() {
+        standing_wave.0.boundary_conditions = input.boundary_conditions;
+    }
+}
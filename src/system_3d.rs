@@ -14,7 +14,6 @@ use bevy::{
     },
 };
 
-#[allow(dead_code)]
 pub fn add_3d_space(app: &mut App) {
     app.add_plugins((
         DefaultPlugins,
@@ -40,6 +39,11 @@ fn setup_light(mut commands: Commands) {
         color: Color::WHITE,
         brightness: 1.0,
     });
+    // from above, so surfaces in the x-z plane show their slopes
+    commands.spawn(DirectionalLightBundle {
+        transform: Transform::from_xyz(2.0, 4.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
 }
 
 fn setup_global_axes(mut gizmos: Gizmos) {
@@ -0,0 +1,297 @@
+use bevy::{
+    color::palettes::css::{DARK_GRAY, ORANGE, WHITE, YELLOW},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_simple_text_input::TextInputValue;
+use uom::si::{
+    f32::{Force, Length, LinearMassDensity, Time},
+    force::newton,
+    length::meter,
+    linear_mass_density::kilogram_per_meter,
+};
+use waves_core::vibrating_string::{
+    InitialConditions, InitialShape, InitialVelocity, StringEnd, StringParameters, StringSimulation,
+};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::{add_display_scale, DisplayScale},
+    vibrating_string_ui::{
+        draw_string_button_handler, left_end_button_handler, listen_draw_string_events,
+        listen_restart_string_events, listen_string_end_events, listen_vibrating_string_ui_inputs,
+        restart_string_button_handler, right_end_button_handler, setup_vibrating_string_ui,
+        update_vibrating_string_info_label, vibrating_string_text_listener, DrawStringEvent,
+        RestartStringEvent, StringEndEvent, VibratingStringUiInputEntities,
+        VibratingStringUiInputs,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// grid points along the string, minus one
+const SEGMENTS: usize = 400;
+/// upper bound of the steps per fixed step, catching up after a jump of the clock takes longer
+const MAX_STEPS_PER_TICK: usize = 10_000;
+/// radius of the ring marking a free end
+const FREE_END_RADIUS: f32 = 5.0;
+
+/// the string as entered in the form
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct VibratingString {
+    pub parameters: StringParameters,
+    pub left_end: StringEnd,
+    pub right_end: StringEnd,
+    pub initial: InitialConditions,
+}
+
+impl Default for VibratingString {
+    /// 8 m, c = 4 m/s, plucked at a quarter
+    fn default() -> Self {
+        VibratingString {
+            parameters: StringParameters {
+                length: Length::new::<meter>(8.0),
+                tension: Force::new::<newton>(16.0),
+                linear_density: LinearMassDensity::new::<kilogram_per_meter>(1.0),
+            },
+            left_end: StringEnd::Fixed,
+            right_end: StringEnd::Fixed,
+            initial: InitialConditions {
+                shape: InitialShape::Pluck { position: 0.25 },
+                amplitude: Length::new::<meter>(1.0),
+                velocity: InitialVelocity::AtRest,
+            },
+        }
+    }
+}
+
+/// the running solver, the string starts moving at `origin` on the clock
+#[derive(Resource, Default)]
+pub struct VibratingStringSimulation {
+    pub simulation: Option<StringSimulation>,
+    /// physical time of the clock at which the string was released
+    pub origin: Time,
+}
+
+impl VibratingStringSimulation {
+    /// releases the string again at t
+    pub fn restart(&mut self, t: Time) {
+        self.simulation = None;
+        self.origin = t;
+    }
+}
+
+/// points drawn with the mouse, in m, x from the left end
+#[derive(Resource, Default)]
+pub struct StringDrawing {
+    pub active: bool,
+    pub points: Vec<Vec2>,
+}
+
+/// string solved with finite differences, from an initial displacement and velocity
+pub fn add_vibrating_string_2d_system(app: &mut App) {
+    let string = VibratingString::default();
+    add_text_input_plugin(app);
+    app.add_event::<StringEndEvent>()
+        .add_event::<DrawStringEvent>()
+        .add_event::<RestartStringEvent>()
+        .insert_resource(VibratingStringUiInputs::from(&string))
+        .insert_resource(string)
+        .init_resource::<VibratingStringSimulation>()
+        .init_resource::<StringDrawing>()
+        .add_systems(Startup, setup_vibrating_string_ui)
+        .add_systems(FixedUpdate, step_vibrating_string)
+        .add_systems(
+            Update,
+            (
+                draw_vibrating_string,
+                draw_string_with_mouse.before(listen_draw_string_events),
+                vibrating_string_text_listener,
+                listen_vibrating_string_ui_inputs,
+                left_end_button_handler,
+                right_end_button_handler,
+                listen_string_end_events,
+                draw_string_button_handler,
+                listen_draw_string_events,
+                restart_string_button_handler,
+                listen_restart_string_events,
+                update_vibrating_string_info_label,
+                fit_display_scale,
+            ),
+        );
+    add_display_scale(app, DisplayScale::default_2d());
+}
+
+/// fits the string on screen, with some margin, one round trip 2L/c as period
+fn fit_display_scale(mut display_scale: ResMut<DisplayScale>, string: Res<VibratingString>) {
+    if !display_scale.auto {
+        return;
+    }
+    let parameters = string.parameters;
+    let fitted = display_scale.fitted(
+        parameters.length * 1.25,
+        parameters.length * 2.0 / parameters.wave_speed(),
+        string.initial.amplitude.get::<meter>(),
+    );
+    display_scale.set_if_neq(fitted);
+}
+
+/// restarts the solver when the string changes, then runs it up to the time on the clock
+/// before the origin the string is shown at rest in its initial shape
+fn step_vibrating_string(
+    string: Res<VibratingString>,
+    mut state: ResMut<VibratingStringSimulation>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+) {
+    let t = display_scale.physical_time(clock.elapsed);
+    if string.is_changed() {
+        state.restart(t);
+    }
+    let elapsed = t - state.origin;
+
+    let outdated = match &state.simulation {
+        Some(simulation) => elapsed < simulation.time() && simulation.steps() > 1,
+        None => true,
+    };
+    if outdated {
+        state.simulation = Some(StringSimulation::new(
+            string.parameters,
+            (string.left_end, string.right_end),
+            &string.initial,
+            SEGMENTS,
+        ));
+    }
+    if let Some(simulation) = &mut state.simulation {
+        simulation.advance_to(elapsed, MAX_STEPS_PER_TICK);
+    }
+}
+
+/// the string as polyline, its ends, and the shape being drawn
+fn draw_vibrating_string(
+    mut gizmos: Gizmos,
+    string: Res<VibratingString>,
+    state: Res<VibratingStringSimulation>,
+    drawing: Res<StringDrawing>,
+    display_scale: Res<DisplayScale>,
+) {
+    // the string spans [0, L], centered on screen
+    let half_length = string.parameters.length / 2.0;
+    let to_screen = |x: Length, u: Length| {
+        Vec2::new(
+            display_scale.x_to_screen(x - half_length),
+            display_scale.amplitude_to_screen(u.get::<meter>()),
+        )
+    };
+
+    if let Some(simulation) = &state.simulation {
+        gizmos.linestrip_2d(
+            (0..=simulation.segments())
+                .map(|i| to_screen(simulation.x(i), simulation.displacement(i))),
+            WHITE,
+        );
+
+        let end_height =
+            display_scale.amplitude_to_screen(string.initial.amplitude.get::<meter>().abs());
+        let last = simulation.segments();
+        for (end, i) in [(string.left_end, 0), (string.right_end, last)] {
+            let point = to_screen(simulation.x(i), simulation.displacement(i));
+            match end {
+                StringEnd::Fixed => gizmos.line_2d(
+                    Vec2::new(point.x, -end_height),
+                    Vec2::new(point.x, end_height),
+                    DARK_GRAY,
+                ),
+                // ring sliding along a rod
+                StringEnd::Free => {
+                    gizmos.line_2d(
+                        Vec2::new(point.x, -end_height),
+                        Vec2::new(point.x, end_height),
+                        DARK_GRAY,
+                    );
+                    gizmos.circle_2d(point, FREE_END_RADIUS, YELLOW);
+                }
+                StringEnd::Absorbing => gizmos.line_2d(
+                    Vec2::new(point.x, -end_height),
+                    Vec2::new(point.x, end_height),
+                    ORANGE,
+                ),
+            }
+        }
+    }
+
+    if drawing.active && drawing.points.len() > 1 {
+        gizmos.linestrip_2d(
+            drawing.points.iter().map(|point| {
+                to_screen(Length::new::<meter>(point.x), Length::new::<meter>(point.y))
+            }),
+            YELLOW,
+        );
+    }
+}
+
+/// while drawing is active, a drag with the left mouse button sets the initial shape
+/// the points are relative to the entered amplitude, positions outside the string are dropped
+#[allow(clippy::too_many_arguments)]
+fn draw_string_with_mouse(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    display_scale: Res<DisplayScale>,
+    mut drawing: ResMut<StringDrawing>,
+    mut string: ResMut<VibratingString>,
+    mut inputs: ResMut<VibratingStringUiInputs>,
+    input_entities: Res<VibratingStringUiInputEntities>,
+    mut input_values: Query<&mut TextInputValue>,
+) {
+    if !drawing.active {
+        return;
+    }
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        drawing.points.clear();
+    }
+
+    let length = string.parameters.length.get::<meter>();
+    if mouse_button_input.pressed(MouseButton::Left) {
+        let (Ok(window), Ok((camera, camera_transform))) =
+            (windows.get_single(), cameras.get_single())
+        else {
+            return;
+        };
+        let Some(position) = window
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+        else {
+            return;
+        };
+        let x = position.x / display_scale.length + length / 2.0;
+        let u = position.y / display_scale.amplitude;
+        let is_right_of_last = drawing.points.last().is_none_or(|last| x > last.x);
+        if (0.0..=length).contains(&x) && is_right_of_last {
+            drawing.points.push(Vec2::new(x, u));
+        }
+    }
+
+    if mouse_button_input.just_released(MouseButton::Left) && drawing.points.len() > 1 {
+        let amplitude = string.initial.amplitude.get::<meter>();
+        let amplitude = if amplitude == 0.0 { 1.0 } else { amplitude };
+        let points = drawing
+            .points
+            .iter()
+            .map(|point| (point.x / length, point.y / amplitude))
+            .collect();
+        string.initial.shape = InitialShape::Drawn(points);
+        string.initial.amplitude = Length::new::<meter>(amplitude);
+        inputs.shape = string.initial.shape.to_string();
+        inputs.amplitude = amplitude.to_string();
+        for (entity, value) in [
+            (input_entities.shape, &inputs.shape),
+            (input_entities.amplitude, &inputs.amplitude),
+        ] {
+            if let Ok(mut input_value) = input_values.get_mut(entity) {
+                input_value.0 = value.clone();
+            }
+        }
+        drawing.active = false;
+        drawing.points.clear();
+    }
+}
@@ -0,0 +1,452 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{
+    energy::joule,
+    f32::{Force, Length, LinearMassDensity},
+    force::newton,
+    frequency::hertz,
+    length::meter,
+    linear_mass_density::kilogram_per_meter,
+    time::second,
+    velocity::meter_per_second,
+};
+use waves_core::vibrating_string::{InitialShape, InitialVelocity};
+
+use crate::{
+    clock::SimulationClock,
+    display_scale::DisplayScale,
+    vibrating_string::{StringDrawing, VibratingString, VibratingStringSimulation},
+    wave_ui::{
+        add_button, add_label, add_warning_label, generate_input_box, generate_label, parse_float,
+        AmplitudeInputMarker, WarningMarker,
+    },
+};
+
+#[derive(Resource)]
+pub struct VibratingStringUiInputs {
+    pub length: String,
+    pub tension: String,
+    pub linear_density: String,
+    pub amplitude: String,
+    pub shape: String,
+    pub velocity: String,
+}
+
+impl From<&VibratingString> for VibratingStringUiInputs {
+    fn from(string: &VibratingString) -> Self {
+        VibratingStringUiInputs {
+            length: string.parameters.length.get::<meter>().to_string(),
+            tension: string.parameters.tension.get::<newton>().to_string(),
+            linear_density: string
+                .parameters
+                .linear_density
+                .get::<kilogram_per_meter>()
+                .to_string(),
+            amplitude: string.initial.amplitude.get::<meter>().to_string(),
+            shape: string.initial.shape.to_string(),
+            velocity: string.initial.velocity.to_string(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct VibratingStringUiInputEntities {
+    pub length: Entity,
+    pub tension: Entity,
+    pub linear_density: Entity,
+    pub amplitude: Entity,
+    pub shape: Entity,
+    pub velocity: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct StringLengthInputMarker;
+#[derive(Component, Default)]
+pub struct TensionInputMarker;
+#[derive(Component, Default)]
+pub struct LinearDensityInputMarker;
+#[derive(Component, Default)]
+pub struct InitialShapeInputMarker;
+#[derive(Component, Default)]
+pub struct InitialVelocityInputMarker;
+#[derive(Component, Default)]
+pub struct LeftEndMarker;
+#[derive(Component, Default)]
+pub struct RightEndMarker;
+#[derive(Component, Default)]
+pub struct DrawStringMarker;
+#[derive(Component, Default)]
+pub struct RestartStringMarker;
+#[derive(Component, Default)]
+pub struct VibratingStringInfoMarker;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringSide {
+    Left,
+    Right,
+}
+
+/// switches the end to the next kind, fixed, free, absorbing
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StringEndEvent {
+    pub side: StringSide,
+}
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DrawStringEvent;
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RestartStringEvent;
+
+/// adds ui to configure the string
+pub fn setup_vibrating_string_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    form_state: Res<VibratingStringUiInputs>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            top: Val::Px(0.0),
+            right: Val::Px(0.0),
+            width: Val::Px(130.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    let length_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Length (m)",
+        StringLengthInputMarker,
+        form_state.length.clone(),
+    );
+    let tension_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Tension (N)",
+        TensionInputMarker,
+        form_state.tension.clone(),
+    );
+    let linear_density_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Density (kg/m)",
+        LinearDensityInputMarker,
+        form_state.linear_density.clone(),
+    );
+    let amplitude_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Amplitude (m)",
+        AmplitudeInputMarker,
+        form_state.amplitude.clone(),
+    );
+    let shape_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Shape",
+        InitialShapeInputMarker,
+        form_state.shape.clone(),
+    );
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "pluck 0.25\ngaussian 0.5 0.05\nsine 2, flat",
+    );
+    let velocity_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Velocity",
+        InitialVelocityInputMarker,
+        form_state.velocity.clone(),
+    );
+    add_label(
+        &mut commands,
+        root_id,
+        &font,
+        "rest, right, left\n2 gaussian 0.5 0.05",
+    );
+
+    add_warning_label(&mut commands, root_id, &font);
+
+    add_button(&mut commands, root_id, &font, "Left end", LeftEndMarker);
+    add_button(&mut commands, root_id, &font, "Right end", RightEndMarker);
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Draw shape",
+        DrawStringMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Restart",
+        RestartStringMarker,
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((VibratingStringInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(VibratingStringUiInputEntities {
+        length: length_input,
+        tension: tension_input,
+        linear_density: linear_density_input,
+        amplitude: amplitude_input,
+        shape: shape_input,
+        velocity: velocity_input,
+    });
+}
+
+pub fn vibrating_string_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    mut inputs: ResMut<VibratingStringUiInputs>,
+    input_entities: Res<VibratingStringUiInputEntities>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.length {
+            inputs.length = event.value.clone();
+        } else if event.entity == input_entities.tension {
+            inputs.tension = event.value.clone();
+        } else if event.entity == input_entities.linear_density {
+            inputs.linear_density = event.value.clone();
+        } else if event.entity == input_entities.amplitude {
+            inputs.amplitude = event.value.clone();
+        } else if event.entity == input_entities.shape {
+            inputs.shape = event.value.clone();
+        } else if event.entity == input_entities.velocity {
+            inputs.velocity = event.value.clone();
+        }
+    }
+}
+
+/// parses the form into the string, showing errors in the warning label
+pub fn listen_vibrating_string_ui_inputs(
+    inputs: Res<VibratingStringUiInputs>,
+    mut string: ResMut<VibratingString>,
+    mut warning_query: Query<&mut Text, With<WarningMarker>>,
+) {
+    if !inputs.is_changed() {
+        return;
+    }
+
+    let warning = match parse_vibrating_string_inputs(&inputs, &string) {
+        Ok(parsed) => {
+            string.set_if_neq(parsed);
+            "".to_string()
+        }
+        Err(err) => err,
+    };
+
+    for mut warning_label in &mut warning_query {
+        warning_label.sections[0].value = warning.clone();
+    }
+}
+
+fn parse_vibrating_string_inputs(
+    inputs: &VibratingStringUiInputs,
+    current: &VibratingString,
+) -> Result<VibratingString, String> {
+    let positive = |value: &str, name: &str| {
+        let value = parse_float(value)?;
+        if value > 0.0 {
+            Ok(value)
+        } else {
+            Err(format!("{} must be positive", name))
+        }
+    };
+    let length = positive(&inputs.length, "Length")?;
+    let tension = positive(&inputs.tension, "Tension")?;
+    let linear_density = positive(&inputs.linear_density, "Density")?;
+    let amplitude = parse_float(&inputs.amplitude)?;
+    // a drawn shape has no text form, "drawn" keeps the current one
+    let shape = match (inputs.shape.trim(), &current.initial.shape) {
+        ("drawn", InitialShape::Drawn(points)) => InitialShape::Drawn(points.clone()),
+        (shape, _) => shape.parse()?,
+    };
+    let velocity: InitialVelocity = inputs.velocity.parse()?;
+
+    let mut string = current.clone();
+    string.parameters.length = Length::new::<meter>(length);
+    string.parameters.tension = Force::new::<newton>(tension);
+    string.parameters.linear_density = LinearMassDensity::new::<kilogram_per_meter>(linear_density);
+    string.initial.amplitude = Length::new::<meter>(amplitude);
+    string.initial.shape = shape;
+    string.initial.velocity = velocity;
+    Ok(string)
+}
+
+/// wave speed, fundamental frequency, grid and energy
+pub fn update_vibrating_string_info_label(
+    string: Res<VibratingString>,
+    state: Res<VibratingStringSimulation>,
+    drawing: Res<StringDrawing>,
+    mut label_query: Query<&mut Text, With<VibratingStringInfoMarker>>,
+) {
+    let parameters = string.parameters;
+    let mut text = format!(
+        "ends: {}-{}\nc: {} m/s\nf₁: {}",
+        string.left_end,
+        string.right_end,
+        parameters.wave_speed().get::<meter_per_second>(),
+        parameters
+            .fundamental_frequency(string.left_end, string.right_end)
+            .map(|f| format!("{} hz", f.get::<hertz>()))
+            .unwrap_or_else(|| "-".to_owned()),
+    );
+    if let Some(simulation) = &state.simulation {
+        text.push_str(&format!(
+            "\nΔx: {:.3} m\nΔt: {:.2e} s\nt: {:.2} s\nE: {:.3e} J",
+            simulation.dx().get::<meter>(),
+            simulation.time_step().get::<second>(),
+            simulation.time().get::<second>(),
+            simulation.energy().get::<joule>(),
+        ));
+    }
+    if drawing.active {
+        text.push_str("\ndrag to draw");
+    }
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn left_end_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<LeftEndMarker>),
+    >,
+    mut my_events: EventWriter<StringEndEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        string_button_handler((interaction, &mut color, &mut border_color), || {
+            my_events.send(StringEndEvent {
+                side: StringSide::Left,
+            });
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn right_end_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RightEndMarker>),
+    >,
+    mut my_events: EventWriter<StringEndEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        string_button_handler((interaction, &mut color, &mut border_color), || {
+            my_events.send(StringEndEvent {
+                side: StringSide::Right,
+            });
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn draw_string_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<DrawStringMarker>),
+    >,
+    mut my_events: EventWriter<DrawStringEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        string_button_handler((interaction, &mut color, &mut border_color), || {
+            my_events.send(DrawStringEvent);
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn restart_string_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RestartStringMarker>),
+    >,
+    mut my_events: EventWriter<RestartStringEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        string_button_handler((interaction, &mut color, &mut border_color), || {
+            my_events.send(RestartStringEvent);
+        });
+    }
+}
+
+fn string_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    mut on_press: impl FnMut(),
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            on_press();
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+pub fn listen_string_end_events(
+    mut events: EventReader<StringEndEvent>,
+    mut string: ResMut<VibratingString>,
+) {
+    for event in events.read() {
+        match event.side {
+            StringSide::Left => string.left_end = string.left_end.next(),
+            StringSide::Right => string.right_end = string.right_end.next(),
+        }
+    }
+}
+
+pub fn listen_draw_string_events(
+    mut events: EventReader<DrawStringEvent>,
+    mut drawing: ResMut<StringDrawing>,
+) {
+    for _ in events.read() {
+        drawing.active = !drawing.active;
+        drawing.points.clear();
+    }
+}
+
+/// releases the string again in its initial shape, from now on
+pub fn listen_restart_string_events(
+    mut events: EventReader<RestartStringEvent>,
+    mut state: ResMut<VibratingStringSimulation>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+) {
+    for _ in events.read() {
+        state.restart(display_scale.physical_time(clock.elapsed));
+    }
+}
@@ -1,22 +1,36 @@
-use std::f32::consts::PI;
-
+use crate::attenuation::{add_attenuation_system, AttenuationInput};
+use crate::clock::SimulationClock;
+use crate::display_scale::{add_display_scale, samples, DisplayScale, WAVES_ON_SCREEN};
+use crate::medium::{add_medium_system, MediumInput};
+use crate::oscilloscope::add_oscilloscope_system;
+use crate::sampled_wave::SampledWave;
+use crate::spectrum::add_spectrum_system;
+use crate::wave_packet::{
+    add_wave_packet_system, PacketOnScreen, WavePacketInput, GROUP_MARKER_COLOR, PHASE_MARKER_COLOR,
+};
 use crate::wave_ui::{
-    focus, form_state_notifier_system, listen_wave_ui_inputs, setup_wave_ui, text_listener,
-    Amplitude, Freq, Phase, UiInputs, UiInputsEvent, WaveLength,
+    add_component_wave_button_handler, clear_component_waves_button_handler, focus,
+    form_state_notifier_system, listen_component_waves_ui_inputs, listen_wave_ui_inputs,
+    remove_component_wave_button_handler, setup_wave_ui, text_listener,
+    update_component_waves_label, Amplitude, ComponentWaves, ComponentWavesEvent, Freq, Phase,
+    UiInputs, UiInputsEvent, WaveLength,
+};
+use bevy::{
+    color::palettes::css::{AQUA, DARK_GRAY, FUCHSIA, ORANGE, WHITE, YELLOW},
+    ecs::query::QuerySingleError,
+    prelude::*,
 };
-use bevy::{color::palettes::css::WHITE, ecs::query::QuerySingleError, prelude::*};
 use bevy_simple_text_input::{TextInputPlugin, TextInputSystem};
-use uom::si::{
-    angle::radian,
-    f32::{Frequency, Length},
-    frequency::hertz,
-    length::meter,
-    time::second,
+use uom::si::{f32::Length, length::meter};
+use waves_core::attenuation::Attenuation;
+use waves_core::wave::{
+    calculate_u, calculate_u_superposition, PropagationDirection, WaveParameters,
 };
+use waves_core::wave_packet::{calculate_envelope_raw, calculate_u_packet_raw};
 
-#[allow(dead_code)]
 pub fn add_wave_2d_system(app: &mut App) {
     app.add_event::<UiInputsEvent>()
+        .add_event::<ComponentWavesEvent>()
         .add_plugins(TextInputPlugin)
         .insert_resource(UiInputs {
             amplitude: "1".to_owned(),
@@ -24,7 +38,9 @@ pub fn add_wave_2d_system(app: &mut App) {
             frequency: "0.5".to_owned(),
             phase: "0".to_owned(),
         })
+        .init_resource::<ComponentWaves>()
         .add_systems(Startup, setup_wave_ui)
+        .add_systems(Update, fit_display_scale)
         .add_systems(Update, focus.before(TextInputSystem))
         .add_systems(
             Update,
@@ -33,20 +49,54 @@ pub fn add_wave_2d_system(app: &mut App) {
                 listen_wave_ui_inputs,
                 text_listener,
                 form_state_notifier_system,
+                add_component_wave_button_handler,
+                remove_component_wave_button_handler,
+                clear_component_waves_button_handler,
+                listen_component_waves_ui_inputs.pipe(handle_component_waves_result),
+                update_component_waves_label,
             ),
         );
+    add_display_scale(app, DisplayScale::default_2d());
+    // v_g = v_p/2, like deep water waves, so the crests visibly move through the envelope
+    add_wave_packet_system(app, WavePacketInput::new(0.5));
+    add_medium_system(app);
+    add_attenuation_system(app, AttenuationInput::mechanical());
+    add_spectrum_system(app);
+    add_oscilloscope_system(app);
 }
 
+/// colors of the component waves, repeated if there are more components
+const COMPONENT_COLORS: [Srgba; 4] = [ORANGE, AQUA, FUCHSIA, YELLOW];
+
 #[allow(clippy::too_many_arguments)]
 fn draw_wave(
     gizmos: Gizmos,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+    component_waves: Res<ComponentWaves>,
+    packet: Res<WavePacketInput>,
+    medium: Res<MediumInput>,
+    attenuation: Res<AttenuationInput>,
+    sampled: ResMut<SampledWave>,
     amplitude: Query<&Amplitude>,
     wave_length: Query<&WaveLength>,
     frequency: Query<&Freq>,
     phase: Query<&Phase>,
 ) {
-    match draw_wave_internal(gizmos, time, amplitude, wave_length, frequency, phase) {
+    match draw_wave_internal(
+        gizmos,
+        clock,
+        display_scale,
+        component_waves,
+        packet,
+        medium,
+        attenuation,
+        sampled,
+        amplitude,
+        wave_length,
+        frequency,
+        phase,
+    ) {
         Ok(_) => {}
         Err(e) => match e {
             QuerySingleError::NoEntities(s) => {
@@ -64,178 +114,254 @@ fn draw_wave(
 #[allow(clippy::too_many_arguments)]
 fn draw_wave_internal(
     mut gizmos: Gizmos,
-    time: Res<Time>,
+    clock: Res<SimulationClock>,
+    display_scale: Res<DisplayScale>,
+    component_waves: Res<ComponentWaves>,
+    packet: Res<WavePacketInput>,
+    medium: Res<MediumInput>,
+    attenuation: Res<AttenuationInput>,
+    mut sampled: ResMut<SampledWave>,
     amplitude: Query<&Amplitude>,
     wave_length: Query<&WaveLength>,
     frequency: Query<&Freq>,
     phase: Query<&Phase>,
 ) -> Result<(), QuerySingleError> {
-    let user_pars = WaveUserParameters {
+    let mut pars: WaveParameters = WaveUserParameters {
         amplitude: *amplitude.get_single()?,
         wave_length: *wave_length.get_single()?,
         frequency: *frequency.get_single()?,
         phase: *phase.get_single()?,
-    };
+    }
+    .into();
 
-    let range = 20;
+    let display_scale = *display_scale;
+    let (x_start, x_end) = display_scale.x_range();
+    sampled.x_axis = Vec3::X;
+    sampled.up_axis = Vec3::Y;
 
-    let t = uom::si::f32::Time::new::<second>(time.elapsed_seconds());
-    // let t = uom::si::f32::Time::new::<second>(0);  // not animated
+    pars.frequency = medium.frequency(pars.wave_length, pars.frequency);
+
+    // α is entered directly for mechanical waves, so it's the same for all components
+    let attenuation = attenuation.at(pars.frequency);
+
+    if packet.enabled {
+        let packet = PacketOnScreen::new(
+            packet.to_parameters(
+                pars.amplitude.get::<meter>(),
+                pars.wave_length,
+                pars.frequency,
+                pars.phase,
+                &medium,
+            ),
+            &display_scale,
+            clock.elapsed,
+        );
+        draw_wave_packet(&mut gizmos, &display_scale, &packet, &attenuation);
+        sampled.record(x_start, x_end, packet.t, |x| {
+            calculate_u_packet_raw(packet.local_x(x), packet.t, &packet.components, Vec3::Y)
+                * attenuation.factor(Length::new::<meter>(x), packet.t)
+        });
+        return Ok(());
+    }
+
+    let t = display_scale.physical_time(clock.elapsed);
+    let decay = |x: f32| attenuation.factor(Length::new::<meter>(x), t);
 
     let function = |x: f32| {
-        let vec3 = calculate_u(Length::new::<meter>(x), t, &user_pars, Vec3::Y).to_vec3();
-        Vec2::new(vec3.x, vec3.y)
+        let vec3 = calculate_u(Length::new::<meter>(x), t, &pars, Vec3::Y).to_vec3();
+        Vec2::new(vec3.x, vec3.y) * decay(x)
     };
 
-    draw_planar_fn_as_vert_vecs(&mut gizmos, -range, range, WHITE, function);
+    if component_waves.0.is_empty() {
+        draw_planar_fn_as_vert_vecs(&mut gizmos, &display_scale, x_start, x_end, WHITE, function);
+        sampled.record(x_start, x_end, t, |x| function(x).extend(0.0));
+        return Ok(());
+    }
+
+    // the form wave is a preview of the next component
+    draw_planar_fn_as_vert_vecs(
+        &mut gizmos,
+        &display_scale,
+        x_start,
+        x_end,
+        DARK_GRAY,
+        function,
+    );
+
+    let components: Vec<WaveParameters> = component_waves
+        .0
+        .iter()
+        .map(|user_pars| {
+            let mut pars: WaveParameters = (*user_pars).into();
+            pars.frequency = medium.frequency(pars.wave_length, pars.frequency);
+            pars
+        })
+        .collect();
+
+    for (index, pars) in components.iter().enumerate() {
+        let color = COMPONENT_COLORS[index % COMPONENT_COLORS.len()];
+        draw_planar_fn_as_vert_vecs(&mut gizmos, &display_scale, x_start, x_end, color, |x| {
+            let vec3 = calculate_u(Length::new::<meter>(x), t, pars, Vec3::Y).to_vec3();
+            Vec2::new(vec3.x, vec3.y) * decay(x)
+        });
+    }
+
+    // sum on top
+    let superposition = |x: f32| {
+        calculate_u_superposition(Length::new::<meter>(x), t, &components, Vec3::Y).to_vec3()
+            * decay(x)
+    };
+    draw_planar_fn_as_vert_vecs(&mut gizmos, &display_scale, x_start, x_end, WHITE, |x| {
+        superposition(x).truncate()
+    });
+    sampled.record(x_start, x_end, t, superposition);
 
     Ok(())
 }
 
-/// parameters the user sets via the ui
-#[derive(Debug, Clone)]
-pub struct WaveUserParameters {
-    pub amplitude: Amplitude,
-    pub wave_length: WaveLength,
-    pub frequency: Freq,
-    pub phase: Phase,
-}
+/// carrier, envelope and the markers moving with phase and group velocity
+fn draw_wave_packet(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    packet: &PacketOnScreen,
+    attenuation: &Attenuation,
+) {
+    let (x_start, x_end) = display_scale.x_range();
+    let decay = |x: f32| attenuation.factor(Length::new::<meter>(x), packet.t);
 
-/// to share wave calculation for different domains (currently electromagnetic / non electromagnetic)
-/// lacks some domain specific units and uses just numbers instead
-/// not meant to be instantiated directly
-#[derive(Debug)]
-pub struct RawUserParameters {
-    pub amplitude: f32,
-    pub wave_length: WaveLength,
-    pub frequency: Freq,
-    pub phase: Phase,
-}
+    draw_planar_fn_as_vert_vecs(gizmos, display_scale, x_start, x_end, WHITE, |x| {
+        let vec3 = calculate_u_packet_raw(packet.local_x(x), packet.t, &packet.components, Vec3::Y);
+        Vec2::new(vec3.x, vec3.y) * decay(x)
+    });
 
-impl From<WaveUserParameters> for RawUserParameters {
-    fn from(p: WaveUserParameters) -> Self {
-        RawUserParameters {
-            amplitude: p.amplitude.0.get::<meter>(),
-            wave_length: p.wave_length,
-            frequency: p.frequency,
-            phase: p.phase,
-        }
+    for sign in [1.0, -1.0] {
+        draw_planar_fn_as_line(gizmos, display_scale, x_start, x_end, YELLOW, |x| {
+            sign * calculate_envelope_raw(packet.local_x(x), packet.t, &packet.components)
+                * decay(x)
+        });
     }
-}
 
-#[allow(clippy::too_many_arguments)]
-pub fn calculate_u(
-    x: Length,
-    t: uom::si::f32::Time,
-    up: &WaveUserParameters,
-    direction: Vec3,
-) -> LengthVec3 {
-    let raw = calculate_u_raw(x, t, &up.clone().into(), direction);
-    // assumption: raw amplitude passed to calculate_u_raw (RawUserParameters) was in meter
-    LengthVec3 {
-        x: Length::new::<meter>(raw.x),
-        y: Length::new::<meter>(raw.y),
-        z: Length::new::<meter>(raw.z),
+    let marker_height = display_scale.amplitude_to_screen(packet.packet.amplitude.abs()) * 1.2;
+    for (x, color) in [
+        (packet.phase_x(), PHASE_MARKER_COLOR),
+        (packet.group_x(), GROUP_MARKER_COLOR),
+    ] {
+        let x = x * display_scale.length;
+        gizmos.line_2d(
+            Vec2::new(x, -marker_height),
+            Vec2::new(x, marker_height),
+            color,
+        );
+        gizmos.circle_2d(Vec2::new(x, marker_height), 5.0, color);
     }
 }
 
-#[derive(Debug)]
-pub struct LengthVec3 {
-    pub x: Length,
-    pub y: Length,
-    pub z: Length,
-}
-
-/// f32 vec
-impl LengthVec3 {
-    fn to_vec3(&self) -> Vec3 {
-        Vec3::new(
-            self.x.get::<meter>(),
-            self.y.get::<meter>(),
-            self.z.get::<meter>(),
-        )
+fn handle_component_waves_result(In(result): In<Result<(), QuerySingleError>>) {
+    match result {
+        Ok(_) => {}
+        Err(e) => match e {
+            QuerySingleError::NoEntities(s) => {
+                info!("No entity added yet: {}", s)
+            }
+            QuerySingleError::MultipleEntities(s) => {
+                error!("Found multiple entities of a type: {}", s)
+            }
+        },
     }
 }
 
-/// equation of travelling wave: u(x,t)=Acos(kx−ωt)
-/// nice explanation https://physics.stackexchange.com/a/259007
-/// returns a unit-less vector to be shared between electromagnetic and non electromagnetic wave
-#[allow(clippy::too_many_arguments)]
-pub fn calculate_u_raw(
-    x: Length,
-    t: uom::si::f32::Time,
-    up: &RawUserParameters,
-    unit_vector: Vec3,
-) -> Vec3 {
-    let scalar = calculate_u_scalar_raw(x, t, up).cos();
-    unit_vector * (up.amplitude * scalar)
-}
-
-#[allow(clippy::too_many_arguments)]
-pub fn calculate_u_scalar_raw(x: Length, t: uom::si::f32::Time, up: &RawUserParameters) -> f32 {
-    let screen_speed_pars = to_screen_speed(up);
-    // println!("screen_speed_pars: {:?}", screen_speed_pars);
-
-    // wave cycles per unit distance
-    // there might be reciprocal units on uom? (1/meter here), for now implicit
-    let k = 2.0 * PI / screen_speed_pars.wave_length.get::<meter>();
-
-    let angular_frequency = 2.0 * PI * screen_speed_pars.frequency.get::<hertz>();
-
-    (k * x.get::<meter>()) - (angular_frequency * t.get::<second>()) + up.phase.0.get::<radian>()
+/// parameters the user sets via the ui
+#[derive(Debug, Clone, Copy)]
+pub struct WaveUserParameters {
+    pub amplitude: Amplitude,
+    pub wave_length: WaveLength,
+    pub frequency: Freq,
+    pub phase: Phase,
 }
 
-#[derive(Debug)]
-pub struct ScreenSpeedParameters {
-    pub wave_length: Length,
-    pub frequency: Frequency,
+impl From<WaveUserParameters> for WaveParameters {
+    fn from(p: WaveUserParameters) -> Self {
+        WaveParameters {
+            amplitude: p.amplitude.0,
+            wave_length: p.wave_length.0,
+            frequency: p.frequency.0,
+            phase: p.phase.0,
+            direction: PropagationDirection::Forward,
+        }
+    }
 }
 
-/// slow down for animation
-pub fn to_screen_speed(up: &RawUserParameters) -> ScreenSpeedParameters {
-    let speed_factor: f32 = 0.00000001;
-    // v = fλ -> v * factor = (fλ) * factor
-    ScreenSpeedParameters {
-        // actually, scale down only frequency,
-        // scaling down distance here will give very small decimals, which can't be rendered properly
-        // there's probably a better solution for this (scaling the coordinate space perhaps), but this seems fine for now
-        // wave_length: up.wave_length.0 * speed_factor,
-        wave_length: up.wave_length.0,
-        frequency: up.frequency.0 * speed_factor,
+/// picks the display scale for the wave entered in the form
+fn fit_display_scale(
+    mut display_scale: ResMut<DisplayScale>,
+    medium: Res<MediumInput>,
+    amplitude: Query<&Amplitude>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+) {
+    if !display_scale.auto {
+        return;
     }
+    let (Ok(amplitude), Ok(wave_length), Ok(frequency)) = (
+        amplitude.get_single(),
+        wave_length.get_single(),
+        frequency.get_single(),
+    ) else {
+        return;
+    };
+    let fitted = display_scale.fitted(
+        wave_length.0 * WAVES_ON_SCREEN,
+        1.0 / medium.frequency(wave_length.0, frequency.0),
+        amplitude.0.get::<meter>(),
+    );
+    display_scale.set_if_neq(fitted);
 }
 
-// /// draws planar function as a sequence o/ }
-
-/// draws planar function as a sequence of vectors
+/// draws planar function as a sequence of vectors, x in m
 pub fn draw_planar_fn_as_vert_vecs<F>(
     gizmos: &mut Gizmos,
-    range_start: i32,
-    range_end: i32,
+    display_scale: &DisplayScale,
+    x_start: f32,
+    x_end: f32,
     color: Srgba,
     function: F,
 ) where
     F: Fn(f32) -> Vec2,
 {
-    let x_scaling = 50.0;
-    let y_scaling = 50.0;
-
-    let mut value = range_start as f32;
-    while value < range_end as f32 {
-        let x = value;
+    for x in samples(x_start, x_end) {
         let vec = function(x);
 
-        let scaled_x = x * x_scaling;
-        let scaled_y = vec.y * y_scaling;
+        let scaled_x = x * display_scale.length;
+        let scaled_y = display_scale.amplitude_to_screen(vec.y);
 
-        println!("x: {}, y: {}", scaled_x, scaled_y);
+        // println!("x: {}, y: {}", scaled_x, scaled_y);
 
         gizmos.line_2d(
             Vec2::new(scaled_x, 0.0),
             Vec2::new(scaled_x, scaled_y),
             color,
         );
-
-        value += 0.1;
     }
 }
+
+/// draws a function as a continuous line, e.g. an envelope, x in m
+pub fn draw_planar_fn_as_line<F>(
+    gizmos: &mut Gizmos,
+    display_scale: &DisplayScale,
+    x_start: f32,
+    x_end: f32,
+    color: Srgba,
+    function: F,
+) where
+    F: Fn(f32) -> f32,
+{
+    gizmos.linestrip_2d(
+        samples(x_start, x_end).map(|x| {
+            Vec2::new(
+                x * display_scale.length,
+                display_scale.amplitude_to_screen(function(x)),
+            )
+        }),
+        color,
+    );
+}
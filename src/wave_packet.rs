@@ -0,0 +1,150 @@
+use bevy::{
+    color::palettes::css::{LIME, RED},
+    prelude::*,
+};
+use uom::si::{
+    f32::{Angle, Frequency, Length, Time},
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+use waves_core::{wave::RawWaveParameters, wave_packet::WavePacketParameters};
+
+use crate::{
+    display_scale::DisplayScale,
+    medium::MediumInput,
+    wave_packet_ui::{
+        listen_toggle_packet_events, setup_wave_packet_ui, toggle_packet_button_handler,
+        update_wave_packet_label, wave_packet_text_listener, TogglePacketEvent,
+    },
+    wave_ui::add_text_input_plugin,
+};
+
+/// color of the marker moving with the phase velocity
+pub const PHASE_MARKER_COLOR: Srgba = RED;
+/// color of the marker moving with the group velocity
+pub const GROUP_MARKER_COLOR: Srgba = LIME;
+
+/// the envelope enters and leaves the screen this many σ beyond its edges
+const ENVELOPE_MARGIN: f32 = 3.0;
+
+/// draws a gaussian packet around the wave entered in the form instead of the infinite sinusoid
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct WavePacketInput {
+    pub enabled: bool,
+    /// σ, in wave lengths of the carrier
+    pub envelope_width: f32,
+    /// v_g / v_p, 1 for a non dispersive medium
+    pub group_velocity_ratio: f32,
+}
+
+impl WavePacketInput {
+    pub fn new(group_velocity_ratio: f32) -> Self {
+        WavePacketInput {
+            enabled: false,
+            envelope_width: 1.0,
+            group_velocity_ratio,
+        }
+    }
+
+    /// in a medium, its dispersion replaces the entered frequency and group velocity ratio
+    pub fn to_parameters(
+        self,
+        amplitude: f32,
+        wave_length: Length,
+        frequency: Frequency,
+        phase: Angle,
+        medium: &MediumInput,
+    ) -> WavePacketParameters {
+        let frequency = medium.frequency(wave_length, frequency);
+        let group_velocity = medium
+            .0
+            .and_then(|medium| medium.group_velocity(wave_length))
+            .unwrap_or(frequency * wave_length * self.group_velocity_ratio);
+        WavePacketParameters {
+            amplitude,
+            wave_length,
+            frequency,
+            phase,
+            envelope_width: wave_length * self.envelope_width,
+            group_velocity,
+            medium: medium.0,
+        }
+    }
+}
+
+/// adds the packet panel, the scene's draw system checks WavePacketInput::enabled
+pub fn add_wave_packet_system(app: &mut App, input: WavePacketInput) {
+    add_text_input_plugin(app);
+    app.add_event::<TogglePacketEvent>()
+        .insert_resource(input)
+        .add_systems(Startup, setup_wave_packet_ui)
+        .add_systems(
+            Update,
+            (
+                toggle_packet_button_handler,
+                listen_toggle_packet_events,
+                wave_packet_text_listener,
+                update_wave_packet_label,
+            ),
+        );
+}
+
+/// the packet as drawn: it enters at one edge of the screen and starts over once its envelope has
+/// left at the other edge, otherwise it'd be gone after a few periods
+pub struct PacketOnScreen {
+    pub packet: WavePacketParameters,
+    pub components: Vec<RawWaveParameters>,
+    /// time since the packet started at the edge
+    pub t: Time,
+    /// position of the envelope's center at t=0, in m
+    pub x_offset: f32,
+}
+
+impl PacketOnScreen {
+    pub fn new(
+        packet: WavePacketParameters,
+        display_scale: &DisplayScale,
+        animation_seconds: f32,
+    ) -> Self {
+        let t = display_scale.physical_time(animation_seconds);
+        let (x_start, x_end) = display_scale.x_range();
+        let margin = ENVELOPE_MARGIN * packet.envelope_width.get::<meter>();
+        let group_velocity = packet.group_velocity.get::<meter_per_second>();
+
+        let (t, x_offset) = if group_velocity.abs() < f32::EPSILON {
+            (t, 0.0)
+        } else {
+            let crossing = (x_end - x_start + 2.0 * margin) / group_velocity.abs();
+            let x_offset = if group_velocity > 0.0 {
+                x_start - margin
+            } else {
+                x_end + margin
+            };
+            let t = Time::new::<second>(t.get::<second>().rem_euclid(crossing));
+            (t, x_offset)
+        };
+
+        PacketOnScreen {
+            packet,
+            components: packet.components(),
+            t,
+            x_offset,
+        }
+    }
+
+    /// position relative to the packet's origin
+    pub fn local_x(&self, x: f32) -> Length {
+        Length::new::<meter>(x - self.x_offset)
+    }
+
+    /// m
+    pub fn group_x(&self) -> f32 {
+        self.packet.group_position(self.t).get::<meter>() + self.x_offset
+    }
+
+    /// m
+    pub fn phase_x(&self) -> f32 {
+        self.packet.phase_position(self.t).get::<meter>() + self.x_offset
+    }
+}
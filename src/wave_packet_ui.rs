@@ -0,0 +1,178 @@
+use bevy::{
+    color::palettes::css::{BLACK, GREEN},
+    prelude::*,
+};
+use bevy_simple_text_input::TextInputSubmitEvent;
+use uom::si::{f32::Angle, velocity::meter_per_second};
+
+use crate::{
+    medium::MediumInput,
+    wave_packet::WavePacketInput,
+    wave_ui::{add_button, generate_input_box, generate_label, parse_float, Freq, WaveLength},
+};
+
+#[derive(Resource)]
+pub struct WavePacketUiInputEntities {
+    pub envelope_width: Entity,
+    pub group_velocity_ratio: Entity,
+}
+
+#[derive(Component, Default)]
+pub struct TogglePacketMarker;
+#[derive(Component, Default)]
+pub struct EnvelopeWidthInputMarker;
+#[derive(Component, Default)]
+pub struct GroupVelocityRatioInputMarker;
+#[derive(Component, Default)]
+pub struct WavePacketInfoMarker;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TogglePacketEvent;
+
+/// adds the wave packet controls, bottom, next to the playback panel
+pub fn setup_wave_packet_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input: Res<WavePacketInput>,
+) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    let root = commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            bottom: Val::Px(0.0),
+            left: Val::Px(530.0),
+            width: Val::Px(210.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::BLACK),
+        ..default()
+    });
+
+    let root_id = root.id();
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Wave packet on/off",
+        TogglePacketMarker,
+    );
+
+    let envelope_width_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Envelope σ (λ)",
+        EnvelopeWidthInputMarker,
+        input.envelope_width.to_string(),
+    );
+    let group_velocity_ratio_input = generate_input_box(
+        &font,
+        root_id,
+        &mut commands,
+        "Group / phase velocity",
+        GroupVelocityRatioInputMarker,
+        input.group_velocity_ratio.to_string(),
+    );
+
+    let info_label = generate_label(&font, "");
+    let spawned_info_label = commands.spawn((WavePacketInfoMarker, info_label)).id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_info_label]);
+
+    commands.insert_resource(WavePacketUiInputEntities {
+        envelope_width: envelope_width_input,
+        group_velocity_ratio: group_velocity_ratio_input,
+    });
+}
+
+pub fn wave_packet_text_listener(
+    mut events: EventReader<TextInputSubmitEvent>,
+    input_entities: Res<WavePacketUiInputEntities>,
+    mut input: ResMut<WavePacketInput>,
+) {
+    for event in events.read() {
+        if event.entity == input_entities.envelope_width {
+            match parse_float(&event.value) {
+                Ok(f) if f > 0.0 => input.envelope_width = f,
+                Ok(f) => println!("error: envelope width must be positive: {}", f),
+                Err(err) => println!("error: {}", err),
+            }
+        } else if event.entity == input_entities.group_velocity_ratio {
+            match parse_float(&event.value) {
+                Ok(f) => input.group_velocity_ratio = f,
+                Err(err) => println!("error: {}", err),
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn toggle_packet_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<TogglePacketMarker>),
+    >,
+    mut my_events: EventWriter<TogglePacketEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = GREEN.into();
+                border_color.0 = GREEN.into();
+                my_events.send(TogglePacketEvent);
+            }
+            Interaction::Hovered => {}
+            Interaction::None => {
+                *color = BLACK.into();
+                border_color.0 = BLACK.into();
+            }
+        }
+    }
+}
+
+pub fn listen_toggle_packet_events(
+    mut events: EventReader<TogglePacketEvent>,
+    mut input: ResMut<WavePacketInput>,
+) {
+    for _ in events.read() {
+        input.enabled = !input.enabled;
+    }
+}
+
+/// phase and group velocity of the packet built from the carrier entered in the form
+pub fn update_wave_packet_label(
+    input: Res<WavePacketInput>,
+    medium: Res<MediumInput>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+    mut label_query: Query<&mut Text, With<WavePacketInfoMarker>>,
+) {
+    let (Ok(wave_length), Ok(frequency)) = (wave_length.get_single(), frequency.get_single())
+    else {
+        return;
+    };
+    let packet = input.to_parameters(1.0, wave_length.0, frequency.0, Angle::default(), &medium);
+    let text = if input.enabled {
+        format!(
+            "phase velocity (red): {:e} m/s\ngroup velocity (lime): {:e} m/s{}",
+            packet.phase_velocity().get::<meter_per_second>(),
+            packet.group_velocity.get::<meter_per_second>(),
+            if medium.0.is_some() {
+                "\nfrom the medium"
+            } else {
+                ""
+            }
+        )
+    } else {
+        "off".to_owned()
+    };
+    for mut label in &mut label_query {
+        if label.sections[0].value != text {
+            label.sections[0].value = text.clone();
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use std::f32::consts::PI;
+
 use bevy::{
-    color::palettes::css::{BLUE, GRAY, RED, WHITE},
+    color::palettes::css::{BLACK, BLUE, GRAY, GREEN, RED, WHITE},
+    ecs::query::QuerySingleError,
     prelude::*,
 };
 use bevy_simple_text_input::{
-    TextInputBundle, TextInputInactive, TextInputSettings, TextInputSubmitEvent, TextInputValue,
+    TextInputBundle, TextInputInactive, TextInputPlugin, TextInputSettings, TextInputSubmitEvent,
+    TextInputSystem, TextInputValue,
 };
 use uom::si::{
     angle::radian,
@@ -12,6 +16,19 @@ use uom::si::{
     length::meter,
 };
 
+use crate::{
+    attenuation::AttenuationInput,
+    attenuation_ui::add_attenuation_inputs,
+    slider::{generate_slider_input_box, SliderRange},
+    wave::WaveUserParameters,
+};
+
+/// what the sliders of the mechanical wave span
+const AMPLITUDE_RANGE: SliderRange = SliderRange::linear(0.0, 5.0, 0.01);
+const WAVE_LENGTH_RANGE: SliderRange = SliderRange::log(0.1, 100.0, 0.01);
+const FREQUENCY_RANGE: SliderRange = SliderRange::log(0.01, 10.0, 0.01);
+pub const PHASE_RANGE: SliderRange = SliderRange::linear(-PI, PI, 0.01);
+
 #[derive(Resource)]
 pub struct UiInputs {
     pub amplitude: String,
@@ -48,11 +65,33 @@ pub struct FrequencyInputMarker;
 pub struct PhaseMarker;
 #[derive(Component, Default)]
 pub struct WarningMarker;
+#[derive(Component, Default)]
+pub struct AddComponentWaveMarker;
+#[derive(Component, Default)]
+pub struct RemoveComponentWaveMarker;
+#[derive(Component, Default)]
+pub struct ClearComponentWavesMarker;
+#[derive(Component, Default)]
+pub struct ComponentWavesLabelMarker;
+
+/// waves added by the user to be superposed
+/// the wave currently entered in the form is only a preview until it's added
+#[derive(Resource, Default, Debug)]
+pub struct ComponentWaves(pub Vec<WaveUserParameters>);
+
+#[derive(Event, Debug, Clone, Copy)]
+pub enum ComponentWavesEvent {
+    /// adds the wave currently entered in the form
+    Add,
+    RemoveLast,
+    Clear,
+}
 
 pub fn setup_wave_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     form_state: Res<UiInputs>,
+    attenuation: Res<AttenuationInput>,
 ) {
     let font = asset_server.load("fonts/FiraMono-Medium.ttf");
 
@@ -72,41 +111,78 @@ pub fn setup_wave_ui(
 
     let root_id = root.id();
 
-    let amplitude_input = generate_input_box(
+    let amplitude_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Amplitude (m)",
         AmplitudeInputMarker,
         form_state.amplitude.clone(),
+        AMPLITUDE_RANGE,
     );
-    let wave_length_input = generate_input_box(
+    add_attenuation_inputs(&mut commands, root_id, &font, &attenuation);
+    let wave_length_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Wave length (m)",
         WaveLengthInputMarker,
         form_state.wave_length.clone(),
+        WAVE_LENGTH_RANGE,
     );
-    let frequency_input = generate_input_box(
+    let frequency_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Frequency (hz)",
         FrequencyInputMarker,
         form_state.frequency.clone(),
+        FREQUENCY_RANGE,
     );
-    let phase_input = generate_input_box(
+    let phase_input = generate_slider_input_box(
         &font,
         root_id,
         &mut commands,
         "Phase (rad)",
         PhaseMarker,
         form_state.phase.clone(),
+        PHASE_RANGE,
     );
 
     add_warning_label(&mut commands, root_id, &font);
 
+    add_label(&mut commands, root_id, &font, "Superposition");
+
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Add wave",
+        AddComponentWaveMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Remove last",
+        RemoveComponentWaveMarker,
+    );
+    add_button(
+        &mut commands,
+        root_id,
+        &font,
+        "Clear",
+        ClearComponentWavesMarker,
+    );
+
+    let component_waves_label = generate_label(&font, "");
+    let spawned_component_waves_label = commands
+        .spawn((ComponentWavesLabelMarker, component_waves_label))
+        .id();
+    commands
+        .entity(root_id)
+        .push_children(&[spawned_component_waves_label]);
+
     commands.insert_resource(UiInputEntities {
         amplitude: amplitude_input,
         wave_length: wave_length_input,
@@ -310,21 +386,14 @@ pub fn text_listener(
     input_entities: Res<UiInputEntities>,
 ) {
     for event in events.read() {
-        println!("read an event");
         if event.entity == input_entities.amplitude {
-            info!("submitted amplitude: {}", event.value);
             inputs.amplitude = event.value.clone();
         } else if event.entity == input_entities.wave_length {
-            println!("submitted wave length: {:?}", event.entity);
             inputs.wave_length = event.value.clone();
         } else if event.entity == input_entities.frequency {
-            println!("submitted frequency: {:?}", event.entity);
             inputs.frequency = event.value.clone();
         } else if event.entity == input_entities.phase {
-            println!("submitted phase: {:?}", event.entity);
             inputs.phase = event.value.clone();
-        } else {
-            println!("unknown entity: {:?}", event.entity);
         }
     }
 }
@@ -388,6 +457,131 @@ pub fn listen_wave_ui_inputs(
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub fn add_component_wave_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<AddComponentWaveMarker>),
+    >,
+    mut my_events: EventWriter<ComponentWavesEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        component_waves_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ComponentWavesEvent::Add,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn remove_component_wave_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<RemoveComponentWaveMarker>),
+    >,
+    mut my_events: EventWriter<ComponentWavesEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        component_waves_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ComponentWavesEvent::RemoveLast,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn clear_component_waves_button_handler(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut BorderColor),
+        (Changed<Interaction>, With<ClearComponentWavesMarker>),
+    >,
+    mut my_events: EventWriter<ComponentWavesEvent>,
+) {
+    for (interaction, mut color, mut border_color) in &mut interaction_query {
+        component_waves_button_handler(
+            (interaction, &mut color, &mut border_color),
+            &mut my_events,
+            ComponentWavesEvent::Clear,
+        );
+    }
+}
+
+fn component_waves_button_handler(
+    interaction: (&Interaction, &mut BackgroundColor, &mut BorderColor),
+    my_events: &mut EventWriter<ComponentWavesEvent>,
+    event: ComponentWavesEvent,
+) {
+    let (interaction, color, border_color) = interaction;
+    match *interaction {
+        Interaction::Pressed => {
+            *color = GREEN.into();
+            border_color.0 = GREEN.into();
+            my_events.send(event);
+        }
+        Interaction::Hovered => {}
+        Interaction::None => {
+            *color = BLACK.into();
+            border_color.0 = BLACK.into();
+        }
+    }
+}
+
+/// processes the superposition button events
+pub fn listen_component_waves_ui_inputs(
+    mut events: EventReader<ComponentWavesEvent>,
+    mut component_waves: ResMut<ComponentWaves>,
+    amplitude: Query<&Amplitude>,
+    wave_length: Query<&WaveLength>,
+    frequency: Query<&Freq>,
+    phase: Query<&Phase>,
+) -> Result<(), QuerySingleError> {
+    for event in events.read() {
+        match event {
+            ComponentWavesEvent::Add => component_waves.0.push(WaveUserParameters {
+                amplitude: *amplitude.get_single()?,
+                wave_length: *wave_length.get_single()?,
+                frequency: *frequency.get_single()?,
+                phase: *phase.get_single()?,
+            }),
+            ComponentWavesEvent::RemoveLast => {
+                component_waves.0.pop();
+            }
+            ComponentWavesEvent::Clear => component_waves.0.clear(),
+        }
+    }
+    Ok(())
+}
+
+/// lists the added waves under the superposition buttons
+pub fn update_component_waves_label(
+    component_waves: Res<ComponentWaves>,
+    mut label_query: Query<&mut Text, With<ComponentWavesLabelMarker>>,
+) {
+    if !component_waves.is_changed() {
+        return;
+    }
+    for mut label in &mut label_query {
+        label.sections[0].value = component_waves
+            .0
+            .iter()
+            .enumerate()
+            .map(|(index, wave)| {
+                format!(
+                    "{}: A {} λ {} f {} φ {}",
+                    index + 1,
+                    wave.amplitude.0.get::<meter>(),
+                    wave.wave_length.0.get::<meter>(),
+                    wave.frequency.0.get::<hertz>(),
+                    wave.phase.0.get::<radian>(),
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+    }
+}
+
 pub fn parse_float(str: &str) -> Result<f32, String> {
     let f = str.parse::<f32>();
     match f {
@@ -405,6 +599,14 @@ where
     }
 }
 
+/// for ui that can be added to any scene, scenes with inputs already add the plugin themselves
+pub fn add_text_input_plugin(app: &mut App) {
+    if !app.is_plugin_added::<TextInputPlugin>() {
+        app.add_plugins(TextInputPlugin)
+            .add_systems(Update, focus.before(TextInputSystem));
+    }
+}
+
 pub fn focus(
     query: Query<(Entity, &Interaction), Changed<Interaction>>,
     mut text_input_query: Query<(Entity, &mut TextInputInactive, &mut BorderColor)>,
@@ -0,0 +1,93 @@
+use std::f32::consts::PI;
+
+use uom::si::{
+    electric_permittivity::farad_per_meter,
+    electrical_conductivity::siemens_per_meter,
+    f32::{ElectricalConductivity, Frequency, Length, ReciprocalLength, Time},
+    frequency::hertz,
+    length::meter,
+    magnetic_permeability::henry_per_meter,
+    reciprocal_length::reciprocal_meter,
+    time::second,
+};
+
+use crate::poynting::{VACUUM_PERMEABILITY, VACUUM_PERMITTIVITY};
+
+/// exponential decay of a wave: in space when it travels through an absorbing medium, in time when
+/// its source is damped
+/// u(x,t) = e^(−αx)e^(−γt)·Acos(kx−ωt+φ), the absorbing medium starts at x=0
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Attenuation {
+    /// α
+    pub alpha: ReciprocalLength,
+    /// γ, 1/s
+    pub gamma: Frequency,
+}
+
+impl Attenuation {
+    /// factor the amplitude is multiplied with, x < 0 is outside the absorbing medium
+    /// the damping starts at t = 0, before that the amplitude stays at its initial value, so a
+    /// reversed or scrubbed clock doesn't blow it up
+    pub fn factor(&self, x: Length, t: Time) -> f32 {
+        let in_medium = x.max(Length::new::<meter>(0.0));
+        let damped = t.max(Time::new::<second>(0.0));
+        (-(self.alpha * in_medium).value - (self.gamma * damped).value).exp()
+    }
+
+    /// δ = 1/α, distance after which the amplitude dropped to 1/e
+    /// None if the medium doesn't absorb
+    pub fn skin_depth(&self) -> Option<Length> {
+        let alpha = self.alpha.get::<reciprocal_meter>();
+        (alpha > 0.0).then(|| Length::new::<meter>(1.0 / alpha))
+    }
+}
+
+/// α of a medium with conductivity σ and real relative permittivity εᵣ (μᵣ = 1), i.e. with the
+/// complex permittivity ε = εᵣε₀ − iσ/ω
+/// α = ω√(με/2)·√(√(1 + (σ/ωε)²) − 1), for good conductors about √(ωμσ/2)
+/// see e.g. Griffiths, Introduction to Electrodynamics, section 9.4.1
+pub fn calculate_attenuation_coefficient(
+    frequency: Frequency,
+    conductivity: ElectricalConductivity,
+    relative_permittivity: f32,
+) -> ReciprocalLength {
+    let omega = 2.0 * PI * frequency.get::<hertz>();
+    let sigma = conductivity.get::<siemens_per_meter>();
+    if omega <= 0.0 || sigma <= 0.0 {
+        return ReciprocalLength::new::<reciprocal_meter>(0.0);
+    }
+    let mu = VACUUM_PERMEABILITY.get::<henry_per_meter>();
+    let epsilon = relative_permittivity * VACUUM_PERMITTIVITY.get::<farad_per_meter>();
+
+    let loss_tangent = sigma / (omega * epsilon);
+    // √(1 + x²) − 1, written so it doesn't cancel out for small x
+    let root = (1.0 + loss_tangent * loss_tangent).sqrt();
+    let difference = loss_tangent * loss_tangent / (root + 1.0);
+
+    ReciprocalLength::new::<reciprocal_meter>(
+        omega * (mu * epsilon / 2.0).sqrt() * difference.sqrt(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::frequency::hertz;
+
+    use super::*;
+
+    #[test]
+    fn damping_starts_at_zero() {
+        let attenuation = Attenuation {
+            alpha: ReciprocalLength::new::<reciprocal_meter>(0.5),
+            gamma: Frequency::new::<hertz>(2.0),
+        };
+        let origin = Length::new::<meter>(0.0);
+        assert_eq!(attenuation.factor(origin, Time::new::<second>(-5.0)), 1.0);
+        assert_eq!(
+            attenuation.factor(Length::new::<meter>(-3.0), Time::new::<second>(0.0)),
+            1.0
+        );
+        let factor = attenuation.factor(Length::new::<meter>(2.0), Time::new::<second>(1.0));
+        assert!((factor - (-3.0f32).exp()).abs() < 1e-6);
+    }
+}
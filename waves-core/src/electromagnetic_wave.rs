@@ -0,0 +1,413 @@
+use std::f32::consts::PI;
+
+use glam::Vec3;
+use once_cell::sync::Lazy;
+use uom::si::{
+    angle::radian,
+    electric_field::volt_per_meter,
+    f32::{Angle, ElectricField, Frequency, Length, MagneticFluxDensity, Time, Velocity},
+    magnetic_flux_density::tesla,
+    velocity::meter_per_second,
+};
+
+use crate::wave::{
+    calculate_u_raw, calculate_u_scalar_raw, LengthVec3, PropagationDirection, RawWaveParameters,
+    SphericalDirection,
+};
+
+pub static SPEED_OF_LIGHT: Lazy<Velocity> =
+    Lazy::new(|| Velocity::new::<meter_per_second>(299_792_458.0));
+
+/// parameters of an electromagnetic wave, the amplitude is the one of the electric field
+#[derive(Debug, Clone, Copy)]
+pub struct ElectromagneticWaveParameters {
+    pub amplitude: ElectricField,
+    pub wave_length: Length,
+    pub frequency: Frequency,
+    pub phase: Angle,
+}
+
+impl From<ElectromagneticWaveParameters> for RawWaveParameters {
+    fn from(p: ElectromagneticWaveParameters) -> Self {
+        RawWaveParameters {
+            amplitude: p.amplitude.get::<volt_per_meter>(),
+            wave_length: p.wave_length,
+            frequency: p.frequency,
+            phase: p.phase,
+            direction: PropagationDirection::Forward,
+        }
+    }
+}
+
+/// frequency for c=fλ
+pub fn calculate_frequency(wave_length: Length) -> Frequency {
+    *SPEED_OF_LIGHT / wave_length
+}
+
+/// wave length for c=fλ
+pub fn calculate_wave_length(frequency: Frequency) -> Length {
+    *SPEED_OF_LIGHT / frequency
+}
+
+/// calculates wave function for a planar polarity wave
+pub fn calculate_u_planar(
+    x: Length,
+    t: Time,
+    p: &ElectromagneticWaveParameters,
+    direction: Vec3,
+) -> ElectricFieldVec3 {
+    let raw = calculate_u_raw(x, t, &(*p).into(), direction);
+    // assumption: raw amplitude passed to calculate_u_raw (RawWaveParameters) was in volt_per_meter
+    ElectricFieldVec3 {
+        x: ElectricField::new::<volt_per_meter>(raw.x),
+        y: ElectricField::new::<volt_per_meter>(raw.y),
+        z: ElectricField::new::<volt_per_meter>(raw.z),
+    }
+}
+
+/// calculates wave function for a circular polarity wave
+/// u(x, y) = A(cos(kx - wt)y ∓ sin (kx - wt)z)
+/// see e.g. https://web.mit.edu/sahughes/www/8.022/lec21.pdf section 21.5
+pub fn calculate_u_circular(
+    x: Length,
+    t: Time,
+    p: &ElectromagneticWaveParameters,
+    unit_vector1: Vec3,
+    unit_vector2: Vec3,
+    handedness: Handedness,
+) -> ElectricFieldVec3 {
+    calculate_u_polarized(
+        x,
+        t,
+        p,
+        &JonesVector::circular(handedness),
+        unit_vector1,
+        unit_vector2,
+    )
+}
+
+/// calculates wave function for an arbitrary (elliptical) polarity
+/// u(x, t) = A(J₁cos(kx - wt)e₁ + J₂cos(kx - wt + δ)e₂)
+/// planar and circular polarity are special cases, see [`JonesVector::planar`] and [`JonesVector::circular`]
+pub fn calculate_u_polarized(
+    x: Length,
+    t: Time,
+    p: &ElectromagneticWaveParameters,
+    jones: &JonesVector,
+    unit_vector1: Vec3,
+    unit_vector2: Vec3,
+) -> ElectricFieldVec3 {
+    let scalar = calculate_u_scalar_raw(x, t, &(*p).into());
+    let delta = jones.phase_difference.get::<radian>();
+
+    let sub =
+        unit_vector1 * (jones.y * scalar.cos()) + unit_vector2 * (jones.z * (scalar + delta).cos());
+
+    ElectricFieldVec3 {
+        x: p.amplitude * sub.x,
+        y: p.amplitude * sub.y,
+        z: p.amplitude * sub.z,
+    }
+}
+
+/// like [`calculate_u_polarized`] for a wave travelling in any direction, E is always
+/// perpendicular to k̂ because the jones vector is relative to the transverse unit vectors, see
+/// [`SphericalDirection::transverse_unit_vectors`]
+pub fn calculate_u_polarized_3d(
+    position: &LengthVec3,
+    t: Time,
+    p: &ElectromagneticWaveParameters,
+    jones: &JonesVector,
+    direction: &SphericalDirection,
+) -> ElectricFieldVec3 {
+    let (unit_vector1, unit_vector2) = direction.transverse_unit_vectors();
+    calculate_u_polarized(
+        position.dot(direction.unit_vector()),
+        t,
+        p,
+        jones,
+        unit_vector1,
+        unit_vector2,
+    )
+}
+
+/// sense of rotation of the electric field
+/// right: counterclockwise around the propagation direction (right hand rule with thumb along k),
+/// i.e. clockwise seen from the source, as in the IEEE convention
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Handedness {
+    #[default]
+    Right,
+    Left,
+}
+
+/// polarization state of a wave travelling along x, as Jones vector (J₁, J₂e^{iδ})
+/// components are relative to the wave amplitude, i.e. E = A Re{(J₁e₁ + J₂e^{iδ}e₂)e^{i(kx−ωt+φ)}}
+#[derive(Debug, Clone, Copy)]
+pub struct JonesVector {
+    /// relative amplitude along the first transverse axis (y)
+    pub y: f32,
+    /// relative amplitude along the second transverse axis (z)
+    pub z: f32,
+    /// phase of the z component relative to the y component, positive is right handed
+    pub phase_difference: Angle,
+}
+
+impl JonesVector {
+    /// linear polarization along z
+    pub fn planar() -> Self {
+        JonesVector {
+            y: 0.0,
+            z: 1.0,
+            phase_difference: Angle::new::<radian>(0.0),
+        }
+    }
+
+    /// equal amplitudes, 90° apart
+    pub fn circular(handedness: Handedness) -> Self {
+        Self::elliptical(1.0, 1.0, Angle::new::<radian>(PI / 2.0), handedness)
+    }
+
+    /// only the magnitude of phase_difference is used, the sign is determined by the handedness
+    pub fn elliptical(y: f32, z: f32, phase_difference: Angle, handedness: Handedness) -> Self {
+        let magnitude = phase_difference.get::<radian>().abs();
+        let sign = match handedness {
+            Handedness::Right => 1.0,
+            Handedness::Left => -1.0,
+        };
+        JonesVector {
+            y,
+            z,
+            phase_difference: Angle::new::<radian>(sign * magnitude),
+        }
+    }
+
+    /// None for linear polarization
+    pub fn handedness(&self) -> Option<Handedness> {
+        let sin_delta = self.phase_difference.get::<radian>().sin();
+        if self.y == 0.0 || self.z == 0.0 || sin_delta.abs() < f32::EPSILON {
+            None
+        } else if sin_delta > 0.0 {
+            Some(Handedness::Right)
+        } else {
+            Some(Handedness::Left)
+        }
+    }
+
+    /// ellipse traced by the electric field in the transverse plane
+    /// see e.g. Born & Wolf, Principles of Optics, section 1.4.2
+    pub fn ellipse(&self) -> PolarizationEllipse {
+        let (a, b) = (self.y, self.z);
+        let delta = self.phase_difference.get::<radian>();
+
+        let sum = a * a + b * b;
+        let orientation = 0.5 * (2.0 * a * b * delta.cos()).atan2(a * a - b * b);
+        let ellipticity_angle = if sum == 0.0 {
+            0.0
+        } else {
+            0.5 * (2.0 * a * b * delta.sin() / sum).clamp(-1.0, 1.0).asin()
+        };
+
+        // semi axes from a² + b² = M² + m² and Mm = |ab sin δ|
+        let product = a * b * delta.sin();
+        let semi_major_squared =
+            (sum + (sum * sum - 4.0 * product * product).max(0.0).sqrt()) / 2.0;
+        let semi_minor_squared = (sum - semi_major_squared).max(0.0);
+
+        PolarizationEllipse {
+            orientation: Angle::new::<radian>(orientation),
+            ellipticity_angle: Angle::new::<radian>(ellipticity_angle),
+            semi_major: semi_major_squared.sqrt(),
+            semi_minor: semi_minor_squared.sqrt(),
+        }
+    }
+}
+
+/// shape of the polarization ellipse, axes relative to the wave amplitude
+#[derive(Debug, Clone, Copy)]
+pub struct PolarizationEllipse {
+    /// angle of the major axis, measured from the first transverse axis (y) towards the second (z)
+    pub orientation: Angle,
+    /// χ with tan χ = ±minor/major, positive for right handed
+    pub ellipticity_angle: Angle,
+    pub semi_major: f32,
+    pub semi_minor: f32,
+}
+
+impl PolarizationEllipse {
+    /// minor / major, 0 for linear and 1 for circular polarization
+    pub fn ellipticity(&self) -> f32 {
+        if self.semi_major == 0.0 {
+            0.0
+        } else {
+            self.semi_minor / self.semi_major
+        }
+    }
+}
+
+/// 3d vector with domain specific units
+#[derive(Debug, Clone, Copy)]
+pub struct ElectricFieldVec3 {
+    pub x: ElectricField,
+    pub y: ElectricField,
+    pub z: ElectricField,
+}
+
+/// f32 vec
+impl ElectricFieldVec3 {
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(
+            self.x.get::<volt_per_meter>(),
+            self.y.get::<volt_per_meter>(),
+            self.z.get::<volt_per_meter>(),
+        )
+    }
+
+    /// e.g. by the factor of an [`crate::attenuation::Attenuation`]
+    pub fn scaled(&self, factor: f32) -> ElectricFieldVec3 {
+        ElectricFieldVec3 {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+}
+
+/// magnetic field of a plane wave in vacuum, B = (k̂ × E) / c
+pub fn calculate_magnetic_field(
+    electric: &ElectricFieldVec3,
+    propagation_direction: Vec3,
+) -> MagneticFluxDensityVec3 {
+    let k = propagation_direction.normalize();
+    let c = *SPEED_OF_LIGHT;
+    MagneticFluxDensityVec3 {
+        x: (electric.z * k.y - electric.y * k.z) / c,
+        y: (electric.x * k.z - electric.z * k.x) / c,
+        z: (electric.y * k.x - electric.x * k.y) / c,
+    }
+}
+
+/// 3d vector with domain specific units
+#[derive(Debug, Clone, Copy)]
+pub struct MagneticFluxDensityVec3 {
+    pub x: MagneticFluxDensity,
+    pub y: MagneticFluxDensity,
+    pub z: MagneticFluxDensity,
+}
+
+/// f32 vec
+impl MagneticFluxDensityVec3 {
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(
+            self.x.get::<tesla>(),
+            self.y.get::<tesla>(),
+            self.z.get::<tesla>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::{frequency::hertz, length::meter, time::second};
+
+    use super::*;
+
+    fn parameters() -> ElectromagneticWaveParameters {
+        ElectromagneticWaveParameters {
+            amplitude: ElectricField::new::<volt_per_meter>(2.0),
+            wave_length: Length::new::<meter>(1.0),
+            frequency: Frequency::new::<hertz>(1.0),
+            phase: Angle::new::<radian>(0.0),
+        }
+    }
+
+    fn field(jones: &JonesVector, t: f32) -> Vec3 {
+        let x = Length::new::<meter>(0.0);
+        let t = Time::new::<second>(t);
+        calculate_u_polarized(x, t, &parameters(), jones, Vec3::Y, Vec3::Z).to_vec3()
+    }
+
+    #[test]
+    fn field_rotates_with_the_handedness() {
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let jones = JonesVector::elliptical(1.0, 0.5, Angle::new::<radian>(1.0), handedness);
+            assert_eq!(jones.handedness(), Some(handedness));
+            // right handed: counterclockwise around k̂ = +x at a fixed position
+            let turn = field(&jones, 0.0).cross(field(&jones, 0.01)).x;
+            match handedness {
+                Handedness::Right => assert!(turn > 0.0),
+                Handedness::Left => assert!(turn < 0.0),
+            }
+        }
+        assert_eq!(JonesVector::planar().handedness(), None);
+        let linear = JonesVector::elliptical(1.0, 1.0, Angle::new::<radian>(0.0), Handedness::Left);
+        assert_eq!(linear.handedness(), None);
+    }
+
+    #[test]
+    fn ellipse_axes() {
+        let circular = JonesVector::circular(Handedness::Right).ellipse();
+        assert!((circular.ellipticity() - 1.0).abs() < 1e-5);
+        assert!((circular.ellipticity_angle.get::<radian>() - PI / 4.0).abs() < 1e-5);
+
+        let planar = JonesVector::planar().ellipse();
+        assert!(planar.ellipticity().abs() < 1e-5);
+        assert!((planar.semi_major - 1.0).abs() < 1e-5);
+        assert!((planar.orientation.get::<radian>().abs() - PI / 2.0).abs() < 1e-5);
+
+        // δ = 90° with unequal amplitudes: the axes are along y and z
+        let jones =
+            JonesVector::elliptical(1.0, 0.5, Angle::new::<radian>(PI / 2.0), Handedness::Left);
+        let ellipse = jones.ellipse();
+        assert!((ellipse.semi_major - 1.0).abs() < 1e-5);
+        assert!((ellipse.semi_minor - 0.5).abs() < 1e-5);
+        assert!(ellipse.orientation.get::<radian>().abs() < 1e-5);
+        assert!(ellipse.ellipticity_angle.get::<radian>() < 0.0);
+    }
+
+    #[test]
+    fn field_stays_on_the_ellipse() {
+        let jones = JonesVector::elliptical(0.8, 0.6, Angle::new::<radian>(0.7), Handedness::Right);
+        let ellipse = jones.ellipse();
+        let amplitude = parameters().amplitude.get::<volt_per_meter>();
+        let (major, minor) = (0..100)
+            .map(|i| field(&jones, i as f32 / 100.0).length() / amplitude)
+            .fold((0.0f32, f32::MAX), |(max, min), r| (max.max(r), min.min(r)));
+        assert!((major - ellipse.semi_major).abs() < 1e-3);
+        assert!((minor - ellipse.semi_minor).abs() < 1e-3);
+    }
+
+    #[test]
+    fn magnetic_field_is_k_cross_e_over_c() {
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        let direction = SphericalDirection {
+            theta: Angle::new::<radian>(1.0),
+            phi: Angle::new::<radian>(2.0),
+        };
+        let k = direction.unit_vector();
+        let position = LengthVec3::from_meters(Vec3::new(0.3, -0.2, 0.7));
+        let jones = JonesVector::circular(Handedness::Left);
+        let electric = calculate_u_polarized_3d(
+            &position,
+            Time::new::<second>(0.1),
+            &parameters(),
+            &jones,
+            &direction,
+        );
+        let magnetic = calculate_magnetic_field(&electric, k * 3.0).to_vec3();
+        let e = electric.to_vec3();
+        assert!((magnetic - k.cross(e) / c).length() < 1e-12);
+        assert!((magnetic.length() - e.length() / c).abs() < 1e-12);
+        assert!(magnetic.dot(e).abs() < 1e-12);
+        assert!(magnetic.dot(k).abs() < 1e-12);
+
+        // E along z travelling along +x: B along −y
+        let electric = ElectricFieldVec3 {
+            x: ElectricField::new::<volt_per_meter>(0.0),
+            y: ElectricField::new::<volt_per_meter>(0.0),
+            z: ElectricField::new::<volt_per_meter>(c),
+        };
+        let magnetic = calculate_magnetic_field(&electric, Vec3::X).to_vec3();
+        assert!((magnetic - Vec3::NEG_Y).length() < 1e-6);
+    }
+}
@@ -0,0 +1,496 @@
+use std::{
+    f32::consts::{E, PI},
+    fmt,
+    str::FromStr,
+};
+
+/// arithmetic expression typed by the user, e.g. "sin(2*pi*x) + 0.5*x^2"
+/// variables are resolved to indices when parsing, so evaluating doesn't look up names
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    source: String,
+    node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f32),
+    /// index into the values passed to evaluate
+    Variable(usize),
+    Negate(Box<Node>),
+    Binary(BinaryOperator, Box<Node>, Box<Node>),
+    Function(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Exp,
+    Ln,
+    Log,
+    Sqrt,
+    Abs,
+    Sign,
+    Floor,
+    Ceil,
+    Round,
+    /// fractional part, x − ⌊x⌋, handy for periodic functions
+    Fract,
+    Min,
+    Max,
+    Atan2,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        Some(match name {
+            "sin" => Function::Sin,
+            "cos" => Function::Cos,
+            "tan" => Function::Tan,
+            "asin" => Function::Asin,
+            "acos" => Function::Acos,
+            "atan" => Function::Atan,
+            "sinh" => Function::Sinh,
+            "cosh" => Function::Cosh,
+            "tanh" => Function::Tanh,
+            "exp" => Function::Exp,
+            "ln" => Function::Ln,
+            "log" => Function::Log,
+            "sqrt" => Function::Sqrt,
+            "abs" => Function::Abs,
+            "sign" => Function::Sign,
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            "round" => Function::Round,
+            "fract" => Function::Fract,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "atan2" => Function::Atan2,
+            _ => return None,
+        })
+    }
+
+    fn arguments(&self) -> usize {
+        match self {
+            Function::Min | Function::Max | Function::Atan2 => 2,
+            _ => 1,
+        }
+    }
+
+    fn apply(&self, arguments: &[f32]) -> f32 {
+        let a = arguments[0];
+        match self {
+            Function::Sin => a.sin(),
+            Function::Cos => a.cos(),
+            Function::Tan => a.tan(),
+            Function::Asin => a.asin(),
+            Function::Acos => a.acos(),
+            Function::Atan => a.atan(),
+            Function::Sinh => a.sinh(),
+            Function::Cosh => a.cosh(),
+            Function::Tanh => a.tanh(),
+            Function::Exp => a.exp(),
+            Function::Ln => a.ln(),
+            Function::Log => a.log10(),
+            Function::Sqrt => a.sqrt(),
+            Function::Abs => a.abs(),
+            // 0 at 0, unlike f32::signum
+            Function::Sign => {
+                if a == 0.0 {
+                    0.0
+                } else {
+                    a.signum()
+                }
+            }
+            Function::Floor => a.floor(),
+            Function::Ceil => a.ceil(),
+            Function::Round => a.round(),
+            Function::Fract => a - a.floor(),
+            Function::Min => a.min(arguments[1]),
+            Function::Max => a.max(arguments[1]),
+            Function::Atan2 => a.atan2(arguments[1]),
+        }
+    }
+}
+
+impl Expression {
+    /// parses with the given variable names, their values are passed to evaluate in the same order
+    /// besides the variables there are the constants pi and e, + − * / ^ (right associative),
+    /// parentheses and the functions sin, cos, tan, asin, acos, atan, sinh, cosh, tanh, exp,
+    /// ln, log (base 10), sqrt, abs, sign, floor, ceil, round, fract, min, max and atan2
+    pub fn parse(source: &str, variables: &[&str]) -> Result<Expression, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            variables,
+        };
+        let node = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("Unexpected {}", token));
+        }
+        Ok(Expression {
+            source: source.trim().to_owned(),
+            node,
+        })
+    }
+
+    /// values of the variables, in the order they were passed to parse
+    pub fn evaluate(&self, values: &[f32]) -> f32 {
+        evaluate(&self.node, values)
+    }
+}
+
+/// named constants typed by the user, e.g. "a = 2; k = 2*pi/a", separated by `;`
+/// a value may use the parameters before it, the names must differ from the variables in
+/// `reserved`, the built in constants and the functions
+pub fn parse_parameters(source: &str, reserved: &[&str]) -> Result<Vec<(String, f32)>, String> {
+    let mut parameters: Vec<(String, f32)> = vec![];
+    for assignment in source.split(';').filter(|part| !part.trim().is_empty()) {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected name = value instead of {}", assignment.trim()))?;
+        let name = name.trim();
+        let is_identifier = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(format!("Invalid parameter name {}", name));
+        }
+        if reserved.contains(&name)
+            || name == "pi"
+            || name == "e"
+            || Function::from_name(name).is_some()
+            || parameters.iter().any(|(other, _)| other == name)
+        {
+            return Err(format!("{} is already taken", name));
+        }
+        let names: Vec<&str> = parameters.iter().map(|(name, _)| name.as_str()).collect();
+        let values: Vec<f32> = parameters.iter().map(|(_, value)| *value).collect();
+        let value = Expression::parse(value, &names)
+            .map_err(|e| format!("{}: {}", name, e))?
+            .evaluate(&values);
+        if !value.is_finite() {
+            return Err(format!("{} is not a finite number", name));
+        }
+        parameters.push((name.to_owned(), value));
+    }
+    Ok(parameters)
+}
+
+fn evaluate(node: &Node, values: &[f32]) -> f32 {
+    match node {
+        Node::Number(value) => *value,
+        Node::Variable(index) => values.get(*index).copied().unwrap_or(f32::NAN),
+        Node::Negate(node) => -evaluate(node, values),
+        Node::Binary(operator, left, right) => {
+            let (left, right) = (evaluate(left, values), evaluate(right, values));
+            match operator {
+                BinaryOperator::Add => left + right,
+                BinaryOperator::Subtract => left - right,
+                BinaryOperator::Multiply => left * right,
+                BinaryOperator::Divide => left / right,
+                BinaryOperator::Power => left.powf(right),
+            }
+        }
+        Node::Function(function, arguments) => {
+            let arguments: Vec<f32> = arguments
+                .iter()
+                .map(|node| evaluate(node, values))
+                .collect();
+            function.apply(&arguments)
+        }
+    }
+}
+
+/// the text it was parsed from
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// an expression in x
+impl FromStr for Expression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expression::parse(s, &["x"])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Operator(char),
+    Open,
+    Close,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Identifier(name) => write!(f, "{}", name),
+            Token::Operator(operator) => write!(f, "{}", operator),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            let mut previous = c;
+            while let Some(&(i, c)) = chars.peek() {
+                // exponents like 1e-3, the sign only right after the e
+                let is_exponent_sign =
+                    (c == '-' || c == '+') && (previous == 'e' || previous == 'E');
+                if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || is_exponent_sign {
+                    end = i + c.len_utf8();
+                    previous = c;
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let number = &source[start..end];
+            let value = number
+                .parse::<f32>()
+                .map_err(|_| format!("Invalid number {}", number))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' {
+                    end = i + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Identifier(source[start..end].to_owned()));
+        } else {
+            chars.next();
+            tokens.push(match c {
+                '+' | '-' | '*' | '/' | '^' => Token::Operator(c),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                ',' => Token::Comma,
+                _ => return Err(format!("Unexpected {}", c)),
+            });
+        }
+    }
+    if tokens.is_empty() {
+        return Err("Expression is empty".to_owned());
+    }
+    Ok(tokens)
+}
+
+/// recursive descent, one function per precedence level
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    variables: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if *token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {} instead of {}", expected, token)),
+            None => Err(format!("Expected {} at the end", expected)),
+        }
+    }
+
+    /// sums and differences
+    fn expression(&mut self) -> Result<Node, String> {
+        let mut node = self.term()?;
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek() {
+            let operator = if *operator == '+' {
+                BinaryOperator::Add
+            } else {
+                BinaryOperator::Subtract
+            };
+            self.position += 1;
+            node = Node::Binary(operator, Box::new(node), Box::new(self.term()?));
+        }
+        Ok(node)
+    }
+
+    /// products and quotients
+    fn term(&mut self) -> Result<Node, String> {
+        let mut node = self.unary()?;
+        while let Some(Token::Operator(operator @ ('*' | '/'))) = self.peek() {
+            let operator = if *operator == '*' {
+                BinaryOperator::Multiply
+            } else {
+                BinaryOperator::Divide
+            };
+            self.position += 1;
+            node = Node::Binary(operator, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    /// leading signs, binding weaker than powers, so -x^2 = -(x^2)
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.position += 1;
+                Ok(Node::Negate(Box::new(self.unary()?)))
+            }
+            Some(Token::Operator('+')) => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// a^b^c = a^(b^c)
+    fn power(&mut self) -> Result<Node, String> {
+        let base = self.primary()?;
+        if let Some(Token::Operator('^')) = self.peek() {
+            self.position += 1;
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                BinaryOperator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    /// numbers, constants, variables, function calls and parentheses
+    fn primary(&mut self) -> Result<Node, String> {
+        match self.next().cloned() {
+            Some(Token::Number(value)) => Ok(Node::Number(value)),
+            Some(Token::Open) => {
+                let node = self.expression()?;
+                self.expect(Token::Close)?;
+                Ok(node)
+            }
+            Some(Token::Identifier(name)) => {
+                if let Some(index) = self.variables.iter().position(|v| *v == name) {
+                    return Ok(Node::Variable(index));
+                }
+                match name.as_str() {
+                    "pi" => return Ok(Node::Number(PI)),
+                    "e" => return Ok(Node::Number(E)),
+                    _ => {}
+                }
+                let function =
+                    Function::from_name(&name).ok_or_else(|| format!("Unknown name {}", name))?;
+                self.expect(Token::Open)?;
+                let mut arguments = vec![self.expression()?];
+                while let Some(Token::Comma) = self.peek() {
+                    self.position += 1;
+                    arguments.push(self.expression()?);
+                }
+                self.expect(Token::Close)?;
+                if arguments.len() != function.arguments() {
+                    return Err(format!(
+                        "{} takes {} argument(s)",
+                        name,
+                        function.arguments()
+                    ));
+                }
+                Ok(Node::Function(function, arguments))
+            }
+            Some(token) => Err(format!("Unexpected {}", token)),
+            None => Err("Unexpected end".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(source: &str) -> f32 {
+        Expression::parse(source, &["x"]).unwrap().evaluate(&[2.0])
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(value("2 + 3*4"), 14.0);
+        assert_eq!(value("(2 + 3)*4"), 20.0);
+        assert_eq!(value("-2^2"), -4.0);
+        assert_eq!(value("2^3^2"), 512.0);
+        assert_eq!(value("-2^2 + 2^3^2"), 508.0);
+        assert_eq!(value("8/4/2"), 1.0);
+        assert_eq!(value("x^2 - x"), 2.0);
+        assert_eq!(value("min(x, 3) + max(1, x*2)"), 6.0);
+        assert!((value("atan2(1, 1)") - PI / 4.0).abs() < 1e-6);
+        assert!((value("sin(pi/2) + ln(e)") - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn errors() {
+        for source in ["", "2 +", "(1", "1 )", "foo(1)", "min(1)", "b * 2", "2 $ 3"] {
+            assert!(
+                Expression::parse(source, &["x"]).is_err(),
+                "{} should not parse",
+                source
+            );
+        }
+        assert_eq!(
+            Expression::parse("b", &["x"]),
+            Err("Unknown name b".to_owned())
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        let parameters = parse_parameters("a = 2; k = 2*pi/a;", &["x"]).unwrap();
+        assert_eq!(parameters[0], ("a".to_owned(), 2.0));
+        assert!((parameters[1].1 - PI).abs() < 1e-6);
+        assert_eq!(
+            parse_parameters("x = 1", &["x"]),
+            Err("x is already taken".to_owned())
+        );
+        assert!(parse_parameters("a = 1; a = 2", &[]).is_err());
+        assert!(parse_parameters("sin = 1", &[]).is_err());
+        assert!(parse_parameters("a = 1/0", &[]).is_err());
+    }
+}
@@ -0,0 +1,685 @@
+use std::{f32::consts::PI, fmt, str::FromStr};
+
+use uom::si::{
+    angle::radian,
+    electric_field::volt_per_meter,
+    electric_permittivity::farad_per_meter,
+    f32::{Angle, ElectricField, Frequency, Length, Time},
+    frequency::hertz,
+    length::meter,
+    magnetic_permeability::henry_per_meter,
+    time::second,
+};
+
+use crate::{
+    electromagnetic_wave::SPEED_OF_LIGHT,
+    poynting::{VACUUM_PERMEABILITY, VACUUM_PERMITTIVITY},
+};
+
+/// thickness of the absorbing layer around the grid
+pub const PML_CELLS: usize = 20;
+/// S = cΔt/Δx, stable in 1d (S ≤ 1) and 2d (S ≤ 1/√2)
+pub const COURANT_NUMBER: f32 = 0.5;
+/// fewest cells across the simulated span, e.g. for waves much longer than it
+pub const MIN_CELLS: f32 = 100.0;
+/// polynomial grading of the PML conductivity, σ(d) = σ_max(d/thickness)^m
+const PML_GRADING: i32 = 3;
+
+/// time dependence of a source, τ is the time at the source, see [`SourceShape`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Waveform {
+    /// cos(φ − ωτ), switched on at τ = start
+    /// for a plane wave τ = t − x/c, so behind the front it's cos(kx − ωt + φ) like the analytic wave
+    Continuous {
+        frequency: Frequency,
+        phase: Angle,
+        start: Time,
+    },
+    /// e^(−((τ − delay)/width)²)cos(ω(τ − delay)), frequency 0 for a plain gaussian
+    GaussianPulse {
+        frequency: Frequency,
+        width: Time,
+        delay: Time,
+    },
+}
+
+impl Waveform {
+    /// relative to the amplitude of the source
+    pub fn value(&self, tau: Time) -> f32 {
+        match *self {
+            Waveform::Continuous {
+                frequency,
+                phase,
+                start,
+            } => {
+                if tau < start {
+                    0.0
+                } else {
+                    let omega = 2.0 * PI * frequency.get::<hertz>();
+                    (phase.get::<radian>() - omega * tau.get::<second>()).cos()
+                }
+            }
+            Waveform::GaussianPulse {
+                frequency,
+                width,
+                delay,
+            } => {
+                let relative = (tau - delay).get::<second>();
+                let omega = 2.0 * PI * frequency.get::<hertz>();
+                (-(relative / width.get::<second>()).powi(2)).exp() * (omega * relative).cos()
+            }
+        }
+    }
+}
+
+/// where and how a source injects its field, E is along z
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceShape {
+    /// soft source adding to E in the cell nearest to (x, y), τ = t
+    /// the radiated amplitude depends on the grid, it's not the amplitude of the source
+    Point { x: Length, y: Length },
+    /// soft source along the line at x, in 1d the same as a point, τ = t
+    Line { x: Length },
+    /// total-field/scattered-field boundary at x, τ = t − x/c
+    /// right of it there's a plane wave travelling along +x with the amplitude of the source plus
+    /// whatever it scatters, left of it only the scattered field
+    PlaneWave { x: Length },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FdtdSource {
+    pub shape: SourceShape,
+    pub amplitude: ElectricField,
+    pub waveform: Waveform,
+}
+
+/// region with a relative permittivity other than 1, e.g. a glass slab
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DielectricRegion {
+    pub x_min: Length,
+    pub x_max: Length,
+    pub y_min: Length,
+    pub y_max: Length,
+    pub relative_permittivity: f32,
+}
+
+impl DielectricRegion {
+    /// infinite along y, the only shape that makes sense in 1d
+    pub fn slab(x_min: Length, x_max: Length, relative_permittivity: f32) -> Self {
+        DielectricRegion {
+            x_min,
+            x_max,
+            y_min: Length::new::<meter>(f32::NEG_INFINITY),
+            y_max: Length::new::<meter>(f32::INFINITY),
+            relative_permittivity,
+        }
+    }
+
+    pub fn contains(&self, x: Length, y: Length) -> bool {
+        x >= self.x_min && x <= self.x_max && y >= self.y_min && y <= self.y_max
+    }
+
+    fn is_slab(&self) -> bool {
+        self.y_min.value.is_infinite() && self.y_max.value.is_infinite()
+    }
+}
+
+/// "x_min x_max εᵣ" for a slab or "x_min x_max y_min y_max εᵣ", in m
+impl fmt::Display for DielectricRegion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ",
+            self.x_min.get::<meter>(),
+            self.x_max.get::<meter>()
+        )?;
+        if !self.is_slab() {
+            write!(
+                f,
+                "{} {} ",
+                self.y_min.get::<meter>(),
+                self.y_max.get::<meter>()
+            )?;
+        }
+        write!(f, "{}", self.relative_permittivity)
+    }
+}
+
+impl FromStr for DielectricRegion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split_whitespace()
+            .map(|value| {
+                value
+                    .parse::<f32>()
+                    .map_err(|_| format!("Invalid number: {}", value))
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+        if let Some(value) = values.iter().find(|value| !value.is_finite()) {
+            return Err(format!("Values must be finite: {}", value));
+        }
+        let length = Length::new::<meter>;
+        let region = match values[..] {
+            [x_min, x_max, relative_permittivity] => {
+                DielectricRegion::slab(length(x_min), length(x_max), relative_permittivity)
+            }
+            [x_min, x_max, y_min, y_max, relative_permittivity] => DielectricRegion {
+                x_min: length(x_min),
+                x_max: length(x_max),
+                y_min: length(y_min),
+                y_max: length(y_max),
+                relative_permittivity,
+            },
+            _ => return Err("Expected x_min x_max εᵣ or x_min x_max y_min y_max εᵣ".to_owned()),
+        };
+        if region.x_max <= region.x_min || region.y_max <= region.y_min {
+            return Err("The region is empty".to_owned());
+        }
+        if region.relative_permittivity < 1.0 {
+            return Err(format!(
+                "εᵣ must be at least 1: {}",
+                region.relative_permittivity
+            ));
+        }
+        Ok(region)
+    }
+}
+
+/// layout of a Yee grid, E_z at the cell corners, H half a cell shifted
+/// a 1d grid has a single row, i.e. the fields don't depend on y
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FdtdGrid {
+    /// Δx = Δy
+    pub cell_size: Length,
+    /// position of the first E_z, inside the absorbing layer
+    pub origin_x: Length,
+    pub origin_y: Length,
+    pub cells_x: usize,
+    /// 1 in 1d
+    pub cells_y: usize,
+    /// thickness of the absorbing layer, in cells, included in cells_x and cells_y
+    pub pml_cells: usize,
+    pub courant_number: f32,
+}
+
+impl FdtdGrid {
+    /// covers [x_min, x_max] and, in 2d, [y_min, y_max], plus the absorbing layers
+    pub fn covering(
+        (x_min, x_max): (Length, Length),
+        y_range: Option<(Length, Length)>,
+        cell_size: Length,
+    ) -> Self {
+        let pml = Length::new::<meter>(PML_CELLS as f32 * cell_size.get::<meter>());
+        let cells = |min: Length, max: Length| {
+            ((max - min).get::<meter>() / cell_size.get::<meter>()).ceil() as usize
+                + 1
+                + 2 * PML_CELLS
+        };
+        let (origin_y, cells_y) = match y_range {
+            Some((y_min, y_max)) => (y_min - pml, cells(y_min, y_max)),
+            None => (Length::new::<meter>(0.0), 1),
+        };
+        FdtdGrid {
+            cell_size,
+            origin_x: x_min - pml,
+            origin_y,
+            cells_x: cells(x_min, x_max),
+            cells_y,
+            pml_cells: PML_CELLS,
+            courant_number: COURANT_NUMBER,
+        }
+    }
+
+    /// Δx resolving the vacuum wave length c/f with `cells_per_wave_length` cells, with at least
+    /// MIN_CELLS and at most `max_cells` across `span`, the latter bounds the cost of the solver
+    /// None if the span isn't positive, there would be no cells and no time step
+    pub fn fitting_cell_size(
+        frequency: Frequency,
+        span: Length,
+        cells_per_wave_length: f32,
+        max_cells: f32,
+    ) -> Option<Length> {
+        let span = span.get::<meter>();
+        let resolving = (*SPEED_OF_LIGHT / frequency).get::<meter>() / cells_per_wave_length;
+        let cell_size = resolving.min(span / MIN_CELLS).max(span / max_cells);
+        (cell_size.is_finite() && cell_size > 0.0).then(|| Length::new::<meter>(cell_size))
+    }
+
+    pub fn is_two_dimensional(&self) -> bool {
+        self.cells_y > 1
+    }
+
+    /// Δt = SΔx/c
+    pub fn time_step(&self) -> Time {
+        self.cell_size * self.courant_number / *SPEED_OF_LIGHT
+    }
+
+    /// position of E_z in column i
+    pub fn x(&self, i: usize) -> Length {
+        self.origin_x + self.cell_size * i as f32
+    }
+
+    /// position of E_z in row j
+    pub fn y(&self, j: usize) -> Length {
+        self.origin_y + self.cell_size * j as f32
+    }
+
+    /// nearest column, clamped to the grid
+    pub fn column(&self, x: Length) -> usize {
+        let i = ((x - self.origin_x) / self.cell_size).value.round();
+        i.clamp(0.0, (self.cells_x - 1) as f32) as usize
+    }
+
+    /// nearest row, clamped to the grid
+    pub fn row(&self, y: Length) -> usize {
+        let j = ((y - self.origin_y) / self.cell_size).value.round();
+        j.clamp(0.0, (self.cells_y - 1) as f32) as usize
+    }
+
+    /// σ at distance `depth` (in cells) into the absorbing layer, 0 outside
+    fn pml_conductivity(&self, depth: f32) -> f32 {
+        if depth <= 0.0 {
+            return 0.0;
+        }
+        // optimal maximum for a polynomial grading, see Taflove, Computational Electrodynamics,
+        // section 7.6
+        let sigma_max =
+            0.8 * (PML_GRADING + 1) as f32 / (vacuum_impedance() * self.cell_size.get::<meter>());
+        sigma_max * (depth / self.pml_cells as f32).min(1.0).powi(PML_GRADING)
+    }
+
+    /// depth into the absorbing layer at the (possibly half integer) index along an axis
+    fn pml_depth(&self, index: f32, cells: usize) -> f32 {
+        let pml = self.pml_cells as f32;
+        let last = (cells - 1) as f32;
+        (pml - index).max(index - (last - pml))
+    }
+}
+
+/// 1d/2d finite-difference time-domain solver for Maxwell's equations, TMz polarization: E along z,
+/// H in the x-y plane
+/// the boundaries are absorbing (Berenger's split field PML) in front of a perfect conductor
+/// see e.g. Taflove, Computational Electrodynamics, chapters 3 and 7
+pub struct Fdtd {
+    pub grid: FdtdGrid,
+    pub regions: Vec<DielectricRegion>,
+    pub sources: Vec<FdtdSource>,
+    steps: usize,
+    /// E_z = e_zx + e_zy, split for the PML, e_zy stays 0 in 1d
+    e_zx: Vec<f32>,
+    e_zy: Vec<f32>,
+    /// at (i, j + ½)
+    h_x: Vec<f32>,
+    /// at (i + ½, j)
+    h_y: Vec<f32>,
+    /// update coefficients, e_zx = ca_x·e_zx + cb_x·∂H_y/∂x
+    ca_x: Vec<f32>,
+    cb_x: Vec<f32>,
+    ca_y: Vec<f32>,
+    cb_y: Vec<f32>,
+    /// h_y = da_x·h_y + db_x·∂E_z/∂x, per column
+    da_x: Vec<f32>,
+    db_x: Vec<f32>,
+    /// h_x = da_y·h_x − db_y·∂E_z/∂y, per row
+    da_y: Vec<f32>,
+    db_y: Vec<f32>,
+}
+
+impl Fdtd {
+    pub fn new(grid: FdtdGrid, regions: Vec<DielectricRegion>, sources: Vec<FdtdSource>) -> Self {
+        let cells = grid.cells_x * grid.cells_y;
+        let dt = grid.time_step().get::<second>();
+        let epsilon_0 = VACUUM_PERMITTIVITY.get::<farad_per_meter>();
+        let mu_0 = VACUUM_PERMEABILITY.get::<henry_per_meter>();
+
+        // (1 − σΔt/2ε)/(1 + σΔt/2ε) and (Δt/ε)/(1 + σΔt/2ε), same form for H with σ_m and μ
+        let coefficients = |sigma: f32, epsilon: f32| {
+            let loss = sigma * dt / (2.0 * epsilon);
+            ((1.0 - loss) / (1.0 + loss), dt / epsilon / (1.0 + loss))
+        };
+        // matched magnetic conductivity σ_m = σμ₀/ε₀, so the layer has the impedance of vacuum
+        let magnetic_coefficients = |sigma: f32| coefficients(sigma * mu_0 / epsilon_0, mu_0);
+
+        let mut fdtd = Fdtd {
+            grid,
+            regions,
+            sources,
+            steps: 0,
+            e_zx: vec![0.0; cells],
+            e_zy: vec![0.0; cells],
+            h_x: vec![0.0; cells],
+            h_y: vec![0.0; cells],
+            ca_x: vec![0.0; cells],
+            cb_x: vec![0.0; cells],
+            ca_y: vec![0.0; cells],
+            cb_y: vec![0.0; cells],
+            da_x: vec![0.0; grid.cells_x],
+            db_x: vec![0.0; grid.cells_x],
+            da_y: vec![0.0; grid.cells_y],
+            db_y: vec![0.0; grid.cells_y],
+        };
+
+        for j in 0..grid.cells_y {
+            let sigma_y = if grid.is_two_dimensional() {
+                grid.pml_conductivity(grid.pml_depth(j as f32, grid.cells_y))
+            } else {
+                0.0
+            };
+            for i in 0..grid.cells_x {
+                let sigma_x = grid.pml_conductivity(grid.pml_depth(i as f32, grid.cells_x));
+                let epsilon = epsilon_0 * fdtd.relative_permittivity(grid.x(i), grid.y(j));
+                let index = fdtd.index(i, j);
+                (fdtd.ca_x[index], fdtd.cb_x[index]) = coefficients(sigma_x, epsilon);
+                (fdtd.ca_y[index], fdtd.cb_y[index]) = coefficients(sigma_y, epsilon);
+            }
+        }
+        for i in 0..grid.cells_x {
+            let depth = grid.pml_depth(i as f32 + 0.5, grid.cells_x);
+            (fdtd.da_x[i], fdtd.db_x[i]) = magnetic_coefficients(grid.pml_conductivity(depth));
+        }
+        for j in 0..grid.cells_y {
+            let depth = grid.pml_depth(j as f32 + 0.5, grid.cells_y);
+            (fdtd.da_y[j], fdtd.db_y[j]) = magnetic_coefficients(grid.pml_conductivity(depth));
+        }
+        fdtd
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        j * self.grid.cells_x + i
+    }
+
+    /// εᵣ at a position, the last region containing it wins
+    pub fn relative_permittivity(&self, x: Length, y: Length) -> f32 {
+        self.regions
+            .iter()
+            .rev()
+            .find(|region| region.contains(x, y))
+            .map(|region| region.relative_permittivity)
+            .unwrap_or(1.0)
+    }
+
+    /// simulated time, steps·Δt
+    pub fn time(&self) -> Time {
+        self.grid.time_step() * self.steps as f32
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// E_z at the grid point (i, j)
+    pub fn e_z(&self, i: usize, j: usize) -> ElectricField {
+        let index = self.index(i, j);
+        ElectricField::new::<volt_per_meter>(self.e_zx[index] + self.e_zy[index])
+    }
+
+    /// E_z linearly interpolated along x, in the row nearest to y, 0 outside the grid
+    pub fn e_z_at(&self, x: Length, y: Length) -> ElectricField {
+        let position = ((x - self.grid.origin_x) / self.grid.cell_size).value;
+        if position < 0.0 || position > (self.grid.cells_x - 1) as f32 {
+            return ElectricField::new::<volt_per_meter>(0.0);
+        }
+        let i = (position.floor() as usize).min(self.grid.cells_x - 2);
+        let fraction = position - i as f32;
+        let j = self.grid.row(y);
+        self.e_z(i, j) * (1.0 - fraction) + self.e_z(i + 1, j) * fraction
+    }
+
+    /// advances until the simulated time reaches t, at most max_steps, returns the steps taken
+    pub fn advance_to(&mut self, t: Time, max_steps: usize) -> usize {
+        let dt = self.grid.time_step();
+        let mut steps = 0;
+        while steps < max_steps && self.time() + dt <= t {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// one leapfrog step: H from n−½ to n+½, then E from n to n+1
+    pub fn step(&mut self) {
+        let (nx, ny) = (self.grid.cells_x, self.grid.cells_y);
+        let dx = self.grid.cell_size.get::<meter>();
+        let two_dimensional = self.grid.is_two_dimensional();
+        let dt = self.grid.time_step();
+        let t = self.time();
+
+        // H, the outermost E_z stay 0 (perfect conductor behind the PML)
+        for j in 0..ny {
+            for i in 0..nx - 1 {
+                let index = self.index(i, j);
+                let curl = (self.e_zx[index + 1] + self.e_zy[index + 1]
+                    - self.e_zx[index]
+                    - self.e_zy[index])
+                    / dx;
+                self.h_y[index] = self.da_x[i] * self.h_y[index] + self.db_x[i] * curl;
+            }
+        }
+        if two_dimensional {
+            for j in 0..ny - 1 {
+                for i in 0..nx {
+                    let index = self.index(i, j);
+                    let above = index + nx;
+                    let curl =
+                        (self.e_zx[above] + self.e_zy[above] - self.e_zx[index] - self.e_zy[index])
+                            / dx;
+                    self.h_x[index] = self.da_y[j] * self.h_x[index] - self.db_y[j] * curl;
+                }
+            }
+        }
+        self.correct_plane_wave_h(t);
+
+        // E
+        for j in self.updated_rows() {
+            for i in 1..nx - 1 {
+                let index = self.index(i, j);
+                let curl_x = (self.h_y[index] - self.h_y[index - 1]) / dx;
+                self.e_zx[index] = self.ca_x[index] * self.e_zx[index] + self.cb_x[index] * curl_x;
+                if two_dimensional {
+                    let curl_y = (self.h_x[index] - self.h_x[index - nx]) / dx;
+                    self.e_zy[index] =
+                        self.ca_y[index] * self.e_zy[index] - self.cb_y[index] * curl_y;
+                }
+            }
+        }
+        self.correct_plane_wave_e(t + dt * 0.5);
+        self.apply_soft_sources(t + dt);
+
+        self.steps += 1;
+    }
+
+    /// rows of E_z that are updated, in 2d the outermost ones stay 0
+    fn updated_rows(&self) -> std::ops::Range<usize> {
+        if self.grid.is_two_dimensional() {
+            1..self.grid.cells_y - 1
+        } else {
+            0..1
+        }
+    }
+
+    /// incident E_z of a plane wave source, H_y = −E_z/η₀ for a wave along +x
+    fn incident_e_z(source: &FdtdSource, x: Length, t: Time) -> f32 {
+        let tau = t - x / *SPEED_OF_LIGHT;
+        source.amplitude.get::<volt_per_meter>() * source.waveform.value(tau)
+    }
+
+    /// H_y left of the boundary only sees the scattered field, so the incident E_z right of it is
+    /// removed from its curl
+    fn correct_plane_wave_h(&mut self, t: Time) {
+        let dx = self.grid.cell_size.get::<meter>();
+        for source_index in 0..self.sources.len() {
+            let source = self.sources[source_index];
+            let SourceShape::PlaneWave { x } = source.shape else {
+                continue;
+            };
+            let i0 = self.grid.column(x).max(1);
+            let e_incident = Self::incident_e_z(&source, self.grid.x(i0), t);
+            for j in 0..self.grid.cells_y {
+                let index = self.index(i0 - 1, j);
+                self.h_y[index] -= self.db_x[i0 - 1] * e_incident / dx;
+            }
+        }
+    }
+
+    /// E_z right at the boundary is total field, but H_y left of it is only the scattered field,
+    /// so the incident H_y is added to its curl
+    fn correct_plane_wave_e(&mut self, t_half: Time) {
+        let dx = self.grid.cell_size.get::<meter>();
+        for source_index in 0..self.sources.len() {
+            let source = self.sources[source_index];
+            let SourceShape::PlaneWave { x } = source.shape else {
+                continue;
+            };
+            let i0 = self.grid.column(x).max(1);
+            let x_h = self.grid.x(i0) - self.grid.cell_size * 0.5;
+            let h_incident = -Self::incident_e_z(&source, x_h, t_half) / vacuum_impedance();
+            for j in self.updated_rows() {
+                let index = self.index(i0, j);
+                self.e_zx[index] -= self.cb_x[index] * h_incident / dx;
+            }
+        }
+    }
+
+    fn apply_soft_sources(&mut self, t: Time) {
+        for source_index in 0..self.sources.len() {
+            let source = self.sources[source_index];
+            let value = source.amplitude.get::<volt_per_meter>() * source.waveform.value(t);
+            match source.shape {
+                SourceShape::Point { x, y } => {
+                    let index = self.index(self.grid.column(x), self.grid.row(y));
+                    self.e_zx[index] += value;
+                }
+                SourceShape::Line { x } => {
+                    let i = self.grid.column(x);
+                    for j in self.updated_rows() {
+                        let index = self.index(i, j);
+                        self.e_zx[index] += value;
+                    }
+                }
+                SourceShape::PlaneWave { .. } => {}
+            }
+        }
+    }
+}
+
+/// η₀ = √(μ₀/ε₀), in ohm
+fn vacuum_impedance() -> f32 {
+    (VACUUM_PERMEABILITY.get::<henry_per_meter>() / VACUUM_PERMITTIVITY.get::<farad_per_meter>())
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::velocity::meter_per_second;
+
+    use super::*;
+
+    /// 1d vacuum grid over [0, 4] m with a gaussian plane wave pulse entering at x = 0.5 m
+    fn pulse_in_vacuum() -> Fdtd {
+        let cell_size = Length::new::<meter>(0.01);
+        let grid = FdtdGrid::covering(
+            (Length::new::<meter>(0.0), Length::new::<meter>(4.0)),
+            None,
+            cell_size,
+        );
+        let width = cell_size * 20.0 / *SPEED_OF_LIGHT;
+        let source = FdtdSource {
+            shape: SourceShape::PlaneWave {
+                x: Length::new::<meter>(0.5),
+            },
+            amplitude: ElectricField::new::<volt_per_meter>(1.0),
+            waveform: Waveform::GaussianPulse {
+                frequency: Frequency::new::<hertz>(0.0),
+                width,
+                delay: width * 5.0,
+            },
+        };
+        Fdtd::new(grid, vec![], vec![source])
+    }
+
+    /// time and value of the largest E_z at x until t_end
+    fn peaks(fdtd: &mut Fdtd, xs: &[f32], t_end: Time) -> Vec<(f32, f32)> {
+        let mut peaks = vec![(0.0, 0.0); xs.len()];
+        while fdtd.time() < t_end {
+            fdtd.step();
+            for (x, peak) in xs.iter().zip(peaks.iter_mut()) {
+                let value = fdtd
+                    .e_z_at(Length::new::<meter>(*x), Length::new::<meter>(0.0))
+                    .get::<volt_per_meter>();
+                if value > peak.1 {
+                    *peak = (fdtd.time().get::<second>(), value);
+                }
+            }
+        }
+        peaks
+    }
+
+    #[test]
+    fn plane_wave_pulse_arrives_after_distance_over_c() {
+        let mut fdtd = pulse_in_vacuum();
+        let dt = fdtd.grid.time_step().get::<second>();
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        let t_end = Time::new::<second>(5.0 / c);
+        let peaks = peaks(&mut fdtd, &[1.0, 3.0], t_end);
+
+        let travel_time = peaks[1].0 - peaks[0].0;
+        assert!(
+            (travel_time - 2.0 / c).abs() <= 2.0 * dt,
+            "pulse took {} s for 2 m, expected {} s",
+            travel_time,
+            2.0 / c
+        );
+        for (_, value) in peaks {
+            assert!((value - 1.0).abs() < 0.05, "peak {} instead of 1", value);
+        }
+    }
+
+    #[test]
+    fn nothing_arrives_before_the_front() {
+        let mut fdtd = pulse_in_vacuum();
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        // the pulse peaks at the source 5 widths after the start, 1 m takes 100 widths
+        fdtd.advance_to(Time::new::<second>(1.0 / c), usize::MAX);
+        let far = fdtd.e_z_at(Length::new::<meter>(3.0), Length::new::<meter>(0.0));
+        assert!(far.get::<volt_per_meter>().abs() < 1e-6);
+    }
+
+    #[test]
+    fn parses_dielectric_region() {
+        let region: DielectricRegion = "2 4 2.25".parse().unwrap();
+        assert_eq!(region.relative_permittivity, 2.25);
+        assert!(region.contains(Length::new::<meter>(3.0), Length::new::<meter>(100.0)));
+        assert!(!region.contains(Length::new::<meter>(5.0), Length::new::<meter>(0.0)));
+        for s in [
+            "NaN 4 2.25",
+            "2 inf 2.25",
+            "2 4 NaN",
+            "0 1 -1 1 inf",
+            "4 2 2.25",
+            "2 4 0.5",
+        ] {
+            assert!(s.parse::<DielectricRegion>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn empty_span_has_no_cell_size() {
+        let frequency = Frequency::new::<hertz>(3e8);
+        let zero = Length::new::<meter>(0.0);
+        assert_eq!(
+            FdtdGrid::fitting_cell_size(frequency, zero, 20.0, 1000.0),
+            None
+        );
+        let span = Length::new::<meter>(-1.0);
+        assert_eq!(
+            FdtdGrid::fitting_cell_size(frequency, span, 20.0, 1000.0),
+            None
+        );
+        let span = Length::new::<meter>(4.0);
+        let cell_size = FdtdGrid::fitting_cell_size(frequency, span, 20.0, 1000.0).unwrap();
+        // c/f = 1 m would give 0.05 m, but the span needs MIN_CELLS
+        assert!((cell_size.get::<meter>() - 0.04).abs() < 1e-6);
+    }
+}
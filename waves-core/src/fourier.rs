@@ -0,0 +1,245 @@
+use std::{f32::consts::PI, fmt, str::FromStr};
+
+use uom::si::{
+    angle::radian,
+    f32::{Angle, Frequency, Length},
+};
+
+use crate::{
+    expression::Expression,
+    wave::{PropagationDirection, WaveParameters},
+};
+
+/// samples per period the coefficients are integrated with, the midpoint rule is exact for the
+/// harmonics below half of it, up to the jumps of the waveform
+const INTEGRATION_SAMPLES: usize = 4096;
+/// samples per period the maximum of a partial sum is searched with
+const OVERSHOOT_SAMPLES: usize = 4096;
+
+/// one period of a periodic function, evaluated at the fraction s of the period in [0, 1)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Waveform {
+    /// 1 in the first half, −1 in the second
+    Square,
+    /// rising from −1 to 1, jumping back at the end of the period
+    Sawtooth,
+    /// −1 at the start, 1 in the middle
+    Triangle,
+    /// 1 for the fraction `duty` of the period, 0 for the rest
+    PulseTrain { duty: f32 },
+    /// an expression in x, the fraction of the period
+    Custom(Expression),
+}
+
+impl Waveform {
+    /// value at s, which is wrapped into [0, 1)
+    pub fn value(&self, s: f32) -> f32 {
+        let s = s - s.floor();
+        match self {
+            Waveform::Square => {
+                if s < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Sawtooth => 2.0 * s - 1.0,
+            Waveform::Triangle => 1.0 - 4.0 * (s - 0.5).abs(),
+            Waveform::PulseTrain { duty } => {
+                if s < *duty {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Waveform::Custom(expression) => expression.evaluate(&[s]),
+        }
+    }
+}
+
+impl fmt::Display for Waveform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Waveform::Square => write!(f, "square"),
+            Waveform::Sawtooth => write!(f, "sawtooth"),
+            Waveform::Triangle => write!(f, "triangle"),
+            Waveform::PulseTrain { duty } => write!(f, "pulse {}", duty),
+            Waveform::Custom(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+/// "square", "sawtooth", "triangle", "pulse <duty>", or else an expression in x ∈ [0, 1),
+/// e.g. "sin(2*pi*x)^3"
+impl FromStr for Waveform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut parts = s.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("square"), None, _) => Ok(Waveform::Square),
+            (Some("sawtooth"), None, _) => Ok(Waveform::Sawtooth),
+            (Some("triangle"), None, _) => Ok(Waveform::Triangle),
+            (Some("pulse"), Some(duty), None) => {
+                let duty = duty
+                    .parse::<f32>()
+                    .map_err(|e| format!("Invalid duty cycle {}: {}", duty, e))?;
+                if duty > 0.0 && duty < 1.0 {
+                    Ok(Waveform::PulseTrain { duty })
+                } else {
+                    Err(format!("Duty cycle must be between 0 and 1: {}", duty))
+                }
+            }
+            _ => Ok(Waveform::Custom(s.parse()?)),
+        }
+    }
+}
+
+/// A cos(2πns + φ), the n-th term of the series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Harmonic {
+    pub n: usize,
+    pub amplitude: f32,
+    pub phase: Angle,
+}
+
+/// f(s) = mean + Σ Aₙcos(2πns + φₙ), n = 1..
+#[derive(Debug, Clone, PartialEq)]
+pub struct FourierSeries {
+    pub mean: f32,
+    pub harmonics: Vec<Harmonic>,
+}
+
+impl FourierSeries {
+    /// the first `count` harmonics, aₙ = 2∫f(s)cos(2πns)ds and bₙ = 2∫f(s)sin(2πns)ds over one
+    /// period, so aₙcos + bₙsin = Aₙcos(2πns + φₙ) with Aₙ = √(aₙ² + bₙ²) and φₙ = atan2(−bₙ, aₙ)
+    pub fn of(waveform: &Waveform, count: usize) -> FourierSeries {
+        let samples: Vec<(f32, f32)> = (0..INTEGRATION_SAMPLES)
+            .map(|i| {
+                let s = (i as f32 + 0.5) / INTEGRATION_SAMPLES as f32;
+                (s, waveform.value(s))
+            })
+            .collect();
+        let mean = samples.iter().map(|(_, f)| f).sum::<f32>() / INTEGRATION_SAMPLES as f32;
+        let harmonics = (1..=count)
+            .map(|n| {
+                let (a, b) = samples.iter().fold((0.0, 0.0), |(a, b), (s, f)| {
+                    let angle = 2.0 * PI * n as f32 * s;
+                    (a + f * angle.cos(), b + f * angle.sin())
+                });
+                let (a, b) = (
+                    2.0 * a / INTEGRATION_SAMPLES as f32,
+                    2.0 * b / INTEGRATION_SAMPLES as f32,
+                );
+                Harmonic {
+                    n,
+                    amplitude: a.hypot(b),
+                    phase: Angle::new::<radian>((-b).atan2(a)),
+                }
+            })
+            .collect();
+        FourierSeries { mean, harmonics }
+    }
+
+    /// mean plus the first `count` harmonics at s
+    pub fn partial_sum(&self, s: f32, count: usize) -> f32 {
+        self.mean
+            + self
+                .harmonics
+                .iter()
+                .take(count)
+                .map(|h| h.amplitude * (2.0 * PI * h.n as f32 * s + h.phase.get::<radian>()).cos())
+                .sum::<f32>()
+    }
+
+    /// the harmonic as travelling wave with the fundamental's wave length and frequency, i.e.
+    /// λ/n and nf, scaled by amplitude
+    /// with s = (x − ct)/λ, the sum of these waves is the partial sum travelling along +x
+    pub fn harmonic_wave(
+        harmonic: &Harmonic,
+        amplitude: Length,
+        wave_length: Length,
+        frequency: Frequency,
+    ) -> WaveParameters {
+        WaveParameters {
+            amplitude: amplitude * harmonic.amplitude,
+            wave_length: wave_length / harmonic.n as f32,
+            frequency: frequency * harmonic.n as f32,
+            phase: harmonic.phase,
+            direction: PropagationDirection::Forward,
+        }
+    }
+
+    /// Gibbs phenomenon: how far the partial sum overshoots the largest value of the waveform,
+    /// relative to the waveform's range, about 9% next to a jump no matter how many harmonics
+    /// 0 for a continuous waveform with enough harmonics
+    pub fn overshoot(&self, waveform: &Waveform, count: usize) -> f32 {
+        let (mut max, mut min, mut max_sum) = (f32::MIN, f32::MAX, f32::MIN);
+        for i in 0..OVERSHOOT_SAMPLES {
+            let s = i as f32 / OVERSHOOT_SAMPLES as f32;
+            let value = waveform.value(s);
+            max = max.max(value);
+            min = min.min(value);
+            max_sum = max_sum.max(self.partial_sum(s, count));
+        }
+        if max > min {
+            ((max_sum - max) / (max - min)).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// s in [0, 1) where the partial sum is largest, where the overshoot is
+    pub fn peak(&self, count: usize) -> f32 {
+        (0..OVERSHOOT_SAMPLES)
+            .map(|i| i as f32 / OVERSHOOT_SAMPLES as f32)
+            .fold((0.0, f32::MIN), |(peak, max), s| {
+                let value = self.partial_sum(s, count);
+                if value > max {
+                    (s, value)
+                } else {
+                    (peak, max)
+                }
+            })
+            .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_wave_coefficients() {
+        let series = FourierSeries::of(&Waveform::Square, 9);
+        assert!(series.mean.abs() < 1e-4);
+        for harmonic in &series.harmonics {
+            if harmonic.n % 2 == 0 {
+                assert!(
+                    harmonic.amplitude < 1e-3,
+                    "A{} = {}",
+                    harmonic.n,
+                    harmonic.amplitude
+                );
+            } else {
+                let expected = 4.0 / (PI * harmonic.n as f32);
+                assert!(
+                    (harmonic.amplitude - expected).abs() < 1e-3,
+                    "A{} = {}",
+                    harmonic.n,
+                    harmonic.amplitude
+                );
+                // sin(2πns) = cos(2πns − π/2)
+                assert!((harmonic.phase.get::<radian>() + PI / 2.0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn gibbs_overshoot() {
+        let series = FourierSeries::of(&Waveform::Square, 50);
+        let overshoot = series.overshoot(&Waveform::Square, 50);
+        assert!((0.08..0.10).contains(&overshoot), "overshoot {}", overshoot);
+    }
+}
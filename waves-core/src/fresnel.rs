@@ -0,0 +1,270 @@
+use std::f32::consts::PI;
+
+use glam::Vec3;
+use uom::si::{angle::radian, f32::Angle};
+
+use crate::electromagnetic_wave::JonesVector;
+
+/// planar interface between two non absorbing media, the wave comes from the medium with n1
+/// the interface is the plane x=0 with its normal along x, the plane of incidence is x-z
+/// s polarization is along y, p polarization in the plane of incidence, see [`p_unit_vector`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interface {
+    pub n1: f32,
+    pub n2: f32,
+}
+
+/// unit vector of s polarization, perpendicular to the plane of incidence
+pub const S_UNIT_VECTOR: Vec3 = Vec3::Y;
+
+/// unit vector of p polarization for a wave travelling along k, k̂ × ŝ
+/// for k along x this is z, i.e. the transverse axes of the wave along x are (s, p)
+pub fn p_unit_vector(k: Vec3) -> Vec3 {
+    k.normalize().cross(S_UNIT_VECTOR)
+}
+
+/// propagation directions, transmitted is None for total internal reflection
+#[derive(Debug, Clone, Copy)]
+pub struct WaveDirections {
+    pub incident: Vec3,
+    pub reflected: Vec3,
+    pub transmitted: Option<Vec3>,
+}
+
+/// complex ratio of the amplitudes of two waves, |r|e^{iδ}
+/// δ is added to the phase of the wave, e.g. π for a real negative ratio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmplitudeRatio {
+    pub magnitude: f32,
+    pub phase: Angle,
+}
+
+impl AmplitudeRatio {
+    pub fn from_real(value: f32) -> Self {
+        AmplitudeRatio {
+            magnitude: value.abs(),
+            phase: Angle::new::<radian>(if value < 0.0 { PI } else { 0.0 }),
+        }
+    }
+
+    /// (a + ib) / (c + id)
+    fn from_complex_quotient(a: f32, b: f32, c: f32, d: f32) -> Self {
+        AmplitudeRatio {
+            magnitude: a.hypot(b) / c.hypot(d),
+            phase: Angle::new::<radian>(b.atan2(a) - d.atan2(c)),
+        }
+    }
+}
+
+/// amplitude ratios of the reflected and transmitted waves to the incident one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FresnelCoefficients {
+    pub r_s: AmplitudeRatio,
+    pub r_p: AmplitudeRatio,
+    pub t_s: AmplitudeRatio,
+    pub t_p: AmplitudeRatio,
+    /// the transmitted wave is evanescent, nothing is transmitted on average
+    pub total_internal_reflection: bool,
+}
+
+impl FresnelCoefficients {
+    /// fraction of the s polarized intensity that is reflected, |r_s|²
+    pub fn reflectance_s(&self) -> f32 {
+        self.r_s.magnitude * self.r_s.magnitude
+    }
+
+    /// fraction of the p polarized intensity that is reflected, |r_p|²
+    pub fn reflectance_p(&self) -> f32 {
+        self.r_p.magnitude * self.r_p.magnitude
+    }
+
+    /// polarization of the reflected wave and the phase added to the incident wave
+    /// the jones vector of the incident wave is relative to (s, p)
+    pub fn reflected(&self, jones: &JonesVector) -> (JonesVector, Angle) {
+        apply_ratios(jones, &self.r_s, &self.r_p)
+    }
+
+    /// like [`FresnelCoefficients::reflected`]
+    pub fn transmitted(&self, jones: &JonesVector) -> (JonesVector, Angle) {
+        apply_ratios(jones, &self.t_s, &self.t_p)
+    }
+}
+
+fn apply_ratios(
+    jones: &JonesVector,
+    s: &AmplitudeRatio,
+    p: &AmplitudeRatio,
+) -> (JonesVector, Angle) {
+    (
+        JonesVector {
+            y: jones.y * s.magnitude,
+            z: jones.z * p.magnitude,
+            phase_difference: jones.phase_difference + p.phase - s.phase,
+        },
+        s.phase,
+    )
+}
+
+impl Interface {
+    /// θt from Snell's law n1 sin θi = n2 sin θt, None for total internal reflection
+    pub fn refraction_angle(&self, incidence: Angle) -> Option<Angle> {
+        let sin_t = self.n1 * incidence.get::<radian>().sin() / self.n2;
+        (sin_t.abs() <= 1.0).then(|| Angle::new::<radian>(sin_t.asin()))
+    }
+
+    /// θc = asin(n2/n1), None if n2 ≥ n1, i.e. there's always a transmitted wave
+    pub fn critical_angle(&self) -> Option<Angle> {
+        (self.n2 < self.n1).then(|| Angle::new::<radian>((self.n2 / self.n1).asin()))
+    }
+
+    /// θB = atan(n2/n1), incidence angle at which p polarized light isn't reflected
+    pub fn brewster_angle(&self) -> Angle {
+        Angle::new::<radian>((self.n2 / self.n1).atan())
+    }
+
+    /// the incident wave hits the origin coming from x<0 and travels downwards (−z)
+    pub fn directions(&self, incidence: Angle) -> WaveDirections {
+        let theta_i = incidence.get::<radian>();
+        WaveDirections {
+            incident: Vec3::new(theta_i.cos(), 0.0, -theta_i.sin()),
+            reflected: Vec3::new(-theta_i.cos(), 0.0, -theta_i.sin()),
+            transmitted: self.refraction_angle(incidence).map(|theta_t| {
+                let theta_t = theta_t.get::<radian>();
+                Vec3::new(theta_t.cos(), 0.0, -theta_t.sin())
+            }),
+        }
+    }
+
+    /// Fresnel equations, for p with the unit vectors of [`p_unit_vector`]
+    /// r_s = (n1cosθi − n2cosθt)/(n1cosθi + n2cosθt), t_s = 2n1cosθi/(n1cosθi + n2cosθt)
+    /// r_p = (n2cosθi − n1cosθt)/(n2cosθi + n1cosθt), t_p = 2n1cosθi/(n2cosθi + n1cosθt)
+    /// beyond the critical angle cosθt = i√(sin²θt − 1), so |r| = 1 and only the phase changes
+    /// see e.g. Hecht, Optics, section 4.6
+    pub fn coefficients(&self, incidence: Angle) -> FresnelCoefficients {
+        let (n1, n2) = (self.n1, self.n2);
+        let theta_i = incidence.get::<radian>();
+        let cos_i = theta_i.cos();
+        let sin_t = n1 * theta_i.sin() / n2;
+
+        if sin_t.abs() <= 1.0 {
+            let cos_t = (1.0 - sin_t * sin_t).sqrt();
+            let s_denominator = n1 * cos_i + n2 * cos_t;
+            let p_denominator = n2 * cos_i + n1 * cos_t;
+            FresnelCoefficients {
+                r_s: AmplitudeRatio::from_real((n1 * cos_i - n2 * cos_t) / s_denominator),
+                r_p: AmplitudeRatio::from_real((n2 * cos_i - n1 * cos_t) / p_denominator),
+                t_s: AmplitudeRatio::from_real(2.0 * n1 * cos_i / s_denominator),
+                t_p: AmplitudeRatio::from_real(2.0 * n1 * cos_i / p_denominator),
+                total_internal_reflection: false,
+            }
+        } else {
+            // cos θt = iκ
+            let kappa = (sin_t * sin_t - 1.0).sqrt();
+            FresnelCoefficients {
+                r_s: AmplitudeRatio::from_complex_quotient(
+                    n1 * cos_i,
+                    -n2 * kappa,
+                    n1 * cos_i,
+                    n2 * kappa,
+                ),
+                r_p: AmplitudeRatio::from_complex_quotient(
+                    n2 * cos_i,
+                    -n1 * kappa,
+                    n2 * cos_i,
+                    n1 * kappa,
+                ),
+                t_s: AmplitudeRatio::from_complex_quotient(
+                    2.0 * n1 * cos_i,
+                    0.0,
+                    n1 * cos_i,
+                    n2 * kappa,
+                ),
+                t_p: AmplitudeRatio::from_complex_quotient(
+                    2.0 * n1 * cos_i,
+                    0.0,
+                    n2 * cos_i,
+                    n1 * kappa,
+                ),
+                total_internal_reflection: true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::angle::degree;
+
+    use super::*;
+
+    /// fraction of the incident power transmitted, |t|²·n2cosθt/(n1cosθi)
+    fn transmittance(interface: &Interface, incidence: Angle, t: &AmplitudeRatio) -> f32 {
+        let theta_t = interface.refraction_angle(incidence).unwrap();
+        t.magnitude * t.magnitude * interface.n2 * theta_t.get::<radian>().cos()
+            / (interface.n1 * incidence.get::<radian>().cos())
+    }
+
+    #[test]
+    fn reflected_and_transmitted_power_add_up() {
+        for interface in [
+            Interface { n1: 1.0, n2: 1.5 },
+            Interface { n1: 1.5, n2: 1.0 },
+        ] {
+            for degrees in [0.0, 20.0, 40.0, 41.0, 60.0, 85.0] {
+                let incidence = Angle::new::<degree>(degrees);
+                if interface.refraction_angle(incidence).is_none() {
+                    continue;
+                }
+                let coefficients = interface.coefficients(incidence);
+                let s = coefficients.reflectance_s()
+                    + transmittance(&interface, incidence, &coefficients.t_s);
+                let p = coefficients.reflectance_p()
+                    + transmittance(&interface, incidence, &coefficients.t_p);
+                assert!(
+                    (s - 1.0).abs() < 1e-5,
+                    "R + T = {} for s at {}°",
+                    s,
+                    degrees
+                );
+                assert!(
+                    (p - 1.0).abs() < 1e-5,
+                    "R + T = {} for p at {}°",
+                    p,
+                    degrees
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn p_isnt_reflected_at_brewster_angle() {
+        let interface = Interface { n1: 1.0, n2: 1.5 };
+        let brewster = interface.brewster_angle();
+        assert!((brewster.get::<degree>() - 56.31).abs() < 0.01);
+        let coefficients = interface.coefficients(brewster);
+        assert!(coefficients.r_p.magnitude < 1e-6);
+        assert!(coefficients.r_s.magnitude > 0.1);
+    }
+
+    #[test]
+    fn everything_is_reflected_beyond_the_critical_angle() {
+        let interface = Interface { n1: 1.5, n2: 1.0 };
+        let critical = interface.critical_angle().unwrap();
+        assert!((critical.get::<degree>() - 41.81).abs() < 0.01);
+        let coefficients = interface.coefficients(Angle::new::<degree>(60.0));
+        assert!(coefficients.total_internal_reflection);
+        assert!((coefficients.reflectance_s() - 1.0).abs() < 1e-6);
+        assert!((coefficients.reflectance_p() - 1.0).abs() < 1e-6);
+        assert!(interface
+            .refraction_angle(Angle::new::<degree>(60.0))
+            .is_none());
+    }
+
+    #[test]
+    fn normal_incidence() {
+        let coefficients = Interface { n1: 1.0, n2: 1.5 }.coefficients(Angle::new::<degree>(0.0));
+        // ((n1 − n2)/(n1 + n2))² = 4%, the reflected wave is inverted
+        assert!((coefficients.reflectance_s() - 0.04).abs() < 1e-6);
+        assert!((coefficients.r_s.phase.get::<radian>() - PI).abs() < 1e-6);
+    }
+}
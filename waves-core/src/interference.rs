@@ -0,0 +1,209 @@
+use std::f32::consts::PI;
+
+use uom::si::{
+    angle::radian,
+    f32::{Angle, Frequency, Length, Time},
+    frequency::hertz,
+    length::meter,
+    time::second,
+};
+
+/// point sources a slit of finite width is made of, Huygens' principle
+pub const SLIT_SAMPLES: usize = 12;
+
+/// coherent sources (or slits) on the y axis, centered at the origin, radiating into x > 0
+/// neighbouring sources differ in phase by `phase_step`, like a phased array
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceArray {
+    pub count: usize,
+    /// d, between the centers of neighbouring sources
+    pub separation: Length,
+    /// a, 0 for point sources
+    pub slit_width: Length,
+    /// Δφ
+    pub phase_step: Angle,
+}
+
+/// one of the point sources the array is made of, weight 1 for point sources, 1/SLIT_SAMPLES for
+/// the samples of a slit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointSource {
+    pub y: Length,
+    pub phase: Angle,
+    pub weight: f32,
+}
+
+impl SourceArray {
+    /// center of source n, n in 0..count
+    pub fn center(&self, n: usize) -> Length {
+        self.separation * (n as f32 - (self.count as f32 - 1.0) / 2.0)
+    }
+
+    pub fn point_sources(&self) -> Vec<PointSource> {
+        let samples = if self.slit_width.get::<meter>() > 0.0 {
+            SLIT_SAMPLES
+        } else {
+            1
+        };
+        (0..self.count)
+            .flat_map(|n| {
+                let center = self.center(n);
+                let phase = self.phase_step * n as f32;
+                (0..samples).map(move |s| PointSource {
+                    // sample centers across the slit
+                    y: center + self.slit_width * ((s as f32 + 0.5) / samples as f32 - 0.5),
+                    phase,
+                    weight: 1.0 / samples as f32,
+                })
+            })
+            .collect()
+    }
+
+    /// directions of the principal maxima in the far field, kd sinθ − Δφ = 2πm, i.e.
+    /// d sinθ = (m + Δφ/2π)λ, as (m, θ) with θ from the x axis
+    pub fn maxima(&self, wave_length: Length) -> Vec<(i32, Angle)> {
+        let d = self.separation.get::<meter>();
+        let lambda = wave_length.get::<meter>();
+        if self.count < 2 || d <= 0.0 || lambda <= 0.0 {
+            return vec![];
+        }
+        let offset = self.phase_step.get::<radian>() / (2.0 * PI);
+        let m_max = (d / lambda + offset.abs()).ceil() as i32;
+        (-m_max..=m_max)
+            .filter_map(|m| {
+                let sin = (m as f32 + offset) * lambda / d;
+                (sin.abs() <= 1.0).then(|| (m, Angle::new::<radian>(sin.asin())))
+            })
+            .collect()
+    }
+
+    /// time averaged intensity far away in direction θ, relative to one source,
+    /// (sin(Nβ/2)/sin(β/2))² with β = kd sinθ − Δφ, times sinc²(ka sinθ/2) for slits
+    pub fn far_field_intensity(&self, theta: Angle, wave_length: Length) -> f32 {
+        let k = 2.0 * PI / wave_length.get::<meter>();
+        let sin = theta.get::<radian>().sin();
+        let beta = k * self.separation.get::<meter>() * sin - self.phase_step.get::<radian>();
+        let n = self.count as f32;
+        let array_factor = if (beta / 2.0).sin().abs() < 1e-6 {
+            n * n
+        } else {
+            ((n * beta / 2.0).sin() / (beta / 2.0).sin()).powi(2)
+        };
+        let alpha = k * self.slit_width.get::<meter>() * sin / 2.0;
+        let envelope = if alpha.abs() < 1e-6 {
+            1.0
+        } else {
+            (alpha.sin() / alpha).powi(2)
+        };
+        array_factor * envelope
+    }
+}
+
+/// Σ wₙe^(i(krₙ + φₙ)) at (x, y), as (re, im)
+/// all sources have the same amplitude everywhere, the 1/√r decay of circular waves is left out
+/// so the pattern is equally visible near and far from the sources
+pub fn phasor(sources: &[PointSource], (x, y): (f32, f32), wave_length: Length) -> (f32, f32) {
+    let k = 2.0 * PI / wave_length.get::<meter>();
+    sources.iter().fold((0.0, 0.0), |(re, im), source| {
+        let r = x.hypot(y - source.y.get::<meter>());
+        let phase = k * r + source.phase.get::<radian>();
+        (
+            re + source.weight * phase.cos(),
+            im + source.weight * phase.sin(),
+        )
+    })
+}
+
+/// Σ wₙcos(krₙ − ωt + φₙ), relative to the amplitude of one source
+pub fn field(
+    sources: &[PointSource],
+    position: (f32, f32),
+    wave_length: Length,
+    frequency: Frequency,
+    t: Time,
+) -> f32 {
+    let (re, im) = phasor(sources, position, wave_length);
+    let omega_t = 2.0 * PI * frequency.get::<hertz>() * t.get::<second>();
+    re * omega_t.cos() + im * omega_t.sin()
+}
+
+/// time average of the squared field times 2, |Σ wₙe^(i(krₙ + φₙ))|², N² at a principal maximum
+pub fn intensity(sources: &[PointSource], position: (f32, f32), wave_length: Length) -> f32 {
+    let (re, im) = phasor(sources, position, wave_length);
+    re * re + im * im
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn array(count: usize, phase_step: f32) -> SourceArray {
+        SourceArray {
+            count,
+            separation: Length::new::<meter>(5.0),
+            slit_width: Length::new::<meter>(0.0),
+            phase_step: Angle::new::<radian>(phase_step),
+        }
+    }
+
+    /// intensity from the sources at distance r in direction θ
+    fn intensity_at(array: &SourceArray, theta: f32, r: f32) -> f32 {
+        let position = (r * theta.cos(), r * theta.sin());
+        intensity(&array.point_sources(), position, Length::new::<meter>(1.0))
+    }
+
+    #[test]
+    fn maxima_where_the_path_difference_is_a_whole_number_of_wave_lengths() {
+        let wave_length = Length::new::<meter>(1.0);
+        let sources = array(3, 0.0);
+        let maxima = sources.maxima(wave_length);
+        // d sinθ = mλ, |m| ≤ d/λ
+        assert_eq!(maxima.len(), 11);
+        for (m, theta) in maxima {
+            let theta = theta.get::<radian>();
+            assert!((5.0 * theta.sin() - m as f32).abs() < 1e-5);
+            let far_field = sources.far_field_intensity(Angle::new::<radian>(theta), wave_length);
+            assert!((far_field - 9.0).abs() < 1e-2, "{} at m = {}", far_field, m);
+            if m.abs() < 5 {
+                assert!((intensity_at(&sources, theta, 1e4) - 9.0).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn two_sources_cancel_at_half_a_wave_length_path_difference() {
+        let sources = array(2, 0.0);
+        let theta = (0.5f32 / 5.0).asin();
+        let wave_length = Length::new::<meter>(1.0);
+        assert!(sources.far_field_intensity(Angle::new::<radian>(theta), wave_length) < 1e-4);
+        assert!(intensity_at(&sources, theta, 1e4) < 1e-3);
+    }
+
+    #[test]
+    fn phase_step_steers_the_maxima() {
+        // Δφ = π shifts the maxima by half an order, d sinθ = (m + ½)λ
+        let sources = array(4, PI);
+        let wave_length = Length::new::<meter>(1.0);
+        let maxima = sources.maxima(wave_length);
+        assert!(maxima.iter().all(|(m, theta)| {
+            (5.0 * theta.get::<radian>().sin() - (*m as f32 + 0.5)).abs() < 1e-5
+        }));
+        assert!(maxima
+            .iter()
+            .all(|(_, theta)| theta.get::<radian>().abs() > 0.05));
+        let first = (0.5f32 / 5.0).asin();
+        assert!((intensity_at(&sources, first, 1e4) - 16.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn slit_width_adds_the_single_slit_envelope() {
+        let mut sources = array(2, 0.0);
+        sources.slit_width = Length::new::<meter>(2.5);
+        // first zero of the envelope, a sinθ = λ, falls on the order m = 2
+        let theta = (1.0f32 / 2.5).asin();
+        let wave_length = Length::new::<meter>(1.0);
+        assert!(sources.far_field_intensity(Angle::new::<radian>(theta), wave_length) < 1e-4);
+        assert!(intensity_at(&sources, theta, 1e4) < 0.05);
+        assert_eq!(sources.point_sources().len(), 2 * SLIT_SAMPLES);
+    }
+}
@@ -0,0 +1,19 @@
+//! Wave physics, without any dependency on bevy.
+//! The models use `uom` quantities, the evaluation functions are pure and can be called from
+//! batch jobs, tests or the renderer alike.
+
+pub mod attenuation;
+pub mod electromagnetic_wave;
+pub mod expression;
+pub mod fdtd;
+pub mod fourier;
+pub mod fresnel;
+pub mod interference;
+pub mod medium;
+pub mod membrane;
+pub mod poynting;
+pub mod spectrum;
+pub mod standing_wave;
+pub mod vibrating_string;
+pub mod wave;
+pub mod wave_packet;
@@ -0,0 +1,369 @@
+use std::{f32::consts::PI, fmt, str::FromStr};
+
+use uom::si::{
+    f32::{Frequency, Length, Velocity},
+    frequency::hertz,
+    length::{meter, micrometer},
+    velocity::meter_per_second,
+};
+
+use crate::electromagnetic_wave::SPEED_OF_LIGHT;
+
+/// standard gravity, m/s²
+const GRAVITY: f32 = 9.806_65;
+
+/// iterations to solve k = n(ω)ω/c for ω, n depends only weakly on ω so few are needed
+const MAX_ITERATIONS: usize = 50;
+
+/// relative change of k used to differentiate ω(k)
+const DERIVATIVE_STEP: f32 = 1e-3;
+
+/// medium a wave travels in, given by its dispersion relation ω(k)
+/// the refractive index models (constant, Cauchy, Sellmeier) take the wave length in vacuum λ₀
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Medium {
+    /// ω = ck/n, no dispersion
+    Constant { refractive_index: f32 },
+    /// n(λ₀) = A + B/λ₀² + C/λ₀⁴, λ₀ in µm, for transparent materials in the visible range
+    Cauchy { a: f32, b: f32, c: f32 },
+    /// n²(λ₀) = 1 + Σ Bᵢλ₀²/(λ₀² − Cᵢ), λ₀ in µm, Cᵢ in µm²
+    Sellmeier { b: [f32; 3], c: [f32; 3] },
+    /// ω² = ω_p² + c²k², e.g. the ionosphere, waves below the plasma frequency don't propagate
+    Plasma { plasma_frequency: Frequency },
+    /// ω² = gk, gravity waves on water deeper than half a wave length
+    DeepWater,
+}
+
+impl Medium {
+    /// ω(k), k in rad/m, ω in rad/s
+    /// None if the medium doesn't support a wave with this wave number
+    pub fn angular_frequency_raw(&self, k: f32) -> Option<f32> {
+        if k <= 0.0 {
+            return None;
+        }
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        match self {
+            Medium::Constant { refractive_index } => Some(c * k / refractive_index),
+            Medium::Plasma { plasma_frequency } => {
+                let omega_p = 2.0 * PI * plasma_frequency.get::<hertz>();
+                Some((omega_p * omega_p + c * c * k * k).sqrt())
+            }
+            Medium::DeepWater => Some((GRAVITY * k).sqrt()),
+            Medium::Cauchy { .. } | Medium::Sellmeier { .. } => {
+                // fixed point of ω = ck/n(ω), starting with n = 1
+                // None if it doesn't settle, e.g. when n changes too quickly with ω
+                let mut omega = c * k;
+                for _ in 0..MAX_ITERATIONS {
+                    let next = c * k / self.refractive_index_raw(omega)?;
+                    let converged = ((next - omega) / omega).abs() < 1e-6;
+                    omega = next;
+                    if converged {
+                        return omega.is_finite().then_some(omega);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// k(ω), the inverse of [`Medium::angular_frequency_raw`]
+    /// None e.g. below the plasma frequency, where the wave is evanescent
+    pub fn wave_number_raw(&self, omega: f32) -> Option<f32> {
+        if omega <= 0.0 {
+            return None;
+        }
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        match self {
+            Medium::Plasma { plasma_frequency } => {
+                let omega_p = 2.0 * PI * plasma_frequency.get::<hertz>();
+                (omega > omega_p).then(|| (omega * omega - omega_p * omega_p).sqrt() / c)
+            }
+            Medium::DeepWater => Some(omega * omega / GRAVITY),
+            _ => Some(self.refractive_index_raw(omega)? * omega / c),
+        }
+    }
+
+    /// n of the refractive index models, at ω in rad/s
+    fn refractive_index_raw(&self, omega: f32) -> Option<f32> {
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        let vacuum_wave_length = Length::new::<meter>(2.0 * PI * c / omega).get::<micrometer>();
+        let l2 = vacuum_wave_length * vacuum_wave_length;
+        let n = match self {
+            Medium::Constant { refractive_index } => *refractive_index,
+            Medium::Cauchy { a, b, c } => a + b / l2 + c / (l2 * l2),
+            Medium::Sellmeier { b, c } => {
+                let n2 = 1.0
+                    + b.iter()
+                        .zip(c.iter())
+                        .map(|(b, c)| b * l2 / (l2 - c))
+                        .sum::<f32>();
+                // negative inside an absorption band, where the model doesn't apply
+                if n2 <= 0.0 {
+                    return None;
+                }
+                n2.sqrt()
+            }
+            _ => c * self.wave_number_raw(omega)? / omega,
+        };
+        (n.is_finite() && n > 0.0).then_some(n)
+    }
+
+    /// frequency of a wave with the given wave length in the medium
+    pub fn frequency(&self, wave_length: Length) -> Option<Frequency> {
+        let k = 2.0 * PI / wave_length.get::<meter>();
+        let omega = self.angular_frequency_raw(k)?;
+        Some(Frequency::new::<hertz>(omega / (2.0 * PI)))
+    }
+
+    /// wave length in the medium of a wave with the given frequency
+    pub fn wave_length(&self, frequency: Frequency) -> Option<Length> {
+        let k = self.wave_number_raw(2.0 * PI * frequency.get::<hertz>())?;
+        Some(Length::new::<meter>(2.0 * PI / k))
+    }
+
+    /// v_p = ω/k
+    pub fn phase_velocity(&self, wave_length: Length) -> Option<Velocity> {
+        Some(self.frequency(wave_length)? * wave_length)
+    }
+
+    /// v_g = dω/dk, numerically
+    pub fn group_velocity(&self, wave_length: Length) -> Option<Velocity> {
+        let k = 2.0 * PI / wave_length.get::<meter>();
+        let dk = k * DERIVATIVE_STEP;
+        let d_omega = self.angular_frequency_raw(k + dk)? - self.angular_frequency_raw(k - dk)?;
+        Some(Velocity::new::<meter_per_second>(d_omega / (2.0 * dk)))
+    }
+
+    /// n = c/v_p at the given frequency, also for the media that aren't defined by n
+    pub fn refractive_index(&self, frequency: Frequency) -> Option<f32> {
+        self.refractive_index_raw(2.0 * PI * frequency.get::<hertz>())
+    }
+}
+
+/// the format [`Medium::from_str`] parses
+impl fmt::Display for Medium {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Medium::Constant { refractive_index } => write!(f, "constant {}", refractive_index),
+            Medium::Cauchy { a, b, c } => write!(f, "cauchy {} {} {}", a, b, c),
+            Medium::Sellmeier { b, c } => write!(
+                f,
+                "sellmeier {} {} {} {} {} {}",
+                b[0], b[1], b[2], c[0], c[1], c[2]
+            ),
+            Medium::Plasma { plasma_frequency } => {
+                write!(f, "plasma {}", plasma_frequency.get::<hertz>())
+            }
+            Medium::DeepWater => write!(f, "deep_water"),
+        }
+    }
+}
+
+/// model name followed by its coefficients, separated by whitespace:
+/// "constant <n>", "cauchy <A> <B µm²> [<C µm⁴>]", "sellmeier <B1> <B2> <B3> <C1 µm²> <C2 µm²> <C3 µm²>",
+/// "plasma <plasma frequency hz>" or "deep_water"
+impl FromStr for Medium {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let model = parts.next().ok_or_else(|| "Medium is empty".to_owned())?;
+        let values = parts
+            .map(|part| {
+                part.parse::<f32>()
+                    .map_err(|e| format!("Invalid coefficient {}: {}", part, e))
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+        if let Some(value) = values.iter().find(|value| !value.is_finite()) {
+            return Err(format!("Coefficients must be finite, got {}", value));
+        }
+
+        let expect = |counts: &[usize]| {
+            if counts.contains(&values.len()) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "{} expects {:?} coefficients, got {}",
+                    model,
+                    counts,
+                    values.len()
+                ))
+            }
+        };
+
+        match model {
+            "constant" => {
+                expect(&[1])?;
+                if values[0] <= 0.0 {
+                    return Err(format!(
+                        "Refractive index must be positive, got {}",
+                        values[0]
+                    ));
+                }
+                Ok(Medium::Constant {
+                    refractive_index: values[0],
+                })
+            }
+            "cauchy" => {
+                expect(&[2, 3])?;
+                // keeps n ≥ A > 0 at every wave length
+                if values[0] <= 0.0 || values[1..].iter().any(|value| *value < 0.0) {
+                    return Err(format!(
+                        "cauchy expects A > 0 and B, C ≥ 0, got {:?}",
+                        values
+                    ));
+                }
+                Ok(Medium::Cauchy {
+                    a: values[0],
+                    b: values[1],
+                    c: values.get(2).copied().unwrap_or(0.0),
+                })
+            }
+            "sellmeier" => {
+                expect(&[6])?;
+                // Cᵢ are squared resonance wave lengths
+                if values.iter().any(|value| *value < 0.0) {
+                    return Err(format!(
+                        "sellmeier expects coefficients ≥ 0, got {:?}",
+                        values
+                    ));
+                }
+                Ok(Medium::Sellmeier {
+                    b: [values[0], values[1], values[2]],
+                    c: [values[3], values[4], values[5]],
+                })
+            }
+            "plasma" => {
+                expect(&[1])?;
+                if values[0] <= 0.0 {
+                    return Err(format!(
+                        "Plasma frequency must be positive, got {}",
+                        values[0]
+                    ));
+                }
+                Ok(Medium::Plasma {
+                    plasma_frequency: Frequency::new::<hertz>(values[0]),
+                })
+            }
+            "deep_water" => {
+                expect(&[0])?;
+                Ok(Medium::DeepWater)
+            }
+            _ => Err(format!(
+                "Unknown medium {}, expected constant, cauchy, sellmeier, plasma or deep_water",
+                model
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// n(f)·f·λ₀ = c for the frequency the medium gives a wave length in vacuum λ₀
+    /// λ₀ = nλ, with λ the wave length in the medium
+    fn assert_consistent(medium: &Medium, wave_length: Length) {
+        let frequency = medium.frequency(wave_length).unwrap();
+        let n = medium.refractive_index(frequency).unwrap();
+        let c = (frequency * wave_length * n).get::<meter_per_second>();
+        let expected = SPEED_OF_LIGHT.get::<meter_per_second>();
+        assert!(
+            ((c - expected) / expected).abs() < 1e-5,
+            "{}: n·f·λ = {}",
+            medium,
+            c
+        );
+        let back = medium.wave_length(frequency).unwrap();
+        assert!(((back - wave_length) / wave_length).value.abs() < 1e-4);
+    }
+
+    #[test]
+    fn cauchy_fixed_point() {
+        let medium: Medium = "cauchy 1.5046 0.0042".parse().unwrap();
+        assert_consistent(&medium, Length::new::<micrometer>(0.5));
+        let frequency = medium.frequency(Length::new::<micrometer>(0.5)).unwrap();
+        // λ₀ = nλ ≈ 0.76 µm, n ≈ 1.5119
+        let n = medium.refractive_index(frequency).unwrap();
+        assert!((n - 1.5119).abs() < 1e-3, "n = {}", n);
+    }
+
+    #[test]
+    fn sellmeier_fixed_point() {
+        // BK7
+        let medium: Medium =
+            "sellmeier 1.03961212 0.231792344 1.01046945 0.00600069867 0.0200179144 103.560653"
+                .parse()
+                .unwrap();
+        assert_consistent(&medium, Length::new::<micrometer>(0.35));
+        let frequency = medium.frequency(Length::new::<micrometer>(0.35)).unwrap();
+        let n = medium.refractive_index(frequency).unwrap();
+        assert!((1.50..1.53).contains(&n), "n = {}", n);
+    }
+
+    #[test]
+    fn plasma_cuts_off_below_the_plasma_frequency() {
+        let medium: Medium = "plasma 1e7".parse().unwrap();
+        assert_eq!(medium.wave_length(Frequency::new::<hertz>(5e6)), None);
+        assert_eq!(medium.refractive_index(Frequency::new::<hertz>(5e6)), None);
+        assert!(medium.wave_length(Frequency::new::<hertz>(2e7)).is_some());
+        // every wave length has a frequency, above the plasma frequency
+        let frequency = medium.frequency(Length::new::<meter>(1000.0)).unwrap();
+        assert!(frequency.get::<hertz>() > 1e7);
+    }
+
+    #[test]
+    fn rejects_invalid_refractive_index() {
+        for s in [
+            "constant 0",
+            "constant -1.5",
+            "constant inf",
+            "constant NaN",
+        ] {
+            assert!(s.parse::<Medium>().is_err(), "{}", s);
+        }
+        assert_eq!(
+            "constant 1.33".parse::<Medium>(),
+            Ok(Medium::Constant {
+                refractive_index: 1.33
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_coefficients() {
+        for s in [
+            "cauchy NaN 0.004",
+            "cauchy 0 0.004",
+            "cauchy 1.5 -0.004",
+            "cauchy 1.5 0.004 -1",
+            "sellmeier 1 0.2 1 0.006 0.02 -100",
+            "sellmeier 1 0.2 inf 0.006 0.02 100",
+            "plasma 0",
+            "plasma -1e7",
+            "plasma NaN",
+        ] {
+            assert!(s.parse::<Medium>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn fixed_point_fails_for_steep_dispersion() {
+        // n grows with ω² so quickly that ω = ck/n(ω) oscillates instead of settling
+        let medium: Medium = "cauchy 1 100".parse().unwrap();
+        assert_eq!(medium.frequency(Length::new::<micrometer>(1.0)), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in [
+            "constant 1.5",
+            "cauchy 1.5 0.004 0",
+            "plasma 10000000",
+            "deep_water",
+        ] {
+            let medium: Medium = s.parse().unwrap();
+            assert_eq!(medium.to_string().parse::<Medium>(), Ok(medium));
+        }
+    }
+}
@@ -0,0 +1,594 @@
+use std::{f32::consts::PI, fmt, str::FromStr};
+
+use uom::si::{
+    f32::{Frequency, Length, Time, Velocity},
+    frequency::hertz,
+    length::meter,
+    time::second,
+    velocity::meter_per_second,
+};
+
+/// S = cΔt/Δx, the 2d leapfrog scheme is stable up to 1/√2
+pub const COURANT_NUMBER: f32 = 0.5;
+/// width of the damping layer along absorbing edges, in cells
+pub const SPONGE_CELLS: f32 = 15.0;
+/// γΔt/2 at the outer edge of the damping layer, grows quadratically from 0 at its inner edge
+const MAX_DAMPING: f32 = 0.3;
+
+/// outline of the membrane, centered at the origin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MembraneShape {
+    /// e.g. a ripple tank
+    Rectangle { width: Length, depth: Length },
+    /// circular drum
+    Drum { radius: Length },
+}
+
+impl MembraneShape {
+    /// extent along x and y
+    pub fn size(&self) -> (Length, Length) {
+        match self {
+            MembraneShape::Rectangle { width, depth } => (*width, *depth),
+            MembraneShape::Drum { radius } => (*radius * 2.0, *radius * 2.0),
+        }
+    }
+}
+
+impl fmt::Display for MembraneShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MembraneShape::Rectangle { width, depth } => {
+                write!(f, "rect {} {}", width.get::<meter>(), depth.get::<meter>())
+            }
+            MembraneShape::Drum { radius } => write!(f, "drum {}", radius.get::<meter>()),
+        }
+    }
+}
+
+/// "rect <width> <depth>" or "drum <radius>", in m
+impl FromStr for MembraneShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let name = parts.next().ok_or_else(|| "Shape is empty".to_owned())?;
+        let values = parse_values(parts)?;
+        if let Some(value) = values.iter().find(|value| **value <= 0.0) {
+            return Err(format!("Sizes must be positive: {}", value));
+        }
+        match (name, &values[..]) {
+            ("rect", [width, depth]) => Ok(MembraneShape::Rectangle {
+                width: Length::new::<meter>(*width),
+                depth: Length::new::<meter>(*depth),
+            }),
+            ("drum", [radius]) => Ok(MembraneShape::Drum {
+                radius: Length::new::<meter>(*radius),
+            }),
+            ("rect" | "drum", _) => Err(format!("Wrong number of values for {}", name)),
+            _ => Err(format!("Unknown shape: {}", name)),
+        }
+    }
+}
+
+/// how the rim of the membrane behaves
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MembraneEdge {
+    /// u = 0, waves are reflected inverted, like a drum skin
+    #[default]
+    Fixed,
+    /// a damping layer along the rim swallows the waves, like the beaches of a ripple tank
+    Absorbing,
+}
+
+impl MembraneEdge {
+    pub fn next(&self) -> MembraneEdge {
+        match self {
+            MembraneEdge::Fixed => MembraneEdge::Absorbing,
+            MembraneEdge::Absorbing => MembraneEdge::Fixed,
+        }
+    }
+}
+
+impl fmt::Display for MembraneEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MembraneEdge::Fixed => write!(f, "fixed"),
+            MembraneEdge::Absorbing => write!(f, "absorbing"),
+        }
+    }
+}
+
+/// membrane or water surface, u_tt = c²(u_xx + u_yy)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MembraneParameters {
+    pub shape: MembraneShape,
+    /// √(T/σ) for a membrane under tension T with areal density σ, √(gh) for shallow water
+    pub wave_speed: Velocity,
+    pub edge: MembraneEdge,
+}
+
+/// where a source drives the surface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceGeometry {
+    /// e.g. a dipper
+    Point { x: Length, y: Length },
+    /// e.g. a bar, sending straight wave fronts
+    Line {
+        from: (Length, Length),
+        to: (Length, Length),
+    },
+}
+
+/// drives the covered cells with u = A sin(2πft), all sources are in phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MembraneSource {
+    pub geometry: SourceGeometry,
+    pub frequency: Frequency,
+    pub amplitude: Length,
+}
+
+impl MembraneSource {
+    pub fn displacement(&self, t: Time) -> Length {
+        self.amplitude * (2.0 * PI * self.frequency.get::<hertz>() * t.get::<second>()).sin()
+    }
+}
+
+impl fmt::Display for MembraneSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.geometry {
+            SourceGeometry::Point { x, y } => {
+                write!(f, "point {} {}", x.get::<meter>(), y.get::<meter>())?
+            }
+            SourceGeometry::Line { from, to } => write!(
+                f,
+                "line {} {} {} {}",
+                from.0.get::<meter>(),
+                from.1.get::<meter>(),
+                to.0.get::<meter>(),
+                to.1.get::<meter>()
+            )?,
+        }
+        write!(
+            f,
+            " {} {}",
+            self.frequency.get::<hertz>(),
+            self.amplitude.get::<meter>()
+        )
+    }
+}
+
+/// "point <x> <y> <f> <A>" or "line <x₁> <y₁> <x₂> <y₂> <f> <A>", positions and amplitude in m,
+/// frequency in hz
+impl FromStr for MembraneSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let name = parts.next().ok_or_else(|| "Source is empty".to_owned())?;
+        let values = parse_values(parts)?;
+        let m = Length::new::<meter>;
+        let (geometry, frequency, amplitude) = match (name, &values[..]) {
+            ("point", [x, y, frequency, amplitude]) => (
+                SourceGeometry::Point { x: m(*x), y: m(*y) },
+                *frequency,
+                *amplitude,
+            ),
+            ("line", [x1, y1, x2, y2, frequency, amplitude]) => (
+                SourceGeometry::Line {
+                    from: (m(*x1), m(*y1)),
+                    to: (m(*x2), m(*y2)),
+                },
+                *frequency,
+                *amplitude,
+            ),
+            ("point" | "line", _) => return Err(format!("Wrong number of values for {}", name)),
+            _ => return Err(format!("Unknown source: {}", name)),
+        };
+        if frequency < 0.0 {
+            return Err(format!("Frequency must not be negative: {}", frequency));
+        }
+        Ok(MembraneSource {
+            geometry,
+            frequency: Frequency::new::<hertz>(frequency),
+            amplitude: m(amplitude),
+        })
+    }
+}
+
+/// sources separated by ";", "none" or an empty string for none
+pub fn parse_sources(s: &str) -> Result<Vec<MembraneSource>, String> {
+    let s = s.trim();
+    if s.is_empty() || s == "none" {
+        return Ok(vec![]);
+    }
+    s.split(';').map(|source| source.trim().parse()).collect()
+}
+
+/// inverse of parse_sources
+pub fn format_sources(sources: &[MembraneSource]) -> String {
+    if sources.is_empty() {
+        return "none".to_owned();
+    }
+    sources
+        .iter()
+        .map(|source| source.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn parse_values<'a>(parts: impl Iterator<Item = &'a str>) -> Result<Vec<f32>, String> {
+    parts
+        .map(|part| {
+            part.parse::<f32>()
+                .map_err(|e| format!("Invalid value {}: {}", part, e))
+        })
+        .collect()
+}
+
+/// finite-difference solution of u_tt = c²(u_xx + u_yy) on the nodes of a square grid, leapfrog in
+/// time, five point laplacian in space, the rim of the shape is held at u = 0
+/// absorbing edges add a damping term γu_t in a layer along the rim
+pub struct MembraneSimulation {
+    pub parameters: MembraneParameters,
+    pub sources: Vec<MembraneSource>,
+    /// cells along x and y, there is one node more along each axis
+    cells: (usize, usize),
+    cell_size: Length,
+    steps: usize,
+    /// nodes within the shape, including the rim
+    contained: Vec<bool>,
+    /// contained nodes whose four neighbours are contained, the others form the rim
+    interior: Vec<bool>,
+    /// γΔt/2 per node
+    damping: Vec<f32>,
+    /// nodes driven by each source
+    driven: Vec<Vec<usize>>,
+    /// displacement in m at the nodes, row by row, at the previous and the current step
+    u_previous: Vec<f32>,
+    u: Vec<f32>,
+}
+
+impl MembraneSimulation {
+    /// the larger side of the shape is split into `cells` cells, the surface starts flat and at rest
+    pub fn new(parameters: MembraneParameters, sources: Vec<MembraneSource>, cells: usize) -> Self {
+        let cells = cells.max(4);
+        let (width, depth) = parameters.shape.size();
+        let cell_size = if width > depth { width } else { depth } / cells as f32;
+        let cells_x = ((width / cell_size).value.round() as usize).max(2);
+        let cells_y = ((depth / cell_size).value.round() as usize).max(2);
+        let nodes = (cells_x + 1) * (cells_y + 1);
+
+        let mut simulation = MembraneSimulation {
+            parameters,
+            sources,
+            cells: (cells_x, cells_y),
+            cell_size,
+            steps: 0,
+            contained: vec![false; nodes],
+            interior: vec![false; nodes],
+            damping: vec![0.0; nodes],
+            driven: vec![],
+            u_previous: vec![0.0; nodes],
+            u: vec![0.0; nodes],
+        };
+
+        let dx = cell_size.get::<meter>();
+        for j in 0..=cells_y {
+            for i in 0..=cells_x {
+                let index = simulation.index(i, j);
+                let (x, y) = simulation.position(i, j);
+                let (x, y) = (x.get::<meter>(), y.get::<meter>());
+                simulation.contained[index] = match parameters.shape {
+                    MembraneShape::Rectangle { .. } => true,
+                    // half a cell of tolerance, so the rim is closed
+                    MembraneShape::Drum { radius } => {
+                        x.hypot(y) <= radius.get::<meter>() + 0.5 * dx
+                    }
+                };
+            }
+        }
+        for j in 0..=cells_y {
+            for i in 0..=cells_x {
+                let index = simulation.index(i, j);
+                simulation.interior[index] = i > 0
+                    && j > 0
+                    && i < cells_x
+                    && j < cells_y
+                    && simulation.contained[index]
+                    && [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)]
+                        .iter()
+                        .all(|&(i, j)| simulation.contained[simulation.index(i, j)]);
+                if parameters.edge == MembraneEdge::Absorbing {
+                    let depth = simulation.edge_distance(i, j) / dx;
+                    let layer = ((SPONGE_CELLS - depth) / SPONGE_CELLS).clamp(0.0, 1.0);
+                    simulation.damping[index] = MAX_DAMPING * layer * layer;
+                }
+            }
+        }
+        simulation.driven = simulation
+            .sources
+            .iter()
+            .map(|source| simulation.covered_nodes(&source.geometry))
+            .collect();
+        simulation
+    }
+
+    /// cells along x and y
+    pub fn cells(&self) -> (usize, usize) {
+        self.cells
+    }
+
+    /// Δx = Δy
+    pub fn cell_size(&self) -> Length {
+        self.cell_size
+    }
+
+    /// Δt = SΔx/c
+    pub fn time_step(&self) -> Time {
+        self.cell_size * COURANT_NUMBER / self.parameters.wave_speed
+    }
+
+    /// simulated time, steps·Δt
+    pub fn time(&self) -> Time {
+        self.time_step() * self.steps as f32
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// position of node (i, j), the shape is centered at the origin
+    pub fn position(&self, i: usize, j: usize) -> (Length, Length) {
+        let (width, depth) = self.parameters.shape.size();
+        (
+            self.cell_size * i as f32 - width / 2.0,
+            self.cell_size * j as f32 - depth / 2.0,
+        )
+    }
+
+    /// whether node (i, j) is on the membrane, including its rim
+    pub fn contains(&self, i: usize, j: usize) -> bool {
+        self.contained[self.index(i, j)]
+    }
+
+    pub fn displacement(&self, i: usize, j: usize) -> Length {
+        Length::new::<meter>(self.u[self.index(i, j)])
+    }
+
+    /// largest |u| on the membrane
+    pub fn max_displacement(&self) -> Length {
+        Length::new::<meter>(self.u.iter().fold(0.0, |max: f32, u| max.max(u.abs())))
+    }
+
+    /// adds a gaussian bump of height `amplitude` and 1/e radius `width` at (x, y), at rest,
+    /// like a drop falling into a ripple tank
+    pub fn drop(&mut self, (x, y): (Length, Length), amplitude: Length, width: Length) {
+        let (x, y) = (x.get::<meter>(), y.get::<meter>());
+        let (amplitude, width) = (amplitude.get::<meter>(), width.get::<meter>());
+        if width <= 0.0 {
+            return;
+        }
+        let (cells_x, cells_y) = self.cells;
+        for j in 0..=cells_y {
+            for i in 0..=cells_x {
+                let index = self.index(i, j);
+                if !self.interior[index] {
+                    continue;
+                }
+                let (px, py) = self.position(i, j);
+                let distance_squared =
+                    (px.get::<meter>() - x).powi(2) + (py.get::<meter>() - y).powi(2);
+                let bump = amplitude * (-distance_squared / (width * width)).exp();
+                self.u[index] += bump;
+                self.u_previous[index] += bump;
+            }
+        }
+    }
+
+    /// advances until the simulated time reaches t, at most max_steps, returns the steps taken
+    pub fn advance_to(&mut self, t: Time, max_steps: usize) -> usize {
+        let dt = self.time_step();
+        let mut steps = 0;
+        while steps < max_steps && self.time() + dt <= t {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// (1 + γΔt/2)uⁿ⁺¹ = 2uⁿ − (1 − γΔt/2)uⁿ⁻¹ + S²(uᵢ₊₁ + uᵢ₋₁ + uⱼ₊₁ + uⱼ₋₁ − 4u), the rim stays at 0
+    /// and the sources set their nodes
+    pub fn step(&mut self) {
+        let (cells_x, _) = self.cells;
+        let row = cells_x + 1;
+        let courant_squared = COURANT_NUMBER * COURANT_NUMBER;
+        let mut next = vec![0.0; self.u.len()];
+        for (index, next) in next.iter_mut().enumerate() {
+            if !self.interior[index] {
+                continue;
+            }
+            let u = &self.u;
+            let laplacian =
+                u[index - 1] + u[index + 1] + u[index - row] + u[index + row] - 4.0 * u[index];
+            let damping = self.damping[index];
+            *next = (2.0 * u[index] - (1.0 - damping) * self.u_previous[index]
+                + courant_squared * laplacian)
+                / (1.0 + damping);
+        }
+        self.u_previous = std::mem::replace(&mut self.u, next);
+        self.steps += 1;
+
+        let t = self.time();
+        for (source, nodes) in self.sources.iter().zip(&self.driven) {
+            let displacement = source.displacement(t).get::<meter>();
+            for &index in nodes {
+                self.u[index] = displacement;
+            }
+        }
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        j * (self.cells.0 + 1) + i
+    }
+
+    /// distance in m from node (i, j) to the rim of the shape
+    fn edge_distance(&self, i: usize, j: usize) -> f32 {
+        let (x, y) = self.position(i, j);
+        let (x, y) = (x.get::<meter>(), y.get::<meter>());
+        match self.parameters.shape {
+            MembraneShape::Rectangle { width, depth } => {
+                let half_width = width.get::<meter>() / 2.0;
+                let half_depth = depth.get::<meter>() / 2.0;
+                (half_width - x.abs()).min(half_depth - y.abs())
+            }
+            MembraneShape::Drum { radius } => radius.get::<meter>() - x.hypot(y),
+        }
+    }
+
+    /// interior nodes closest to a point, or along a line sampled every half cell
+    fn covered_nodes(&self, geometry: &SourceGeometry) -> Vec<usize> {
+        let dx = self.cell_size.get::<meter>();
+        let points: Vec<(f32, f32)> = match *geometry {
+            SourceGeometry::Point { x, y } => vec![(x.get::<meter>(), y.get::<meter>())],
+            SourceGeometry::Line { from, to } => {
+                let (x1, y1) = (from.0.get::<meter>(), from.1.get::<meter>());
+                let (x2, y2) = (to.0.get::<meter>(), to.1.get::<meter>());
+                let samples = ((x2 - x1).hypot(y2 - y1) / (0.5 * dx)).ceil().max(1.0) as usize;
+                (0..=samples)
+                    .map(|k| {
+                        let s = k as f32 / samples as f32;
+                        (x1 + s * (x2 - x1), y1 + s * (y2 - y1))
+                    })
+                    .collect()
+            }
+        };
+        let (width, depth) = self.parameters.shape.size();
+        let (cells_x, cells_y) = self.cells;
+        let mut nodes: Vec<usize> = points
+            .into_iter()
+            .filter_map(|(x, y)| {
+                let i = ((x + width.get::<meter>() / 2.0) / dx).round();
+                let j = ((y + depth.get::<meter>() / 2.0) / dx).round();
+                if i < 0.0 || j < 0.0 || i > cells_x as f32 || j > cells_y as f32 {
+                    return None;
+                }
+                let index = self.index(i as usize, j as usize);
+                self.interior[index].then_some(index)
+            })
+            .collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        nodes
+    }
+}
+
+/// frequency of the lowest normal mode, c/2·√(1/a² + 1/b²) for a rectangle with fixed edges and
+/// j₀₁c/2πR for a drum, None with absorbing edges
+pub fn fundamental_frequency(parameters: &MembraneParameters) -> Option<Frequency> {
+    if parameters.edge == MembraneEdge::Absorbing {
+        return None;
+    }
+    let c = parameters.wave_speed.get::<meter_per_second>();
+    let f = match parameters.shape {
+        MembraneShape::Rectangle { width, depth } => {
+            0.5 * c * (width.get::<meter>().powi(-2) + depth.get::<meter>().powi(-2)).sqrt()
+        }
+        MembraneShape::Drum { radius } => {
+            /// first zero of the Bessel function J₀
+            const J01: f32 = 2.404_825_6;
+            J01 * c / (2.0 * PI * radius.get::<meter>())
+        }
+    };
+    Some(Frequency::new::<hertz>(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// starts the simulation at rest in the given mode shape, f(x, y) in m
+    fn excite(simulation: &mut MembraneSimulation, mode: impl Fn(f32, f32) -> f32) {
+        let (cells_x, cells_y) = simulation.cells();
+        for j in 0..=cells_y {
+            for i in 0..=cells_x {
+                let index = simulation.index(i, j);
+                if simulation.interior[index] {
+                    let (x, y) = simulation.position(i, j);
+                    simulation.u[index] = mode(x.get::<meter>(), y.get::<meter>());
+                }
+            }
+        }
+        simulation.u_previous = simulation.u.clone();
+    }
+
+    /// displacement at the center of the membrane after half a period of the fundamental mode,
+    /// relative to the start
+    fn after_half_a_period(parameters: MembraneParameters, mode: impl Fn(f32, f32) -> f32) -> f32 {
+        let mut simulation = MembraneSimulation::new(parameters, vec![], 100);
+        excite(&mut simulation, mode);
+        let (cells_x, cells_y) = simulation.cells();
+        let center = simulation
+            .displacement(cells_x / 2, cells_y / 2)
+            .get::<meter>();
+        let period = 1.0 / fundamental_frequency(&parameters).unwrap().get::<hertz>();
+        simulation.advance_to(Time::new::<second>(period / 2.0), usize::MAX);
+        simulation
+            .displacement(cells_x / 2, cells_y / 2)
+            .get::<meter>()
+            / center
+    }
+
+    #[test]
+    fn rectangle_oscillates_with_its_fundamental_frequency() {
+        let parameters = MembraneParameters {
+            shape: "rect 2 1".parse().unwrap(),
+            wave_speed: Velocity::new::<meter_per_second>(1.0),
+            edge: MembraneEdge::Fixed,
+        };
+        // sin(πx/a)sin(πy/b) with the origin in the middle
+        let relative = after_half_a_period(parameters, |x, y| {
+            (PI * (x / 2.0 + 0.5)).sin() * (PI * (y + 0.5)).sin()
+        });
+        assert!((relative + 1.0).abs() < 0.02, "{}", relative);
+    }
+
+    #[test]
+    fn drum_oscillates_with_its_fundamental_frequency() {
+        let parameters = MembraneParameters {
+            shape: "drum 1".parse().unwrap(),
+            wave_speed: Velocity::new::<meter_per_second>(1.0),
+            edge: MembraneEdge::Fixed,
+        };
+        // J₀(j₀₁r/R) from its series
+        let bessel = |x: f32| {
+            (0..12)
+                .scan(1.0, |term, m| {
+                    let current = *term;
+                    *term *= -(x / 2.0).powi(2) / ((m + 1) * (m + 1)) as f32;
+                    Some(current)
+                })
+                .sum::<f32>()
+        };
+        let relative = after_half_a_period(parameters, |x, y| bessel(2.404_825_6 * x.hypot(y)));
+        assert!((relative + 1.0).abs() < 0.05, "{}", relative);
+    }
+
+    #[test]
+    fn absorbing_edges_calm_the_surface() {
+        let parameters = MembraneParameters {
+            shape: "rect 4 4".parse().unwrap(),
+            wave_speed: Velocity::new::<meter_per_second>(1.0),
+            edge: MembraneEdge::Absorbing,
+        };
+        assert_eq!(fundamental_frequency(&parameters), None);
+        let mut simulation = MembraneSimulation::new(parameters, vec![], 100);
+        let origin = Length::new::<meter>(0.0);
+        simulation.drop(
+            (origin, origin),
+            Length::new::<meter>(1.0),
+            Length::new::<meter>(0.2),
+        );
+        simulation.advance_to(Time::new::<second>(8.0), usize::MAX);
+        assert!(simulation.max_displacement().get::<meter>() < 0.02);
+    }
+}
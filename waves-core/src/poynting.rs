@@ -0,0 +1,164 @@
+use glam::Vec3;
+use once_cell::sync::Lazy;
+use uom::si::{
+    electric_permittivity::farad_per_meter,
+    f32::{ElectricField, ElectricPermittivity, HeatFluxDensity, MagneticPermeability, Pressure},
+    heat_flux_density::watt_per_square_meter,
+    magnetic_permeability::henry_per_meter,
+};
+
+use crate::electromagnetic_wave::{
+    ElectricFieldVec3, JonesVector, MagneticFluxDensityVec3, SPEED_OF_LIGHT,
+};
+
+/// μ0
+pub static VACUUM_PERMEABILITY: Lazy<MagneticPermeability> =
+    Lazy::new(|| MagneticPermeability::new::<henry_per_meter>(1.256_637_1e-6));
+
+/// ε0
+pub static VACUUM_PERMITTIVITY: Lazy<ElectricPermittivity> =
+    Lazy::new(|| ElectricPermittivity::new::<farad_per_meter>(8.854_188e-12));
+
+/// J/m³, which has the dimension of a pressure (uom doesn't have a dedicated quantity)
+pub type EnergyDensity = Pressure;
+
+/// instantaneous energy flux, S = E × B / μ0
+pub fn calculate_poynting_vector(
+    electric: &ElectricFieldVec3,
+    magnetic: &MagneticFluxDensityVec3,
+) -> PoyntingVec3 {
+    let mu0 = *VACUUM_PERMEABILITY;
+    PoyntingVec3 {
+        x: (electric.y * magnetic.z - electric.z * magnetic.y) / mu0,
+        y: (electric.z * magnetic.x - electric.x * magnetic.z) / mu0,
+        z: (electric.x * magnetic.y - electric.y * magnetic.x) / mu0,
+    }
+}
+
+/// instantaneous energy density, u = ½(ε0E² + B²/μ0)
+pub fn calculate_energy_density(
+    electric: &ElectricFieldVec3,
+    magnetic: &MagneticFluxDensityVec3,
+) -> EnergyDensity {
+    let electric_squared =
+        electric.x * electric.x + electric.y * electric.y + electric.z * electric.z;
+    let magnetic_squared =
+        magnetic.x * magnetic.x + magnetic.y * magnetic.y + magnetic.z * magnetic.z;
+    (*VACUUM_PERMITTIVITY * electric_squared + magnetic_squared / *VACUUM_PERMEABILITY) / 2.0
+}
+
+/// time averaged magnitude of the poynting vector, I = ⟨S⟩ = ½cε0E0²(J₁² + J₂²)
+pub fn calculate_intensity(amplitude: ElectricField, jones: &JonesVector) -> HeatFluxDensity {
+    let jones_squared = jones.y * jones.y + jones.z * jones.z;
+    *SPEED_OF_LIGHT * *VACUUM_PERMITTIVITY * amplitude * amplitude * jones_squared / 2.0
+}
+
+/// time averaged energy density, ⟨u⟩ = I/c
+pub fn calculate_average_energy_density(intensity: HeatFluxDensity) -> EnergyDensity {
+    intensity / *SPEED_OF_LIGHT
+}
+
+/// pressure on a surface that absorbs the wave completely, P = I/c
+/// (twice as much for a surface that reflects it completely)
+pub fn calculate_radiation_pressure(intensity: HeatFluxDensity) -> Pressure {
+    intensity / *SPEED_OF_LIGHT
+}
+
+/// 3d vector with domain specific units
+#[derive(Debug, Clone, Copy)]
+pub struct PoyntingVec3 {
+    pub x: HeatFluxDensity,
+    pub y: HeatFluxDensity,
+    pub z: HeatFluxDensity,
+}
+
+/// f32 vec
+impl PoyntingVec3 {
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(
+            self.x.get::<watt_per_square_meter>(),
+            self.y.get::<watt_per_square_meter>(),
+            self.z.get::<watt_per_square_meter>(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::{
+        angle::radian,
+        electric_field::volt_per_meter,
+        f32::{Angle, Frequency, Length, Time},
+        frequency::hertz,
+        length::meter,
+        pressure::pascal,
+        time::second,
+        velocity::meter_per_second,
+    };
+
+    use super::*;
+    use crate::electromagnetic_wave::{
+        calculate_magnetic_field, calculate_u_polarized, ElectromagneticWaveParameters, Handedness,
+    };
+
+    fn fields(jones: &JonesVector, t: f32) -> (ElectricFieldVec3, MagneticFluxDensityVec3) {
+        let p = ElectromagneticWaveParameters {
+            amplitude: ElectricField::new::<volt_per_meter>(100.0),
+            wave_length: Length::new::<meter>(1.0),
+            frequency: Frequency::new::<hertz>(1.0),
+            phase: Angle::new::<radian>(0.0),
+        };
+        let x = Length::new::<meter>(0.2);
+        let electric =
+            calculate_u_polarized(x, Time::new::<second>(t), &p, jones, Vec3::Y, Vec3::Z);
+        let magnetic = calculate_magnetic_field(&electric, Vec3::X);
+        (electric, magnetic)
+    }
+
+    #[test]
+    fn poynting_vector_points_along_k_with_magnitude_c_times_energy_density() {
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        let jones = JonesVector::elliptical(1.0, 0.5, Angle::new::<radian>(0.4), Handedness::Right);
+        for t in [0.0, 0.1, 0.37] {
+            let (electric, magnetic) = fields(&jones, t);
+            let s = calculate_poynting_vector(&electric, &magnetic).to_vec3();
+            assert!(s.x > 0.0);
+            assert!(s.y.abs() < s.x * 1e-5 && s.z.abs() < s.x * 1e-5);
+            // |S| = cu, and |S| = E²/(cμ0) for a plane wave
+            let u = calculate_energy_density(&electric, &magnetic).get::<pascal>();
+            assert!(((s.length() - c * u) / s.length()).abs() < 1e-4);
+            let e_squared = electric.to_vec3().length_squared();
+            let mu0 = VACUUM_PERMEABILITY.get::<henry_per_meter>();
+            assert!(((s.length() - e_squared / (c * mu0)) / s.length()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn intensity_is_the_average_of_the_poynting_vector() {
+        let jones = JonesVector::elliptical(1.0, 0.5, Angle::new::<radian>(0.4), Handedness::Left);
+        let steps = 200;
+        let average = (0..steps)
+            .map(|i| {
+                let (electric, magnetic) = fields(&jones, i as f32 / steps as f32);
+                calculate_poynting_vector(&electric, &magnetic).to_vec3().x
+            })
+            .sum::<f32>()
+            / steps as f32;
+        let amplitude = ElectricField::new::<volt_per_meter>(100.0);
+        let intensity = calculate_intensity(amplitude, &jones).get::<watt_per_square_meter>();
+        assert!(((average - intensity) / intensity).abs() < 1e-3);
+        // ½cε0E0² with J₁² + J₂² = 1.25, about 16.6 W/m²
+        assert!((intensity - 16.6).abs() < 0.1);
+
+        let c = SPEED_OF_LIGHT.get::<meter_per_second>();
+        let intensity = calculate_intensity(amplitude, &jones);
+        let pressure = calculate_radiation_pressure(intensity).get::<pascal>();
+        assert!(
+            ((pressure * c - intensity.get::<watt_per_square_meter>()) / pressure / c).abs() < 1e-5
+        );
+        assert_eq!(
+            calculate_average_energy_density(intensity),
+            calculate_radiation_pressure(intensity)
+        );
+    }
+}
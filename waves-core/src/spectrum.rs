@@ -0,0 +1,187 @@
+use std::{f32::consts::PI, fmt};
+
+use uom::si::{angle::radian, f32::Angle};
+
+/// weights applied to the samples before the transform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Window {
+    /// the samples as they are, exact for a whole number of periods
+    #[default]
+    Rectangular,
+    /// sin² taper to 0 at both ends, less leakage for other frequencies but wider lines
+    Hann,
+}
+
+impl Window {
+    pub fn next(self) -> Window {
+        match self {
+            Window::Rectangular => Window::Hann,
+            Window::Hann => Window::Rectangular,
+        }
+    }
+
+    fn weight(&self, i: usize, count: usize) -> f32 {
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann if count > 1 => (PI * i as f32 / (count - 1) as f32).sin().powi(2),
+            Window::Hann => 1.0,
+        }
+    }
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Window::Rectangular => write!(f, "rectangular"),
+            Window::Hann => write!(f, "hann"),
+        }
+    }
+}
+
+/// a line of a one sided spectrum, the samples contain magnitude·cos(2πνs + phase)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumLine {
+    /// cycles per unit of the sample spacing, e.g. 1/m for samples along x or hz for samples in
+    /// time
+    pub frequency: f32,
+    pub magnitude: f32,
+    /// at the first sample
+    pub phase: Angle,
+}
+
+/// one sided spectrum of samples `spacing` apart, zero padded to a power of two
+/// the magnitudes are divided by the sum of the window, so a sinusoid with amplitude A whose
+/// frequency is on a line has magnitude A there
+pub fn spectrum(samples: &[f32], spacing: f32, window: Window) -> Vec<SpectrumLine> {
+    if samples.is_empty() || spacing <= 0.0 {
+        return vec![];
+    }
+    let count = samples.len();
+    let size = count.next_power_of_two();
+    let mut re = vec![0.0; size];
+    let mut im = vec![0.0; size];
+    let mut window_sum = 0.0;
+    for (i, sample) in samples.iter().enumerate() {
+        let weight = window.weight(i, count);
+        re[i] = sample * weight;
+        window_sum += weight;
+    }
+    fft(&mut re, &mut im);
+
+    (0..=size / 2)
+        .map(|k| {
+            // the negative frequencies fold onto the positive ones, except the mean and Nyquist
+            let one_sided = if k == 0 || k == size / 2 { 1.0 } else { 2.0 };
+            SpectrumLine {
+                frequency: k as f32 / (size as f32 * spacing),
+                magnitude: one_sided * re[k].hypot(im[k]) / window_sum,
+                phase: Angle::new::<radian>(im[k].atan2(re[k])),
+            }
+        })
+        .collect()
+}
+
+/// the line with the largest magnitude, not counting the mean
+pub fn dominant(lines: &[SpectrumLine]) -> Option<&SpectrumLine> {
+    lines
+        .iter()
+        .skip(1)
+        .max_by(|a, b| a.magnitude.total_cmp(&b.magnitude))
+}
+
+/// `count` values evenly spaced from the first to the last point, linearly interpolated
+/// the points are (position, value), sorted by position, e.g. samples taken once per frame
+pub fn resample(points: &[(f32, f32)], count: usize) -> Vec<f32> {
+    let (Some(&(start, _)), Some(&(end, _))) = (points.first(), points.last()) else {
+        return vec![];
+    };
+    let step = if count > 1 {
+        (end - start) / (count - 1) as f32
+    } else {
+        0.0
+    };
+    let mut index = 0;
+    (0..count)
+        .map(|i| {
+            let position = start + i as f32 * step;
+            while index + 2 < points.len() && points[index + 1].0 < position {
+                index += 1;
+            }
+            let (p0, v0) = points[index];
+            let Some(&(p1, v1)) = points.get(index + 1) else {
+                return v0;
+            };
+            if p1 > p0 {
+                v0 + (v1 - v0) * ((position - p0) / (p1 - p0)).clamp(0.0, 1.0)
+            } else {
+                v0
+            }
+        })
+        .collect()
+}
+
+/// in place radix 2 Cooley-Tukey, X_k = Σ x_n e^(−2πikn/N), the length must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let size = re.len();
+    if size < 2 {
+        return;
+    }
+    let bits = size.trailing_zeros();
+    for i in 0..size {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if j > i {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut length = 2;
+    while length <= size {
+        let angle = -2.0 * PI / length as f32;
+        for start in (0..size).step_by(length) {
+            for k in 0..length / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + length / 2);
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        length *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine_peaks_at_its_line() {
+        let count = 64;
+        let spacing = 0.1;
+        let samples: Vec<f32> = (0..count)
+            .map(|i| 0.5 + 1.5 * (2.0 * PI * 5.0 * i as f32 / count as f32 + 0.7).cos())
+            .collect();
+        let lines = spectrum(&samples, spacing, Window::Rectangular);
+
+        assert!((lines[0].magnitude - 0.5).abs() < 1e-5);
+        let line = lines[5];
+        assert!((line.frequency - 5.0 / (count as f32 * spacing)).abs() < 1e-5);
+        assert!((line.magnitude - 1.5).abs() < 1e-4);
+        assert!((line.phase.get::<radian>() - 0.7).abs() < 1e-4);
+        for (i, other) in lines.iter().enumerate().skip(1) {
+            if i != 5 {
+                assert!(other.magnitude < 1e-4, "line {} is {}", i, other.magnitude);
+            }
+        }
+        assert_eq!(dominant(&lines), Some(&line));
+    }
+
+    #[test]
+    fn no_samples_no_lines() {
+        assert!(spectrum(&[], 1.0, Window::Hann).is_empty());
+        assert!(spectrum(&[1.0], 0.0, Window::Rectangular).is_empty());
+    }
+}
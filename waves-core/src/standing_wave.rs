@@ -0,0 +1,169 @@
+use std::f32::consts::PI;
+
+use glam::Vec3;
+use uom::si::{
+    angle::radian,
+    f32::{Angle, Frequency, Length, Time, Velocity},
+    length::meter,
+};
+
+use crate::wave::{calculate_u_superposition, LengthVec3, PropagationDirection, WaveParameters};
+
+/// how the ends of the medium (e.g. a string) at x=0 and x=L behave
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BoundaryConditions {
+    /// both ends can't move, L=nλ/2
+    #[default]
+    FixedFixed,
+    /// x=0 can't move, x=L moves freely, L=(2n−1)λ/4
+    FixedFree,
+}
+
+/// standing wave in a medium of length L, built from a forward wave and its reflection
+#[derive(Debug, Clone, Copy)]
+pub struct StandingWaveParameters {
+    pub amplitude: Length,
+    pub length: Length,
+    pub wave_speed: Velocity,
+    /// n, starting at 1
+    pub harmonic: u32,
+    pub boundary_conditions: BoundaryConditions,
+}
+
+impl StandingWaveParameters {
+    pub fn wave_length(&self) -> Length {
+        let n = self.harmonic.max(1) as f32;
+        match self.boundary_conditions {
+            BoundaryConditions::FixedFixed => self.length * 2.0 / n,
+            BoundaryConditions::FixedFree => self.length * 4.0 / (2.0 * n - 1.0),
+        }
+    }
+
+    /// v = fλ
+    pub fn frequency(&self) -> Frequency {
+        self.wave_speed / self.wave_length()
+    }
+
+    /// wave travelling towards +x
+    pub fn forward_wave(&self) -> WaveParameters {
+        WaveParameters {
+            amplitude: self.amplitude,
+            wave_length: self.wave_length(),
+            frequency: self.frequency(),
+            phase: Angle::new::<radian>(0.0),
+            direction: PropagationDirection::Forward,
+        }
+    }
+
+    /// the forward wave reflected at the fixed end x=0, i.e. inverted
+    pub fn reflected_wave(&self) -> WaveParameters {
+        WaveParameters {
+            phase: Angle::new::<radian>(PI),
+            direction: PropagationDirection::Backward,
+            ..self.forward_wave()
+        }
+    }
+
+    /// positions in [0, L] that don't move, x = mλ/2
+    pub fn nodes(&self) -> Vec<Length> {
+        self.positions_in_medium(Length::new::<meter>(0.0))
+    }
+
+    /// positions in [0, L] with maximum displacement, x = λ/4 + mλ/2
+    pub fn antinodes(&self) -> Vec<Length> {
+        self.positions_in_medium(self.wave_length() / 4.0)
+    }
+
+    /// offset + mλ/2 for all m that are inside the medium
+    fn positions_in_medium(&self, offset: Length) -> Vec<Length> {
+        let half_wave_length = self.wave_length() / 2.0;
+        // tolerance for positions that fall exactly on x=L
+        let tolerance = half_wave_length * 0.001;
+
+        let mut positions = vec![];
+        let mut x = offset;
+        while x <= self.length + tolerance {
+            positions.push(x);
+            x += half_wave_length;
+        }
+        positions
+    }
+}
+
+/// u(x,t) = Acos(kx−ωt) − Acos(kx+ωt) = 2Asin(kx)sin(ωt)
+pub fn calculate_u_standing(
+    x: Length,
+    t: Time,
+    p: &StandingWaveParameters,
+    direction: Vec3,
+) -> LengthVec3 {
+    calculate_u_superposition(x, t, &[p.forward_wave(), p.reflected_wave()], direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::{time::second, velocity::meter_per_second};
+
+    use super::*;
+
+    fn standing(harmonic: u32, boundary_conditions: BoundaryConditions) -> StandingWaveParameters {
+        StandingWaveParameters {
+            amplitude: Length::new::<meter>(0.5),
+            length: Length::new::<meter>(3.0),
+            wave_speed: Velocity::new::<meter_per_second>(6.0),
+            harmonic,
+            boundary_conditions,
+        }
+    }
+
+    /// largest |u| at x over a period
+    fn max_displacement(p: &StandingWaveParameters, x: Length) -> f32 {
+        let period = 1.0 / p.frequency().value;
+        (0..64)
+            .map(|i| {
+                let t = Time::new::<second>(period * i as f32 / 64.0);
+                calculate_u_standing(x, t, p, Vec3::Y)
+                    .y
+                    .get::<meter>()
+                    .abs()
+            })
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn nodes_dont_move_and_antinodes_reach_twice_the_amplitude() {
+        for p in [
+            standing(3, BoundaryConditions::FixedFixed),
+            standing(2, BoundaryConditions::FixedFree),
+        ] {
+            for node in p.nodes() {
+                assert!(max_displacement(&p, node) < 1e-5, "node at {:?}", node);
+            }
+            for antinode in p.antinodes() {
+                let max = max_displacement(&p, antinode);
+                assert!(
+                    (max - 1.0).abs() < 1e-3,
+                    "antinode at {:?}: {}",
+                    antinode,
+                    max
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nodes_and_antinodes_fit_the_boundary_conditions() {
+        // L = 3λ/2: nodes at both ends
+        let fixed = standing(3, BoundaryConditions::FixedFixed);
+        assert_eq!(fixed.nodes().len(), 4);
+        assert_eq!(fixed.antinodes().len(), 3);
+        assert!((fixed.nodes()[3] - fixed.length).value.abs() < 1e-5);
+
+        // L = 3λ/4: node at x = 0, antinode at the free end
+        let free = standing(2, BoundaryConditions::FixedFree);
+        assert_eq!(free.nodes().len(), 2);
+        assert_eq!(free.antinodes().len(), 2);
+        assert!((free.antinodes()[1] - free.length).value.abs() < 1e-5);
+        assert!((free.frequency().value - 1.5).abs() < 1e-5);
+    }
+}
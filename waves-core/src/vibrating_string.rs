@@ -0,0 +1,514 @@
+use std::{f32::consts::PI, fmt, str::FromStr};
+
+use uom::si::{
+    energy::joule,
+    f32::{Energy, Force, Frequency, Length, LinearMassDensity, Time, Velocity},
+    force::newton,
+    length::meter,
+    linear_mass_density::kilogram_per_meter,
+    time::second,
+    velocity::meter_per_second,
+};
+
+use crate::wave::PropagationDirection;
+
+/// S = cΔt/Δx, at 1 the leapfrog scheme is exact for a uniform string, i.e. it reproduces
+/// d'Alembert's solution on the grid points
+pub const COURANT_NUMBER: f32 = 1.0;
+
+/// string under tension, u_tt = c²u_xx with c = √(T/μ)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StringParameters {
+    pub length: Length,
+    pub tension: Force,
+    pub linear_density: LinearMassDensity,
+}
+
+impl StringParameters {
+    /// c = √(T/μ)
+    pub fn wave_speed(&self) -> Velocity {
+        let speed_squared =
+            self.tension.get::<newton>() / self.linear_density.get::<kilogram_per_meter>();
+        Velocity::new::<meter_per_second>(speed_squared.sqrt())
+    }
+
+    /// f₁ of the normal modes, c/2L if both ends are alike, c/4L if one is fixed and the other free
+    /// None with an absorbing end, the string has no normal modes then
+    pub fn fundamental_frequency(&self, left: StringEnd, right: StringEnd) -> Option<Frequency> {
+        let wave_lengths = match (left, right) {
+            (StringEnd::Absorbing, _) | (_, StringEnd::Absorbing) => return None,
+            (StringEnd::Fixed, StringEnd::Fixed) | (StringEnd::Free, StringEnd::Free) => 2.0,
+            _ => 4.0,
+        };
+        Some(self.wave_speed() / (self.length * wave_lengths))
+    }
+}
+
+/// how an end of the string behaves
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StringEnd {
+    /// u = 0, reflects inverted
+    #[default]
+    Fixed,
+    /// ∂u/∂x = 0, e.g. a ring sliding on a rod, reflects upright
+    Free,
+    /// lets waves leave the string (first order Mur condition), like an infinitely long string
+    Absorbing,
+}
+
+impl StringEnd {
+    /// fixed, free, absorbing, fixed, ...
+    pub fn next(&self) -> StringEnd {
+        match self {
+            StringEnd::Fixed => StringEnd::Free,
+            StringEnd::Free => StringEnd::Absorbing,
+            StringEnd::Absorbing => StringEnd::Fixed,
+        }
+    }
+}
+
+impl fmt::Display for StringEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringEnd::Fixed => write!(f, "fixed"),
+            StringEnd::Free => write!(f, "free"),
+            StringEnd::Absorbing => write!(f, "absorbing"),
+        }
+    }
+}
+
+/// profile along the string, relative to an amplitude, evaluated at the fraction x/L
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialShape {
+    Flat,
+    /// triangle with its peak at the fraction `position`, like a plucked guitar string
+    Pluck {
+        position: f32,
+    },
+    /// e^(−((x/L − center)/width)²)
+    Gaussian {
+        center: f32,
+        width: f32,
+    },
+    /// sin(nπx/L), the n-th normal mode of a string with fixed ends
+    Sine {
+        mode: u32,
+    },
+    /// points (x/L, relative value), sorted by x/L and linearly interpolated, 0 outside
+    Drawn(Vec<(f32, f32)>),
+}
+
+impl InitialShape {
+    /// relative value at the fraction x/L in [0, 1]
+    pub fn value(&self, fraction: f32) -> f32 {
+        match self {
+            InitialShape::Flat => 0.0,
+            InitialShape::Pluck { position } => {
+                if fraction <= *position {
+                    if *position > 0.0 {
+                        fraction / position
+                    } else {
+                        1.0
+                    }
+                } else if *position < 1.0 {
+                    (1.0 - fraction) / (1.0 - position)
+                } else {
+                    1.0
+                }
+            }
+            InitialShape::Gaussian { center, width } => {
+                (-((fraction - center) / width).powi(2)).exp()
+            }
+            InitialShape::Sine { mode } => (*mode as f32 * PI * fraction).sin(),
+            InitialShape::Drawn(points) => interpolate(points, fraction),
+        }
+    }
+}
+
+/// linear interpolation between points sorted by their first value, 0 outside
+fn interpolate(points: &[(f32, f32)], x: f32) -> f32 {
+    let after = points.partition_point(|&(px, _)| px < x);
+    match (after.checked_sub(1).map(|i| points[i]), points.get(after)) {
+        (Some((x0, u0)), Some(&(x1, u1))) => {
+            if x1 > x0 {
+                u0 + (u1 - u0) * (x - x0) / (x1 - x0)
+            } else {
+                u1
+            }
+        }
+        // exactly on the first point
+        (None, Some(&(x1, u1))) if x1 == x => u1,
+        _ => 0.0,
+    }
+}
+
+impl fmt::Display for InitialShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitialShape::Flat => write!(f, "flat"),
+            InitialShape::Pluck { position } => write!(f, "pluck {}", position),
+            InitialShape::Gaussian { center, width } => write!(f, "gaussian {} {}", center, width),
+            InitialShape::Sine { mode } => write!(f, "sine {}", mode),
+            InitialShape::Drawn(_) => write!(f, "drawn"),
+        }
+    }
+}
+
+/// "flat", "pluck <x/L>", "gaussian <center x/L> <width x/L>" or "sine <n>"
+/// drawn shapes have no text form
+impl FromStr for InitialShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let name = parts.next().ok_or_else(|| "Shape is empty".to_owned())?;
+        let values = parts
+            .map(|part| {
+                part.parse::<f32>()
+                    .map_err(|e| format!("Invalid value {}: {}", part, e))
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+        let fraction = |value: f32| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(value)
+            } else {
+                Err(format!("Positions are fractions of the length: {}", value))
+            }
+        };
+        match (name, &values[..]) {
+            ("flat", []) => Ok(InitialShape::Flat),
+            ("pluck", [position]) => Ok(InitialShape::Pluck {
+                position: fraction(*position)?,
+            }),
+            ("gaussian", [center, width]) if *width > 0.0 => Ok(InitialShape::Gaussian {
+                center: fraction(*center)?,
+                width: *width,
+            }),
+            ("gaussian", [_, width]) => Err(format!("Width must be positive: {}", width)),
+            ("sine", [mode]) if *mode >= 1.0 && mode.fract() == 0.0 => {
+                Ok(InitialShape::Sine { mode: *mode as u32 })
+            }
+            ("sine", [mode]) => Err(format!("Mode must be an integer >= 1: {}", mode)),
+            ("drawn", _) => Err("Drawn shapes are set with the mouse".to_owned()),
+            ("flat" | "pluck" | "gaussian" | "sine", _) => {
+                Err(format!("Wrong number of values for {}", name))
+            }
+            _ => Err(format!("Unknown shape: {}", name)),
+        }
+    }
+}
+
+/// velocity of the string at t = 0
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialVelocity {
+    AtRest,
+    /// the initial displacement travels along the string without changing its shape,
+    /// u_t = ∓cu_x
+    Travelling(PropagationDirection),
+    /// e.g. a string struck by a hammer
+    Profile {
+        shape: InitialShape,
+        amplitude: Velocity,
+    },
+}
+
+impl fmt::Display for InitialVelocity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitialVelocity::AtRest => write!(f, "rest"),
+            InitialVelocity::Travelling(PropagationDirection::Forward) => write!(f, "right"),
+            InitialVelocity::Travelling(PropagationDirection::Backward) => write!(f, "left"),
+            InitialVelocity::Profile { shape, amplitude } => {
+                write!(f, "{} {}", amplitude.get::<meter_per_second>(), shape)
+            }
+        }
+    }
+}
+
+/// "rest", "right", "left", or an amplitude in m/s followed by a shape, e.g. "2 gaussian 0.5 0.05"
+impl FromStr for InitialVelocity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s {
+            "rest" => Ok(InitialVelocity::AtRest),
+            "right" => Ok(InitialVelocity::Travelling(PropagationDirection::Forward)),
+            "left" => Ok(InitialVelocity::Travelling(PropagationDirection::Backward)),
+            _ => {
+                let (amplitude, shape) = s
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("Unknown velocity: {}", s))?;
+                let amplitude = amplitude
+                    .parse::<f32>()
+                    .map_err(|_| format!("Unknown velocity: {}", s))?;
+                Ok(InitialVelocity::Profile {
+                    shape: shape.parse()?,
+                    amplitude: Velocity::new::<meter_per_second>(amplitude),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialConditions {
+    pub shape: InitialShape,
+    pub amplitude: Length,
+    pub velocity: InitialVelocity,
+}
+
+/// finite-difference solution of u_tt = c²u_xx on a string, leapfrog in time, central differences in
+/// space, see e.g. Langtangen, Finite Difference Computing with PDEs, chapter 2
+pub struct StringSimulation {
+    pub parameters: StringParameters,
+    pub left_end: StringEnd,
+    pub right_end: StringEnd,
+    steps: usize,
+    /// displacement in m at the grid points, at the previous and the current step
+    u_previous: Vec<f32>,
+    u: Vec<f32>,
+}
+
+impl StringSimulation {
+    /// `segments` + 1 grid points from x = 0 to x = L
+    pub fn new(
+        parameters: StringParameters,
+        (left_end, right_end): (StringEnd, StringEnd),
+        initial: &InitialConditions,
+        segments: usize,
+    ) -> Self {
+        let segments = segments.max(2);
+        let amplitude = initial.amplitude.get::<meter>();
+        let u0: Vec<f32> = (0..=segments)
+            .map(|i| amplitude * initial.shape.value(i as f32 / segments as f32))
+            .collect();
+
+        let mut simulation = StringSimulation {
+            parameters,
+            left_end,
+            right_end,
+            steps: 0,
+            u_previous: u0.clone(),
+            u: u0,
+        };
+        simulation.apply_fixed_ends();
+
+        let dx = simulation.dx().get::<meter>();
+        let c = parameters.wave_speed().get::<meter_per_second>();
+        let velocity: Vec<f32> = match &initial.velocity {
+            InitialVelocity::AtRest => vec![0.0; segments + 1],
+            InitialVelocity::Travelling(direction) => (0..=segments)
+                .map(|i| {
+                    // u(x ∓ ct): u_t = ∓cu_x, central differences inside, one-sided at the ends
+                    let (before, after) = (i.saturating_sub(1), (i + 1).min(segments));
+                    let slope = (simulation.u[after] - simulation.u[before])
+                        / (dx * (after - before) as f32);
+                    direction.time_sign() * c * slope
+                })
+                .collect(),
+            InitialVelocity::Profile { shape, amplitude } => (0..=segments)
+                .map(|i| {
+                    amplitude.get::<meter_per_second>() * shape.value(i as f32 / segments as f32)
+                })
+                .collect(),
+        };
+
+        // first step from the Taylor expansion u¹ = u⁰ + Δt·v + ½Δt²c²u_xx
+        let dt = simulation.time_step().get::<second>();
+        let courant_squared = COURANT_NUMBER * COURANT_NUMBER;
+        let first: Vec<f32> = (0..=segments)
+            .map(|i| {
+                simulation.u[i]
+                    + dt * velocity[i]
+                    + 0.5 * courant_squared * simulation.second_difference(&simulation.u, i)
+            })
+            .collect();
+        simulation.u = first;
+        simulation.apply_fixed_ends();
+        simulation.steps = 1;
+        simulation
+    }
+
+    /// Δx = L/segments
+    pub fn dx(&self) -> Length {
+        self.parameters.length / self.segments() as f32
+    }
+
+    /// Δt = SΔx/c
+    pub fn time_step(&self) -> Time {
+        self.dx() * COURANT_NUMBER / self.parameters.wave_speed()
+    }
+
+    pub fn segments(&self) -> usize {
+        self.u.len() - 1
+    }
+
+    /// simulated time, steps·Δt
+    pub fn time(&self) -> Time {
+        self.time_step() * self.steps as f32
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// position of grid point i, from the left end
+    pub fn x(&self, i: usize) -> Length {
+        self.dx() * i as f32
+    }
+
+    pub fn displacement(&self, i: usize) -> Length {
+        Length::new::<meter>(self.u[i])
+    }
+
+    /// kinetic plus potential energy, ½μu_t² + ½Tu_x² integrated along the string
+    /// conserved with fixed and free ends up to the discretization, decreasing with absorbing ones
+    pub fn energy(&self) -> Energy {
+        let dx = self.dx().get::<meter>();
+        let dt = self.time_step().get::<second>();
+        let mu = self.parameters.linear_density.get::<kilogram_per_meter>();
+        let tension = self.parameters.tension.get::<newton>();
+        let kinetic: f32 = self
+            .u
+            .iter()
+            .zip(&self.u_previous)
+            .map(|(u, previous)| 0.5 * mu * ((u - previous) / dt).powi(2) * dx)
+            .sum();
+        let potential: f32 = self
+            .u
+            .windows(2)
+            .map(|pair| 0.5 * tension * ((pair[1] - pair[0]) / dx).powi(2) * dx)
+            .sum();
+        Energy::new::<joule>(kinetic + potential)
+    }
+
+    /// advances until the simulated time reaches t, at most max_steps, returns the steps taken
+    pub fn advance_to(&mut self, t: Time, max_steps: usize) -> usize {
+        let dt = self.time_step();
+        let mut steps = 0;
+        while steps < max_steps && self.time() + dt <= t {
+            self.step();
+            steps += 1;
+        }
+        steps
+    }
+
+    /// uⁿ⁺¹ = 2uⁿ − uⁿ⁻¹ + S²(uᵢ₊₁ − 2uᵢ + uᵢ₋₁)
+    pub fn step(&mut self) {
+        let last = self.segments();
+        let courant_squared = COURANT_NUMBER * COURANT_NUMBER;
+        let mut next: Vec<f32> = (0..=last)
+            .map(|i| {
+                2.0 * self.u[i] - self.u_previous[i]
+                    + courant_squared * self.second_difference(&self.u, i)
+            })
+            .collect();
+
+        // Mur: uₙ₊₁ at the end from the wave arriving from its neighbour
+        let mur = (COURANT_NUMBER - 1.0) / (COURANT_NUMBER + 1.0);
+        if self.left_end == StringEnd::Absorbing {
+            next[0] = self.u[1] + mur * (next[1] - self.u[0]);
+        }
+        if self.right_end == StringEnd::Absorbing {
+            next[last] = self.u[last - 1] + mur * (next[last - 1] - self.u[last]);
+        }
+
+        self.u_previous = std::mem::replace(&mut self.u, next);
+        self.apply_fixed_ends();
+        self.steps += 1;
+    }
+
+    /// uᵢ₊₁ − 2uᵢ + uᵢ₋₁, mirrored at the ends (∂u/∂x = 0), fixed ends are reset afterwards and
+    /// absorbing ones replaced by the Mur condition
+    fn second_difference(&self, u: &[f32], i: usize) -> f32 {
+        let last = u.len() - 1;
+        let left = if i == 0 { u[1] } else { u[i - 1] };
+        let right = if i == last { u[last - 1] } else { u[i + 1] };
+        left - 2.0 * u[i] + right
+    }
+
+    fn apply_fixed_ends(&mut self) {
+        let last = self.segments();
+        if self.left_end == StringEnd::Fixed {
+            self.u[0] = 0.0;
+        }
+        if self.right_end == StringEnd::Fixed {
+            self.u[last] = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plucked(left: StringEnd, right: StringEnd) -> StringSimulation {
+        let parameters = StringParameters {
+            length: Length::new::<meter>(1.0),
+            tension: Force::new::<newton>(100.0),
+            linear_density: LinearMassDensity::new::<kilogram_per_meter>(0.01),
+        };
+        let initial = InitialConditions {
+            shape: InitialShape::Pluck { position: 0.3 },
+            amplitude: Length::new::<meter>(0.01),
+            velocity: InitialVelocity::AtRest,
+        };
+        StringSimulation::new(parameters, (left, right), &initial, 100)
+    }
+
+    #[test]
+    fn energy_stays_bounded_with_fixed_ends() {
+        let mut simulation = plucked(StringEnd::Fixed, StringEnd::Fixed);
+        let initial = simulation.energy().get::<joule>();
+        assert!(initial > 0.0);
+        // 2L/c per period, 20 periods
+        for _ in 0..20 * 200 {
+            simulation.step();
+            let energy = simulation.energy().get::<joule>();
+            assert!(
+                ((energy - initial) / initial).abs() < 0.05,
+                "energy {} at step {}, started at {}",
+                energy,
+                simulation.steps(),
+                initial
+            );
+        }
+    }
+
+    #[test]
+    fn pluck_returns_after_a_period() {
+        let mut simulation = plucked(StringEnd::Fixed, StringEnd::Fixed);
+        let start: Vec<f32> = (0..=100)
+            .map(|i| simulation.displacement(i).value)
+            .collect();
+        // 2L/c = 200Δt at S = 1
+        for _ in 0..200 {
+            simulation.step();
+        }
+        for (i, start) in start.iter().enumerate() {
+            assert!((simulation.displacement(i).value - start).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn absorbing_ends_take_the_energy() {
+        let parameters = plucked(StringEnd::Fixed, StringEnd::Fixed).parameters;
+        let initial = InitialConditions {
+            shape: InitialShape::Gaussian {
+                center: 0.5,
+                width: 0.05,
+            },
+            amplitude: Length::new::<meter>(0.01),
+            velocity: InitialVelocity::AtRest,
+        };
+        let ends = (StringEnd::Absorbing, StringEnd::Absorbing);
+        let mut simulation = StringSimulation::new(parameters, ends, &initial, 100);
+        let start = simulation.energy();
+        // the halves need L/2c to reach the ends
+        for _ in 0..100 {
+            simulation.step();
+        }
+        assert!(simulation.energy() < start * 1e-3);
+    }
+}
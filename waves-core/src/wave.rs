@@ -0,0 +1,278 @@
+use std::f32::consts::PI;
+
+use glam::Vec3;
+use uom::si::{
+    angle::radian,
+    f32::{Angle, Frequency, Length, Time},
+    frequency::hertz,
+    length::meter,
+    time::second,
+};
+
+/// direction of travel along the x axis
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PropagationDirection {
+    /// +x, u(x,t)=Acos(kx−ωt+φ)
+    #[default]
+    Forward,
+    /// −x, u(x,t)=Acos(kx+ωt+φ)
+    Backward,
+}
+
+impl PropagationDirection {
+    /// sign of the ωt term in the wave function
+    pub fn time_sign(&self) -> f32 {
+        match self {
+            PropagationDirection::Forward => -1.0,
+            PropagationDirection::Backward => 1.0,
+        }
+    }
+}
+
+/// direction of the wave vector k in 3d, as spherical angles
+/// θ is measured from +z, φ from +x towards +y, so θ=90°, φ=0 is +x
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SphericalDirection {
+    pub theta: Angle,
+    pub phi: Angle,
+}
+
+impl Default for SphericalDirection {
+    /// +x, like the waves that only have a scalar x
+    fn default() -> Self {
+        SphericalDirection {
+            theta: Angle::new::<radian>(PI / 2.0),
+            phi: Angle::new::<radian>(0.0),
+        }
+    }
+}
+
+impl SphericalDirection {
+    /// k̂
+    pub fn unit_vector(&self) -> Vec3 {
+        let (theta, phi) = (self.theta.get::<radian>(), self.phi.get::<radian>());
+        Vec3::new(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        )
+    }
+
+    /// two unit vectors perpendicular to k̂ and each other, with e₁ × e₂ = k̂
+    /// e₁ = φ̂, e₂ = −θ̂, i.e. (y, z) for a wave along +x
+    pub fn transverse_unit_vectors(&self) -> (Vec3, Vec3) {
+        let (theta, phi) = (self.theta.get::<radian>(), self.phi.get::<radian>());
+        let e1 = Vec3::new(-phi.sin(), phi.cos(), 0.0);
+        let e2 = Vec3::new(
+            -theta.cos() * phi.cos(),
+            -theta.cos() * phi.sin(),
+            theta.sin(),
+        );
+        (e1, e2)
+    }
+}
+
+/// parameters of a mechanical wave, i.e. the amplitude is a displacement
+#[derive(Debug, Clone, Copy)]
+pub struct WaveParameters {
+    pub amplitude: Length,
+    pub wave_length: Length,
+    pub frequency: Frequency,
+    pub phase: Angle,
+    pub direction: PropagationDirection,
+}
+
+/// to share wave calculation for different domains (currently electromagnetic / non electromagnetic)
+/// lacks some domain specific units and uses just numbers instead
+/// not meant to be instantiated directly
+#[derive(Debug, Clone, Copy)]
+pub struct RawWaveParameters {
+    pub amplitude: f32,
+    pub wave_length: Length,
+    pub frequency: Frequency,
+    pub phase: Angle,
+    pub direction: PropagationDirection,
+}
+
+impl From<WaveParameters> for RawWaveParameters {
+    fn from(p: WaveParameters) -> Self {
+        RawWaveParameters {
+            amplitude: p.amplitude.get::<meter>(),
+            wave_length: p.wave_length,
+            frequency: p.frequency,
+            phase: p.phase,
+            direction: p.direction,
+        }
+    }
+}
+
+pub fn calculate_u(x: Length, t: Time, p: &WaveParameters, direction: Vec3) -> LengthVec3 {
+    let raw = calculate_u_raw(x, t, &(*p).into(), direction);
+    // assumption: raw amplitude passed to calculate_u_raw (RawWaveParameters) was in meter
+    LengthVec3 {
+        x: Length::new::<meter>(raw.x),
+        y: Length::new::<meter>(raw.y),
+        z: Length::new::<meter>(raw.z),
+    }
+}
+
+/// superposition principle: the displacement caused by several waves is the sum of their displacements
+pub fn calculate_u_superposition(
+    x: Length,
+    t: Time,
+    waves: &[WaveParameters],
+    direction: Vec3,
+) -> LengthVec3 {
+    let raw: Vec3 = waves
+        .iter()
+        .map(|p| calculate_u_raw(x, t, &(*p).into(), direction))
+        .sum();
+    LengthVec3 {
+        x: Length::new::<meter>(raw.x),
+        y: Length::new::<meter>(raw.y),
+        z: Length::new::<meter>(raw.z),
+    }
+}
+
+/// 3d vector with length units
+#[derive(Debug, Clone, Copy)]
+pub struct LengthVec3 {
+    pub x: Length,
+    pub y: Length,
+    pub z: Length,
+}
+
+/// f32 vec
+impl LengthVec3 {
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(
+            self.x.get::<meter>(),
+            self.y.get::<meter>(),
+            self.z.get::<meter>(),
+        )
+    }
+
+    /// position from a vector in m
+    pub fn from_meters(vec: Vec3) -> Self {
+        LengthVec3 {
+            x: Length::new::<meter>(vec.x),
+            y: Length::new::<meter>(vec.y),
+            z: Length::new::<meter>(vec.z),
+        }
+    }
+
+    /// projection on a unit vector, e.g. k̂·r
+    pub fn dot(&self, unit_vector: Vec3) -> Length {
+        self.x * unit_vector.x + self.y * unit_vector.y + self.z * unit_vector.z
+    }
+}
+
+/// equation of travelling wave: u(x,t)=Acos(kx−ωt)
+/// nice explanation https://physics.stackexchange.com/a/259007
+/// returns a unit-less vector to be shared between electromagnetic and non electromagnetic wave
+pub fn calculate_u_raw(x: Length, t: Time, p: &RawWaveParameters, unit_vector: Vec3) -> Vec3 {
+    let scalar = calculate_u_scalar_raw(x, t, p).cos();
+    unit_vector * (p.amplitude * scalar)
+}
+
+/// plane wave travelling along k̂ in 3d: u(r,t)=Acos(k·r−ωt+φ)
+/// with k̂ = x and r = (x, 0, 0) this is calculate_u_raw
+pub fn calculate_u_raw_3d(
+    position: &LengthVec3,
+    t: Time,
+    p: &RawWaveParameters,
+    propagation: Vec3,
+    unit_vector: Vec3,
+) -> Vec3 {
+    let scalar = calculate_u_scalar_raw_3d(position, t, p, propagation).cos();
+    unit_vector * (p.amplitude * scalar)
+}
+
+/// the argument of the wave function in 3d, k·r∓ωt+φ, in radians
+/// the wave fronts are the planes perpendicular to k̂, so only the projection k̂·r matters
+pub fn calculate_u_scalar_raw_3d(
+    position: &LengthVec3,
+    t: Time,
+    p: &RawWaveParameters,
+    propagation: Vec3,
+) -> f32 {
+    calculate_u_scalar_raw(position.dot(propagation.normalize()), t, p)
+}
+
+/// the argument of the wave function, kx∓ωt+φ depending on the direction, in radians
+pub fn calculate_u_scalar_raw(x: Length, t: Time, p: &RawWaveParameters) -> f32 {
+    // wave cycles per unit distance
+    // there might be reciprocal units on uom? (1/meter here), for now implicit
+    let k = 2.0 * PI / p.wave_length.get::<meter>();
+
+    let angular_frequency = 2.0 * PI * p.frequency.get::<hertz>();
+
+    (k * x.get::<meter>())
+        + p.direction.time_sign() * (angular_frequency * t.get::<second>())
+        + p.phase.get::<radian>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wave(amplitude: f32, wave_length: f32, frequency: f32, phase: f32) -> WaveParameters {
+        WaveParameters {
+            amplitude: Length::new::<meter>(amplitude),
+            wave_length: Length::new::<meter>(wave_length),
+            frequency: Frequency::new::<hertz>(frequency),
+            phase: Angle::new::<radian>(phase),
+            direction: PropagationDirection::Forward,
+        }
+    }
+
+    #[test]
+    fn superposition_is_the_sum_of_the_waves() {
+        let waves = [wave(1.0, 2.0, 0.5, 0.0), wave(0.3, 0.7, 3.0, 1.2)];
+        for (x, t) in [(0.0, 0.0), (0.4, 1.3), (-2.5, 0.7)] {
+            let (x, t) = (Length::new::<meter>(x), Time::new::<second>(t));
+            let sum = calculate_u_superposition(x, t, &waves, Vec3::Y).to_vec3();
+            let expected = waves
+                .iter()
+                .map(|p| calculate_u(x, t, p, Vec3::Y).to_vec3())
+                .sum::<Vec3>();
+            assert!((sum - expected).length() < 1e-6);
+            assert_eq!(sum.x, 0.0);
+        }
+    }
+
+    #[test]
+    fn waves_in_antiphase_cancel() {
+        let waves = [wave(1.0, 2.0, 0.5, 0.0), wave(1.0, 2.0, 0.5, PI)];
+        for x in [0.0, 0.3, 1.1] {
+            let u = calculate_u_superposition(
+                Length::new::<meter>(x),
+                Time::new::<second>(0.8),
+                &waves,
+                Vec3::Y,
+            );
+            assert!(u.y.get::<meter>().abs() < 1e-6);
+        }
+        assert_eq!(
+            calculate_u_superposition(
+                Length::new::<meter>(1.0),
+                Time::new::<second>(0.0),
+                &[],
+                Vec3::Y
+            )
+            .to_vec3(),
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn backward_wave_travels_towards_minus_x() {
+        let mut p = wave(1.0, 2.0, 0.5, 0.0);
+        p.direction = PropagationDirection::Backward;
+        let raw = RawWaveParameters::from(p);
+        // after t the crest at x = 0 moved to x = −vt, v = fλ = 1 m/s
+        let crest =
+            calculate_u_scalar_raw(Length::new::<meter>(-0.3), Time::new::<second>(0.3), &raw);
+        assert!(crest.abs() < 1e-6);
+    }
+}
@@ -0,0 +1,243 @@
+use std::f32::consts::PI;
+
+use glam::Vec3;
+use uom::si::{
+    angle::radian,
+    electric_field::volt_per_meter,
+    f32::{Angle, ElectricField, Frequency, Length, Time, Velocity},
+    frequency::hertz,
+    length::meter,
+    velocity::meter_per_second,
+};
+
+use crate::{
+    electromagnetic_wave::{
+        calculate_u_polarized, ElectricFieldVec3, ElectromagneticWaveParameters, JonesVector,
+    },
+    medium::Medium,
+    wave::{calculate_u_raw, calculate_u_scalar_raw, PropagationDirection, RawWaveParameters},
+};
+
+/// number of sinusoids a packet is built from
+/// the sum repeats every 2π/Δk ≈ 42σ, far enough to only see a single envelope
+pub const PACKET_COMPONENTS: i32 = 41;
+
+/// the components cover the gaussian spectrum up to this many standard deviations from the carrier
+const SPECTRUM_WIDTH: f32 = 3.0;
+
+/// gaussian wave packet travelling towards +x: a carrier under the envelope exp(−(x−v_g·t)²/(2σ²)),
+/// built by superposing waves with wave numbers around the carrier's
+/// without medium the dispersion is linear around the carrier, ω(k) = ω₀ + v_g(k − k₀), so the
+/// envelope keeps its shape while the crests move through it with the phase velocity
+/// in a dispersive medium the components follow its ω(k) and the envelope spreads
+#[derive(Debug, Clone, Copy)]
+pub struct WavePacketParameters {
+    /// peak of the envelope, m for mechanical waves, V/m for em waves
+    pub amplitude: f32,
+    /// of the carrier
+    pub wave_length: Length,
+    /// of the carrier
+    pub frequency: Frequency,
+    pub phase: Angle,
+    /// standard deviation σ of the envelope
+    pub envelope_width: Length,
+    /// should be the medium's v_g at the carrier if there's a medium
+    pub group_velocity: Velocity,
+    pub medium: Option<Medium>,
+}
+
+impl WavePacketParameters {
+    /// v_p = fλ, the speed of the crests
+    pub fn phase_velocity(&self) -> Velocity {
+        self.frequency * self.wave_length
+    }
+
+    /// center of the envelope
+    pub fn group_position(&self, t: Time) -> Length {
+        self.group_velocity * t
+    }
+
+    /// the carrier crest closest to the center of the envelope
+    /// it moves with the phase velocity, once it's half a wave length away from the center the next
+    /// crest is returned
+    pub fn phase_position(&self, t: Time) -> Length {
+        let wave_length = self.wave_length.get::<meter>();
+        let k = 2.0 * PI / wave_length;
+        // kx − ωt + φ = 0
+        let crest = (self.phase_velocity() * t).get::<meter>() - self.phase.get::<radian>() / k;
+        let center = self.group_position(t).get::<meter>();
+        let n = ((center - crest) / wave_length).round();
+        Length::new::<meter>(crest + n * wave_length)
+    }
+
+    /// waves with gaussian distributed wave numbers around k₀, σ_k = 1/σ
+    /// the amplitudes add up to the amplitude of the packet at the center of the envelope
+    pub fn components(&self) -> Vec<RawWaveParameters> {
+        let k0 = 2.0 * PI / self.wave_length.get::<meter>();
+        let omega0 = 2.0 * PI * self.frequency.get::<hertz>();
+        let group_velocity = self.group_velocity.get::<meter_per_second>();
+        let sigma_k = 1.0 / self.envelope_width.get::<meter>();
+
+        let half = PACKET_COMPONENTS / 2;
+        let dk = SPECTRUM_WIDTH * sigma_k / half as f32;
+
+        let weight = |j: i32| {
+            let offset = j as f32 * dk / sigma_k;
+            (-offset * offset / 2.0).exp()
+        };
+        let total: f32 = (-half..=half).map(weight).sum();
+
+        (-half..=half)
+            .filter_map(|j| {
+                let k = k0 + j as f32 * dk;
+                // a wave length has to be positive, only happens for envelopes shorter than λ/3
+                // also skips components the medium doesn't support
+                if k <= 0.0 {
+                    return None;
+                }
+                let omega = match &self.medium {
+                    Some(medium) => medium.angular_frequency_raw(k)?,
+                    None => omega0 + group_velocity * (k - k0),
+                };
+                Some(RawWaveParameters {
+                    amplitude: self.amplitude * weight(j) / total,
+                    wave_length: Length::new::<meter>(2.0 * PI / k),
+                    frequency: Frequency::new::<hertz>(omega / (2.0 * PI)),
+                    phase: self.phase,
+                    direction: PropagationDirection::Forward,
+                })
+            })
+            .collect()
+    }
+}
+
+/// sum of the components, pass [`WavePacketParameters::components`]
+/// taking the components instead of the packet avoids rebuilding them for each x
+pub fn calculate_u_packet_raw(
+    x: Length,
+    t: Time,
+    components: &[RawWaveParameters],
+    unit_vector: Vec3,
+) -> Vec3 {
+    components
+        .iter()
+        .map(|p| calculate_u_raw(x, t, p, unit_vector))
+        .sum()
+}
+
+/// envelope of the packet, i.e. |Σ aⱼe^{i(kⱼx−ωⱼt)}|, the carrier oscillates between ± this value
+pub fn calculate_envelope_raw(x: Length, t: Time, components: &[RawWaveParameters]) -> f32 {
+    let (re, im) = components
+        .iter()
+        .map(|p| {
+            let argument = calculate_u_scalar_raw(x, t, p);
+            (p.amplitude * argument.cos(), p.amplitude * argument.sin())
+        })
+        .fold((0.0, 0.0), |(re, im), (c, s)| (re + c, im + s));
+    (re * re + im * im).sqrt()
+}
+
+/// electric field of an em packet, every component has the polarization of the carrier
+/// see [`calculate_u_polarized`]
+pub fn calculate_u_packet_polarized(
+    x: Length,
+    t: Time,
+    components: &[RawWaveParameters],
+    jones: &JonesVector,
+    unit_vector1: Vec3,
+    unit_vector2: Vec3,
+) -> ElectricFieldVec3 {
+    let raw: Vec3 = components
+        .iter()
+        .map(|p| {
+            let pars = ElectromagneticWaveParameters {
+                amplitude: ElectricField::new::<volt_per_meter>(p.amplitude),
+                wave_length: p.wave_length,
+                frequency: p.frequency,
+                phase: p.phase,
+            };
+            calculate_u_polarized(x, t, &pars, jones, unit_vector1, unit_vector2).to_vec3()
+        })
+        .sum();
+    ElectricFieldVec3 {
+        x: ElectricField::new::<volt_per_meter>(raw.x),
+        y: ElectricField::new::<volt_per_meter>(raw.y),
+        z: ElectricField::new::<volt_per_meter>(raw.z),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uom::si::time::second;
+
+    use super::*;
+
+    fn packet(group_velocity: f32, medium: Option<Medium>) -> WavePacketParameters {
+        let wave_length = Length::new::<meter>(1.0);
+        WavePacketParameters {
+            amplitude: 1.0,
+            wave_length,
+            frequency: match &medium {
+                Some(medium) => medium.frequency(wave_length).unwrap(),
+                None => Frequency::new::<hertz>(2.0),
+            },
+            phase: Angle::new::<radian>(0.3),
+            envelope_width: Length::new::<meter>(4.0),
+            group_velocity: Velocity::new::<meter_per_second>(group_velocity),
+            medium,
+        }
+    }
+
+    /// x in m where the envelope is largest
+    fn envelope_peak(components: &[RawWaveParameters], t: Time) -> f32 {
+        (0..4000)
+            .map(|i| -10.0 + i as f32 * 0.01)
+            .map(|x| {
+                (
+                    x,
+                    calculate_envelope_raw(Length::new::<meter>(x), t, components),
+                )
+            })
+            .fold(
+                (0.0, f32::MIN),
+                |max, (x, e)| if e > max.1 { (x, e) } else { max },
+            )
+            .0
+    }
+
+    #[test]
+    fn envelope_travels_with_the_group_velocity() {
+        let p = packet(0.5, None);
+        let components = p.components();
+        let t = Time::new::<second>(20.0);
+        assert!((envelope_peak(&components, t) - p.group_position(t).get::<meter>()).abs() < 0.02);
+        let peak = calculate_envelope_raw(p.group_position(t), t, &components);
+        assert!((peak - 1.0).abs() < 1e-3);
+
+        // the crest next to the center moves with the phase velocity, fλ = 2 m/s
+        let crest = p.phase_position(t);
+        let u = calculate_u_packet_raw(crest, t, &components, Vec3::Y).y;
+        assert!((u - calculate_envelope_raw(crest, t, &components)).abs() < 1e-3);
+        assert!((p.phase_velocity().get::<meter_per_second>() - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn envelope_travels_with_the_group_velocity_of_the_medium() {
+        // ω = √(gk): v_g = v_p/2
+        let medium = Medium::DeepWater;
+        let wave_length = Length::new::<meter>(1.0);
+        let group_velocity = medium.group_velocity(wave_length).unwrap();
+        let phase_velocity = medium.phase_velocity(wave_length).unwrap();
+        assert!(
+            ((group_velocity * 2.0 - phase_velocity) / phase_velocity)
+                .value
+                .abs()
+                < 1e-3
+        );
+
+        let p = packet(group_velocity.get::<meter_per_second>(), Some(medium));
+        let t = Time::new::<second>(20.0);
+        let expected = p.group_position(t).get::<meter>();
+        assert!((envelope_peak(&p.components(), t) - expected).abs() < 0.1);
+    }
+}